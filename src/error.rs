@@ -1,4 +1,5 @@
 use self::super::util::uppercase_first;
+use chrono::{FixedOffset, DateTime};
 use std::io::Write;
 
 
@@ -41,6 +42,113 @@ pub enum Error {
     },
     /// Failed to log in to the specified service.
     LoginFailed(&'static str),
+    /// A GitHub event was malformed.
+    EventParsingFailed {
+        /// The event's ID, as specified in the event JSON.
+        id: String,
+        /// The event's type, or `"unknown"` if it's missing.
+        tp: String,
+        /// The dot-separated JSON path to the malformed value, with array indices in brackets,
+        /// e.g. `"payload.issue.number"` or `"payload.commits[0].message"`.
+        path: String,
+    },
+    /// GitHub rejected the token (401 Unauthorized).
+    GitHubUnauthorised {
+        /// The response's status code.
+        status: u16,
+        /// The requested URL.
+        url: String,
+        /// The `message` GitHub sent, if any.
+        message: Option<String>,
+    },
+    /// GitHub refused access to a resource (403 Forbidden).
+    GitHubForbidden {
+        /// The response's status code.
+        status: u16,
+        /// The requested URL.
+        url: String,
+        /// The `message` GitHub sent, if any.
+        message: Option<String>,
+    },
+    /// A resource doesn't exist on GitHub or isn't visible with the token (404 Not Found).
+    GitHubNotFound {
+        /// The response's status code.
+        status: u16,
+        /// The requested URL.
+        url: String,
+        /// The `message` GitHub sent, if any.
+        message: Option<String>,
+    },
+    /// GitHub failed to handle a request (5xx).
+    GitHubServerError {
+        /// The response's status code.
+        status: u16,
+        /// The requested URL.
+        url: String,
+        /// The `message` GitHub sent, if any.
+        message: Option<String>,
+    },
+    /// GitHub's (primary or secondary) rate limit was hit, or requests are paused until it resets.
+    GitHubRateLimited {
+        /// The requested URL.
+        url: String,
+        /// The `message` GitHub sent, if any, none if the request wasn't sent.
+        message: Option<String>,
+        /// When requests can resume, if known.
+        until: Option<DateTime<FixedOffset>>,
+    },
+    /// A request got no response, e.g. because the connection was reset or the host couldn't be resolved.
+    Network {
+        /// What was requested.
+        desc: &'static str,
+        /// The failed operation, like `Io`'s.
+        op: &'static str,
+        /// The requested URL.
+        url: String,
+    },
+    /// A request exceeded the connect, read, or overall timeout, see `ops::http::HttpConfig`.
+    Timeout {
+        /// What was requested.
+        desc: &'static str,
+        /// The failed operation, like `Io`'s.
+        op: &'static str,
+        /// The requested URL.
+        url: String,
+    },
+    /// The proxy refused to connect a request through, e.g. because the credentials were wrong, see `ops::proxy::Proxy`.
+    ProxyRefused {
+        /// What was requested.
+        desc: &'static str,
+        /// The requested URL.
+        url: String,
+        /// Why the proxy refused.
+        reason: String,
+    },
+    /// Discord failed to handle a request (5xx), e.g. with a gateway error.
+    DiscordServerError {
+        /// What was requested.
+        desc: &'static str,
+        /// The response's status code.
+        status: u16,
+    },
+    /// Discord rejected a request, e.g. because the bot can't post to the channel, or its rate limit was hit too many times.
+    DiscordRequestFailed {
+        /// What was requested.
+        desc: &'static str,
+        /// The response's status code.
+        status: u16,
+        /// The `message` Discord sent, if any.
+        message: Option<String>,
+    },
+    /// GitHub responded with any other unexpected status.
+    GitHubUnexpectedStatus {
+        /// The response's status code.
+        status: u16,
+        /// The requested URL.
+        url: String,
+        /// The `message` GitHub sent, if any.
+        message: Option<String>,
+    },
 }
 
 impl Error {
@@ -74,17 +182,46 @@ impl Error {
                     writeln!(err_out, "  {}", err).unwrap()
                 }
             }
-            Error::Io { desc, op } => {
-                // Strip the last 'e', if any, so we get correct inflection for continuous times
-                let op = uppercase_first(if op.ends_with('e') {
-                    &op[..op.len() - 1]
-                } else {
-                    op
-                });
-                writeln!(err_out, "{}ing {} failed.", op, desc).unwrap()
-            }
+            Error::Io { desc, op } => writeln!(err_out, "{} {} failed.", continuous(op), desc).unwrap(),
             Error::WatchedDoesNotExist { tp, ref name } => writeln!(err_out, "The watched {} \"{}\" doesn't exist.", tp, name).unwrap(),
             Error::LoginFailed(service) => writeln!(err_out, "Failed to log in to {}.", service).unwrap(),
+            Error::EventParsingFailed { ref id, ref tp, ref path } => {
+                writeln!(err_out, "Failed to parse {} {}: \"{}\" is malformed.", tp, id, path).unwrap()
+            }
+            Error::GitHubUnauthorised { status, ref url, ref message } => {
+                writeln!(err_out, "GitHub rejected the token for {} ({}), check it with dishub init --force.", url, status_message(status, message)).unwrap()
+            }
+            Error::GitHubForbidden { status, ref url, ref message } => {
+                writeln!(err_out, "GitHub refused access to {} ({}).", url, status_message(status, message)).unwrap()
+            }
+            Error::GitHubNotFound { status, ref url, ref message } => {
+                writeln!(err_out, "{} wasn't found on GitHub ({}).", url, status_message(status, message)).unwrap()
+            }
+            Error::GitHubServerError { status, ref url, ref message } => {
+                writeln!(err_out, "GitHub failed to handle {} ({}).", url, status_message(status, message)).unwrap()
+            }
+            Error::GitHubRateLimited { ref url, ref message, until } => {
+                write!(err_out, "GitHub's rate limit was hit for {}", url).unwrap();
+                if let Some(ref message) = *message {
+                    write!(err_out, " (\"{}\")", message).unwrap();
+                }
+                match until {
+                    Some(until) => writeln!(err_out, ", pausing until {}.", until.format("%H:%M:%S")).unwrap(),
+                    None => writeln!(err_out, ".").unwrap(),
+                }
+            }
+            Error::Network { desc, op, ref url } => writeln!(err_out, "{} {} failed: no response from {}.", continuous(op), desc, url).unwrap(),
+            Error::Timeout { desc, op, ref url } => writeln!(err_out, "{} {} timed out waiting for {}.", continuous(op), desc, url).unwrap(),
+            Error::ProxyRefused { desc, ref url, ref reason } => {
+                writeln!(err_out, "The proxy refused to connect to {} for the {}: {}.", url, desc, reason).unwrap()
+            }
+            Error::DiscordServerError { desc, status } => writeln!(err_out, "Discord failed to handle the {} ({}).", desc, status).unwrap(),
+            Error::DiscordRequestFailed { desc, status, ref message } => {
+                writeln!(err_out, "Discord rejected the {} ({}).", desc, status_message(status, message)).unwrap()
+            }
+            Error::GitHubUnexpectedStatus { status, ref url, ref message } => {
+                writeln!(err_out, "Unexpected response from GitHub for {} ({}).", url, status_message(status, message)).unwrap()
+            }
         }
     }
 
@@ -107,6 +244,59 @@ impl Error {
             Error::Io { .. } => 4,
             Error::WatchedDoesNotExist { .. } => 5,
             Error::LoginFailed(_) => 6,
+            Error::EventParsingFailed { .. } => 7,
+            Error::GitHubUnauthorised { .. } => 8,
+            Error::GitHubForbidden { .. } => 9,
+            Error::GitHubNotFound { .. } => 10,
+            Error::GitHubServerError { .. } => 11,
+            Error::GitHubUnexpectedStatus { .. } => 12,
+            Error::GitHubRateLimited { .. } => 13,
+            Error::Network { .. } => 14,
+            Error::DiscordServerError { .. } => 15,
+            Error::Timeout { .. } => 16,
+            Error::ProxyRefused { .. } => 17,
+            Error::DiscordRequestFailed { .. } => 18,
+        }
+    }
+
+    /// Check whether the error is transient, and so the failed operation can be retried, see `ops::retry::RetryPolicy`.
+    ///
+    /// These are requests without a response (`Network`) or that timed out, and server errors from GitHub and Discord.
+    ///
+    /// Posts that timed out aren't, since the message may have been posted anyway, and posting it again would duplicate it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dishub::Error;
+    /// assert!(Error::DiscordServerError { desc: "event message", status: 502 }.is_transient());
+    /// assert!(!Error::Io { desc: "event message", op: "post" }.is_transient());
+    /// ```
+    pub fn is_transient(&self) -> bool {
+        match *self {
+            Error::Network { .. } |
+            Error::GitHubServerError { .. } |
+            Error::DiscordServerError { .. } => true,
+            Error::Timeout { op, .. } => op != "post",
+            _ => false,
         }
     }
 }
+
+
+/// Get the continuous form of the specified lowercase imperative operation, capitalised, as in "Opening".
+fn continuous(op: &str) -> String {
+    // Strip the last 'e', if any, so we get correct inflection for continuous times
+    uppercase_first(if op.ends_with('e') {
+        &op[..op.len() - 1]
+    } else {
+        op
+    }) + "ing"
+}
+
+fn status_message(status: u16, message: &Option<String>) -> String {
+    match *message {
+        Some(ref message) => format!("{}: \"{}\"", status, message),
+        None => status.to_string(),
+    }
+}
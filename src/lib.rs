@@ -19,6 +19,18 @@
 //! 4 - An I/O error au general
 //! 5 - Watched subject does not exist
 //! 6 - Failed to log in to a service
+//! 7 - Couldn't parse a GitHub event
+//! 8 - GitHub rejected the token
+//! 9 - GitHub refused access to a resource
+//! 10 - A resource doesn't exist on GitHub
+//! 11 - GitHub failed to handle a request
+//! 12 - GitHub responded with an unexpected status
+//! 13 - GitHub's rate limit was hit
+//! 14 - A request got no response
+//! 15 - Discord failed to handle a request
+//! 16 - A request timed out
+//! 17 - The proxy refused to connect a request
+//! 18 - Discord rejected a request
 //! ```
 //!
 //! ## Executable manpage
@@ -30,10 +42,12 @@ extern crate rustc_serialize;
 #[macro_use]
 extern crate lazy_static;
 extern crate discord;
+extern crate openssl;
 extern crate chrono;
 extern crate regex;
 #[macro_use]
 extern crate hyper;
+#[macro_use]
 extern crate json;
 #[macro_use]
 extern crate clap;
@@ -1,5 +1,6 @@
 extern crate dishub;
 
+use std::env;
 use std::thread;
 use std::process::exit;
 use std::time::Duration;
@@ -23,12 +24,15 @@ fn actual_main() -> i32 {
 fn result_main() -> Result<(), dishub::Error> {
     let opts = dishub::options::Options::parse();
     println!("{:#?}", opts);
+    let config = try!(dishub::ops::AppConfig::read(&opts.config_dir.1.join("config.toml")));
+    dishub::ops::http::configure(try!(config.http_config(opts.http.clone(), |var| env::var(var).ok())));
+    dishub::ops::github::configure(config.github_urls());
 
     match opts.subsystem {
         dishub::options::Subsystem::Init { force } => init_main(opts, force),
         dishub::options::Subsystem::AddFeeds => add_feeds_main(opts),
         dishub::options::Subsystem::UnfollowFeeds => unfollow_feeds_main(opts),
-        dishub::options::Subsystem::StartDaemon { sleep } => start_daemon_main(opts, sleep),
+        dishub::options::Subsystem::StartDaemon { sleep, retry } => start_daemon_main(opts, sleep, retry),
     }
 }
 
@@ -38,7 +42,8 @@ fn init_main(opts: dishub::options::Options, force: bool) -> Result<(), dishub::
     let stdin = stdin();
     let mut lock = stdin.lock();
 
-    let data = dishub::ops::init::get_data(&mut lock, &mut stdout());
+    let mut data = dishub::ops::init::get_data(&mut lock, &mut stdout());
+    data.github_instances = dishub::ops::AppTokens::read(&data_path).ok().and_then(|tokens| tokens.github_instances);
     data.write(&data_path);
 
     println!("");
@@ -54,9 +59,10 @@ fn add_feeds_main(opts: dishub::options::Options) -> Result<(), dishub::Error> {
     let mut lock = stdin.lock();
 
     let subject = dishub::ops::add_feeds::get_watch_subject(&mut lock, &mut stdout());
+    let urls = dishub::ops::add_feeds::get_github_urls(&mut lock, &mut stdout());
     let tokens = try!(dishub::ops::AppTokens::read(&data_path));
 
-    try!(dishub::ops::add_feeds::verify_subject(&subject, &tokens));
+    try!(dishub::ops::add_feeds::verify_subject(&subject, urls.as_ref().unwrap_or(&dishub::ops::github::urls()), &tokens));
 
     let servers = try!(dishub::ops::add_feeds::known_servers(&tokens));
     let server = dishub::ops::add_feeds::get_valid_server(servers, &mut lock, &mut stdout());
@@ -64,8 +70,16 @@ fn add_feeds_main(opts: dishub::options::Options) -> Result<(), dishub::Error> {
     let channels = try!(dishub::ops::add_feeds::channels_in_server(&tokens, server));
     let channel = dishub::ops::add_feeds::get_valid_channel(channels, &mut lock, &mut stdout());
 
+    let templates = dishub::ops::add_feeds::get_templates(&mut lock, &mut stdout());
+
     let mut feeds = try!(dishub::ops::Feed::read(&feeds_path));
-    feeds.push(dishub::ops::Feed::new(subject, server, channel));
+    let mut feed = dishub::ops::Feed::new(subject, server, channel);
+    feed.templates = templates;
+    if let Some(urls) = urls {
+        feed.api_url = Some(urls.api);
+        feed.web_url = Some(urls.web);
+    }
+    feeds.push(feed);
     dishub::ops::Feed::write(feeds, &feeds_path);
 
     Ok(())
@@ -91,19 +105,40 @@ fn unfollow_feeds_main(opts: dishub::options::Options) -> Result<(), dishub::Err
     Ok(())
 }
 
-fn start_daemon_main(opts: dishub::options::Options, sleep: Duration) -> Result<(), dishub::Error> {
+fn start_daemon_main(opts: dishub::options::Options, sleep: Duration, retry: dishub::ops::retry::RetryPolicy) -> Result<(), dishub::Error> {
     let (tokens_path, feeds_path) = try!(dishub::ops::start_daemon::verify(&opts.config_dir));
     let tokens = try!(dishub::ops::AppTokens::read(&tokens_path));
 
     loop {
         let mut feeds = try!(dishub::ops::Feed::read(&feeds_path));
+        let mut rate_limited = vec![];
 
         for feed in feeds.iter_mut().filter(|f| dishub::ops::start_daemon::feeds_filter(&mut stdout(), f)) {
-            let events = try!(feed.poll(&tokens));
+            let api_url = feed.github_urls().api;
+            if rate_limited.contains(&api_url) {
+                continue;
+            }
+
+            let (events, missed) = match feed.poll(&tokens, &retry, &mut stderr()) {
+                Ok(polled) => polled,
+                Err(err @ dishub::Error::GitHubRateLimited { .. }) => {
+                    err.print_error(&mut stdout());
+                    rate_limited.push(api_url);
+                    continue;
+                }
+                Err(err) => return Err(err),
+            };
             if events.is_empty() {
                 println!("No new events in {}", feed.subject);
             } else {
-                try!(dishub::ops::start_daemon::send_messages(&tokens, events.iter().map(dishub::ops::start_daemon::post_text).collect(), feed.channel));
+                let options = feed.render_options();
+                let mut messages = vec![];
+                if missed {
+                    println!("Some events in {} were missed", feed.subject);
+                    messages.push(dishub::ops::start_daemon::missed_events_message(&feed.subject, &options));
+                }
+                messages.extend(dishub::ops::start_daemon::render_messages(&*feed.renderer(), &options, &events));
+                try!(dishub::ops::start_daemon::send_messages(&tokens, messages, feed.channel, &retry, &mut stderr()));
                 println!("Successfully sent {} event{} from {}",
                          events.len(),
                          if events.len() != 1 { "s" } else { "" },
@@ -111,6 +146,15 @@ fn start_daemon_main(opts: dishub::options::Options, sleep: Duration) -> Result<
             }
         }
 
+        for (api_url, rate_limit) in dishub::ops::github::rate_limits() {
+            if api_url == dishub::util::GITHUB_API_URL {
+                println!("GitHub rate limit: {}", rate_limit);
+            } else {
+                println!("GitHub rate limit at {}: {}", api_url, rate_limit);
+            }
+        }
+
+        let sleep = dishub::ops::start_daemon::sleep_time(sleep, &feeds);
         dishub::ops::Feed::write(feeds, &feeds_path);
         thread::sleep(sleep);
     }
@@ -7,6 +7,8 @@
 //! |> ops::add_feeds::verify()
 //! // Read the subject
 //! |> ops::add_feeds::get_watch_subject()
+//! // Ask the user which GitHub instance it's on, if not the installation's
+//! |> ops::add_feeds::get_github_urls()
 //! |> ops::AppTokens::read()
 //! // Check if the subject exists
 //! |> ops::add_feeds::verify_subject()
@@ -16,6 +18,8 @@
 //! // List channels in the specified server and ask the user which one to post in.
 //! |> ops::add_feeds::channels_in_server()
 //! |> ops::add_feeds::get_valid_channel()
+//! // Ask the user for templates for the feed's events
+//! |> ops::add_feeds::get_templates()
 //! // Update the feed list
 //! |> ops::Feed::read()
 //! |> ops::Feed::new()
@@ -23,10 +27,13 @@
 //! ```
 
 
-use self::super::{AppTokens, Feed, github, verify_file};
-use self::super::super::util::prompt_nonzero_len;
+use self::super::super::util::{prompt_nonzero_len, prompt_multiline, prompt_any_len};
+use self::super::{AppTokens, EventPayload, Feed, github, verify_file};
+use self::super::github::GitHubUrls;
 use discord::model::{ChannelType, ServerId};
+use std::collections::BTreeMap;
 use self::super::super::Error;
+use self::super::render::Template;
 use std::io::{BufRead, Write};
 use std::path::PathBuf;
 use std::str::FromStr;
@@ -111,7 +118,54 @@ pub fn get_watch_subject<R: BufRead, W: Write>(input: &mut R, output: &mut W) ->
         .unwrap()
 }
 
-/// Verify, whether the subject exists.
+/// Prompt the user for the GitHub instance the subject is on, or `None` for the installation's (see `github::urls()`).
+///
+/// The instance is given by its API base URL, and its web interface's base URL is prompted for
+/// only if it can't be derived from that, see `GitHubUrls::web_url_for()`; reprompts if given something that isn't a URL.
+///
+/// # Examples
+///
+/// ```
+/// # use dishub::ops::add_feeds;
+/// # use dishub::ops::github::GitHubUrls;
+/// # use std::io::BufReader;
+/// assert_eq!(add_feeds::get_github_urls(&mut BufReader::new(&b"\n"[..]), &mut Vec::new()), None);
+/// assert_eq!(add_feeds::get_github_urls(&mut BufReader::new(&b"https://ghe.example.com/api/v3/\n"[..]), &mut Vec::new()),
+///            Some(GitHubUrls {
+///                api: "https://ghe.example.com/api/v3".to_string(),
+///                web: "https://ghe.example.com".to_string(),
+///            }));
+/// assert_eq!(add_feeds::get_github_urls(&mut BufReader::new(&b"https://git.example.com/github-api\n\
+///                                                               https://git.example.com\n"[..]), &mut Vec::new()),
+///            Some(GitHubUrls {
+///                api: "https://git.example.com/github-api".to_string(),
+///                web: "https://git.example.com".to_string(),
+///            }));
+/// ```
+pub fn get_github_urls<R: BufRead, W: Write>(input: &mut R, output: &mut W) -> Option<GitHubUrls> {
+    let api = loop {
+        match prompt_any_len(input, output, "GitHub instance API URL (or empty for the default)", |_| true).unwrap() {
+            Some(api) => {
+                match GitHubUrls::base_url(&api) {
+                    Some(api) => break api,
+                    None => writeln!(output, "Invalid URL {}", api).unwrap(),
+                }
+            }
+            None => return None,
+        }
+    };
+    let web = match GitHubUrls::web_url_for(&api) {
+        Some(web) => web,
+        None => GitHubUrls::base_url(&prompt_nonzero_len(input, output, "GitHub instance web URL", |s| GitHubUrls::base_url(s).is_some()).unwrap()).unwrap(),
+    };
+
+    Some(GitHubUrls {
+        api: api,
+        web: web,
+    })
+}
+
+/// Verify, whether the subject exists on the specified GitHub instance.
 ///
 /// # Examples
 ///
@@ -119,38 +173,42 @@ pub fn get_watch_subject<R: BufRead, W: Write>(input: &mut R, output: &mut W) ->
 ///
 /// ```
 /// # use dishub::ops::{add_feeds, AppTokens};
+/// # use dishub::ops::github::GitHubUrls;
 /// # let tokens = AppTokens {
 /// #     github: "".to_string(),
 /// #     discord: "".to_string(),
+/// #     github_instances: None,
 /// # };
-/// assert!(add_feeds::verify_subject("nabijaczleweli", &tokens).is_ok());
-/// assert!(add_feeds::verify_subject("nabijaczleweli/dishub", &tokens).is_ok());
+/// assert!(add_feeds::verify_subject("nabijaczleweli", &GitHubUrls::default(), &tokens).is_ok());
+/// assert!(add_feeds::verify_subject("nabijaczleweli/dishub", &GitHubUrls::default(), &tokens).is_ok());
 /// ```
 ///
 /// Non-existant subject.
 ///
 /// ```no_run
 /// # use dishub::ops::{add_feeds, AppTokens};
+/// # use dishub::ops::github::GitHubUrls;
 /// # use dishub::Error;
 /// # let tokens = AppTokens {
 /// #     github: "".to_string(),
 /// #     discord: "".to_string(),
+/// #     github_instances: None,
 /// # };
-/// assert_eq!(add_feeds::verify_subject("3f0ada6056fe3fc/67a6682230bf1cb6d", &tokens),
+/// assert_eq!(add_feeds::verify_subject("3f0ada6056fe3fc/67a6682230bf1cb6d", &GitHubUrls::default(), &tokens),
 ///            Err(Error::WatchedDoesNotExist {
 ///                tp: "repository",
 ///                name: "3f0ada6056fe3fc/67a6682230bf1cb6d".to_string(),
 ///            }));
 /// ```
-pub fn verify_subject(subject: &str, tokens: &AppTokens) -> Result<(), Error> {
-    type ExistCheck = fn(&str, &AppTokens) -> Result<bool, Error>;
+pub fn verify_subject(subject: &str, urls: &GitHubUrls, tokens: &AppTokens) -> Result<(), Error> {
+    type ExistCheck = fn(&str, &str, &AppTokens) -> Result<bool, Error>;
     let (check_fn, tp): (ExistCheck, &str) = if subject.contains('/') {
         (github::repo_exists, "repository")
     } else {
         (github::user_exists, "user")
     };
 
-    check_fn(subject, tokens).and_then(|e| if e {
+    check_fn(subject, &urls.api, tokens).and_then(|e| if e {
         Ok(())
     } else {
         Err(Error::WatchedDoesNotExist {
@@ -178,6 +236,7 @@ pub fn verify_subject(subject: &str, tokens: &AppTokens) -> Result<(), Error> {
 /// # let tokens = AppTokens {
 /// #     github: "".to_string(),
 /// #     discord: "".to_string(),
+/// #     github_instances: None,
 /// # };
 /// let servers = try!(add_feeds::known_servers(&tokens));
 /// for (id, name) in servers {
@@ -237,6 +296,7 @@ pub fn get_valid_server<R: BufRead, W: Write>(servers: Vec<(u64, String)>, input
 /// # let tokens = AppTokens {
 /// #     github: "".to_string(),
 /// #     discord: "".to_string(),
+/// #     github_instances: None,
 /// # };
 /// let server_id = 1001;
 /// let channels = try!(add_feeds::channels_in_server(&tokens, 1001));
@@ -279,6 +339,50 @@ pub fn get_valid_channel<R: BufRead, W: Write>(channels: Vec<(u64, String)>, inp
     get_valid("Channels in the chosen server", "The channel to post the feed in", channels, input, output)
 }
 
+/// Prompt the user for templates to render the feed's events with, keyed by event type.
+///
+/// Event types are the payload type names (see `EventPayload::type_names()`) and are prompted for until an empty line,
+/// the templates themselves (see `render::Template`) are read with `util::prompt_multiline()`, reprompting with what's wrong if malformed.
+///
+/// # Examples
+///
+/// ```
+/// # use dishub::ops::add_feeds;
+/// # use std::io::BufReader;
+/// let templates = add_feeds::get_templates(&mut BufReader::new(&b"PushEvent\n\
+///                                                                 Push\n\
+///                                                                 {{actor}} pushed to {{branch}}:\\\n\
+///                                                                 {{#each payload.commits}}  {{message}}\\\n\
+///                                                                 {{/each}}\n\
+///                                                                 \n"[..]),
+///                                          &mut Vec::new());
+/// assert_eq!(templates.keys().collect::<Vec<_>>(), vec!["Push"]);
+/// assert_eq!(templates["Push"].source(), "{{actor}} pushed to {{branch}}:\n{{#each payload.commits}}  {{message}}\n{{/each}}");
+/// ```
+pub fn get_templates<R: BufRead, W: Write>(input: &mut R, output: &mut W) -> BTreeMap<String, Template> {
+    writeln!(output, "Event types: {}", EventPayload::type_names().join(", ")).unwrap();
+    writeln!(output, "").unwrap();
+
+    let mut templates = BTreeMap::new();
+    while let Some(tp) = prompt_any_len(input, output, "Event type to template (or empty to end)", |_| true).unwrap() {
+        if !EventPayload::type_names().contains(&&tp[..]) {
+            writeln!(output, "Unknown event type {}", tp).unwrap();
+            continue;
+        }
+
+        loop {
+            match Template::parse(&prompt_multiline(input, output, "Template", |_| true).unwrap()) {
+                Ok(template) => {
+                    templates.insert(tp, template);
+                    break;
+                }
+                Err(e) => writeln!(output, "Malformed template: {}", e).unwrap(),
+            }
+        }
+    }
+    templates
+}
+
 fn get_valid<R: BufRead, W: Write>(list_heading: &str, prompt: &str, instances: Vec<(u64, String)>, input: &mut R, output: &mut W) -> u64 {
     writeln!(output, "{}:", list_heading).unwrap();
     for (idx, &(_, ref name)) in instances.iter().enumerate() {
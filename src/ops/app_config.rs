@@ -0,0 +1,144 @@
+use self::super::github::GitHubUrls;
+use self::super::http::HttpConfig;
+use self::super::read_toml_file;
+use self::super::proxy::Proxy;
+use self::super::super::Error;
+use std::path::{PathBuf, Path};
+
+
+/// Installation-wide settings, all optional, read from `config.toml` in the configuration directory.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Default, RustcEncodable, RustcDecodable)]
+pub struct AppConfig {
+    /// The proxy to make requests to GitHub and Discord through, see `Proxy::parse()`.
+    ///
+    /// The environment's, see `Proxy::from_env()`, if not specified.
+    pub proxy: Option<String>,
+    /// Hosts to connect to directly, even with a proxy, see `Proxy::bypassed()`.
+    ///
+    /// The environment's, see `Proxy::no_proxy_from_env()`, if not specified.
+    pub no_proxy: Option<Vec<String>>,
+    /// The base URL of the API of the GitHub instance feeds are on, unless they specify otherwise, see `GitHubUrls::api`.
+    pub api_url: Option<String>,
+    /// The base URL of the web interface of the GitHub instance feeds are on, unless they specify otherwise, see `GitHubUrls::web`.
+    ///
+    /// Derived from `api_url`, if not specified, see `GitHubUrls::web_url_for()`; can't be specified without it.
+    pub web_url: Option<String>,
+    /// A file with additional PEM certificates to trust, e.g. an internal CA's, relative to the configuration directory.
+    ///
+    /// Absolute once read.
+    pub ca_bundle: Option<String>,
+}
+
+impl AppConfig {
+    /// Read the application configuration from the specified file, or the default one if it doesn't exist.
+    pub fn read(p: &Path) -> Result<AppConfig, Error> {
+        if !p.exists() {
+            return Ok(AppConfig::default());
+        }
+
+        let mut config: AppConfig = try!(read_toml_file(p, "App configuration"));
+        let mut errors = vec![];
+        if let Some(ref proxy) = config.proxy {
+            if Proxy::parse(proxy).is_none() {
+                errors.push(format!("Invalid proxy \"{}\"", proxy));
+            }
+        }
+        for url in config.api_url.iter_mut().chain(config.web_url.iter_mut()) {
+            match GitHubUrls::base_url(url) {
+                Some(base) => *url = base,
+                None => errors.push(format!("Invalid GitHub URL \"{}\"", url)),
+            }
+        }
+        match (&config.api_url, &config.web_url) {
+            (&Some(ref api), &None) if GitHubUrls::web_url_for(api).is_none() => {
+                errors.push(format!("Can't tell the web URL for GitHub API URL \"{}\", set web_url as well", api))
+            }
+            (&None, &Some(ref web)) => errors.push(format!("GitHub URL \"{}\" set without api_url", web)),
+            _ => {}
+        }
+        if let Some(ref mut bundle) = config.ca_bundle {
+            let path = p.parent().unwrap_or_else(|| Path::new(".")).join(&bundle);
+            if path.is_file() {
+                *bundle = path.to_string_lossy().into_owned();
+            } else {
+                errors.push(format!("CA bundle \"{}\" doesn't exist", bundle));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(config)
+        } else {
+            Err(Error::FileParsingFailed {
+                desc: "App configuration",
+                errors: errors,
+            })
+        }
+    }
+
+    /// Get the GitHub instance feeds are on unless they specify otherwise, github.com unless configured,
+    /// with the web URL derived from the API URL if only that's configured.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dishub::ops::AppConfig;
+    /// let config = AppConfig {
+    ///     api_url: Some("https://ghe.example.com/api/v3".to_string()),
+    ///     ..AppConfig::default()
+    /// };
+    /// assert_eq!(config.github_urls().api, "https://ghe.example.com/api/v3");
+    /// assert_eq!(config.github_urls().web, "https://ghe.example.com");
+    /// ```
+    pub fn github_urls(&self) -> GitHubUrls {
+        let default = GitHubUrls::default();
+        GitHubUrls {
+            api: self.api_url.clone().unwrap_or(default.api),
+            web: self.web_url.clone().or_else(|| self.api_url.as_ref().and_then(|api| GitHubUrls::web_url_for(api))).unwrap_or(default.web),
+        }
+    }
+
+    /// Add the proxy settings, falling back to the environment read with the specified getter,
+    /// and the CA bundle, to the specified HTTP configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dishub::ops::http::HttpConfig;
+    /// # use dishub::ops::AppConfig;
+    /// # use dishub::Error;
+    /// let env = |var: &str| match var {
+    ///     "HTTPS_PROXY" => Some("http://proxy.example.com:3128".to_string()),
+    ///     "NO_PROXY" => Some("localhost".to_string()),
+    ///     _ => None,
+    /// };
+    ///
+    /// let config = AppConfig { no_proxy: Some(vec![]), ..AppConfig::default() }.http_config(HttpConfig::default(), env).unwrap();
+    /// assert_eq!(config.proxy.map(|p| p.host), Some("proxy.example.com".to_string()));
+    /// assert_eq!(config.no_proxy, Vec::<String>::new());
+    ///
+    /// assert_eq!(AppConfig::default().http_config(HttpConfig::default(), |_| Some("ftp://proxy".to_string())),
+    ///            Err(Error::FileParsingFailed {
+    ///                desc: "environment",
+    ///                errors: vec!["Invalid proxy \"ftp://proxy\"".to_string()],
+    ///            }));
+    /// ```
+    pub fn http_config<F: Fn(&str) -> Option<String>>(&self, mut http: HttpConfig, env: F) -> Result<HttpConfig, Error> {
+        http.proxy = match self.proxy {
+            Some(ref proxy) => Proxy::parse(proxy),
+            None => {
+                try!(Proxy::from_env(&env).map_err(|url| {
+                    Error::FileParsingFailed {
+                        desc: "environment",
+                        errors: vec![format!("Invalid proxy \"{}\"", url)],
+                    }
+                }))
+            }
+        };
+        http.no_proxy = match self.no_proxy {
+            Some(ref no_proxy) => no_proxy.clone(),
+            None => Proxy::no_proxy_from_env(&env),
+        };
+        http.ca_bundle = self.ca_bundle.as_ref().map(PathBuf::from);
+        Ok(http)
+    }
+}
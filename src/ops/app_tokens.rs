@@ -1,3 +1,5 @@
+use self::super::github::{self, GitHubUrls};
+use std::collections::BTreeMap;
 use self::super::read_toml_file;
 use self::super::super::Error;
 use toml::encode_str;
@@ -7,14 +9,18 @@ use std::fs::File;
 
 
 /// The tokens needed to authenticate the app to GitHub.
+///
+/// Each GitHub instance is only ever sent its own token, see `github_token()`.
 #[derive(Debug, Clone, Hash, PartialEq, Eq, RustcEncodable, RustcDecodable)]
 pub struct AppTokens {
-    /// GitHub OAuth authentication token
+    /// GitHub OAuth authentication token for the installation's GitHub instance, see `github::urls()`
     ///
     /// Required scopes: public_repo, repo:status.
     pub github: String,
     /// Discord bot user token
     pub discord: String,
+    /// GitHub OAuth authentication tokens for other GitHub instances, keyed by their API base URL
+    pub github_instances: Option<BTreeMap<String, String>>,
 }
 
 impl AppTokens {
@@ -23,6 +29,44 @@ impl AppTokens {
         read_toml_file(p, "App tokens")
     }
 
+    /// Get the token for the GitHub instance with the specified API base URL:
+    /// its entry in `github_instances`, or `github` for the installation's instance, unless empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dishub::ops::AppTokens;
+    /// # use std::collections::BTreeMap;
+    /// let mut instances = BTreeMap::new();
+    /// instances.insert("https://ghe.example.com/api/v3/".to_string(), "8ffd5a8e6b4ec5e42e27c4cbd8c9c0b3a1e3d3f4".to_string());
+    /// let tokens = AppTokens {
+    ///     github: "994c365aec1700f5783bac697e2347ffd2268e1e".to_string(),
+    ///     discord: "".to_string(),
+    ///     github_instances: Some(instances),
+    /// };
+    ///
+    /// assert_eq!(tokens.github_token("https://api.github.com"), Some("994c365aec1700f5783bac697e2347ffd2268e1e"));
+    /// assert_eq!(tokens.github_token("https://ghe.example.com/api/v3"), Some("8ffd5a8e6b4ec5e42e27c4cbd8c9c0b3a1e3d3f4"));
+    /// assert_eq!(tokens.github_token("https://git.example.org/api/v3"), None);
+    /// ```
+    pub fn github_token(&self, api_url: &str) -> Option<&str> {
+        let instance = self.github_instances
+            .as_ref()
+            .and_then(|instances| instances.iter().find(|&(url, _)| GitHubUrls::base_url(url).map(|url| url == api_url).unwrap_or(false)))
+            .map(|(_, token)| &token[..]);
+        let token = match instance {
+            Some(token) => token,
+            None if api_url == github::urls().api => &self.github[..],
+            None => return None,
+        };
+
+        if token.is_empty() {
+            None
+        } else {
+            Some(token)
+        }
+    }
+
     /// Save the application GitHub tokens to the specified file.
     pub fn write(&self, p: &Path) {
         File::create(p).unwrap().write_all(encode_str(&self).as_bytes()).unwrap();
@@ -1,11 +1,24 @@
 use chrono::{FixedOffset, DateTime};
+use self::super::super::util::GITHUB_WEB_URL;
+use self::super::locale::Catalogue;
+use self::super::super::Error;
+use std::hash::{Hash, Hasher};
 use json::{self, JsonValue};
 use std::str::FromStr;
+use std::cmp;
+use regex::Regex;
 use std::fmt;
 
 
+lazy_static! {
+    static ref TRAILER_RGX: Regex = Regex::new(r"^(?i)(co-authored-by|signed-off-by):\s*(.*?)\s*<([^>]*)>\s*$").unwrap();
+}
+
+
 /// A representation of the [GitHub Event API's](https://developer.github.com/v3/activity/events) event.
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+///
+/// Hashing ignores `raw`, as all fields we understand are represented anyway.
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Event {
     /// The time this event was generated.
     ///
@@ -13,12 +26,16 @@ pub struct Event {
     pub created_at: DateTime<FixedOffset>,
     /// The person who triggered the event.
     ///
-    /// Corresponds to `actor.display_login` in the event JSON.
-    pub actor: String,
-    /// The repository slug where the event is triggered.
+    /// Corresponds to `actor` in the event JSON.
+    pub actor: Actor,
+    /// The repository where the event is triggered.
+    ///
+    /// Corresponds to `repo` in the event JSON.
+    pub repo: Repo,
+    /// The organisation the repository belongs to.
     ///
-    /// Corresponds to `repo.name` in the event JSON.
-    pub repo: String,
+    /// Corresponds to `org` in the event JSON, `None` if the repository doesn't belong to an organisation.
+    pub org: Option<Org>,
     /// The event ID.
     ///
     /// Corresponds to `id` in the event JSON (except it's a string there).
@@ -27,11 +44,17 @@ pub struct Event {
     ///
     /// Corresponds to `payload` in the event JSON, determined by `type`.
     pub payload: EventPayload,
+    /// The whole event JSON, as received from GitHub.
+    ///
+    /// Use this to get at the fields not represented above, the original payload is under the `"payload"` key.
+    ///
+    /// This is `JsonValue::Null` for events not parsed from JSON.
+    pub raw: JsonValue,
 }
 
 /// A representation of the GitHub Event API's [event payload](https://developer.github.com/v3/activity/events/types).
 ///
-/// We only represent the event types that are visible in timelines and haven't been phased out yet.
+/// We only represent the event types that are visible in timelines.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum EventPayload {
     /// A [CommitCommentEvent](https://developer.github.com/v3/activity/events/types#commitcommentevent).
@@ -67,11 +90,51 @@ pub enum EventPayload {
         /// The deleted ref's name.
         ref_name: String,
     },
+    /// A [DiscussionEvent](https://docs.github.com/en/rest/using-the-rest-api/github-event-types#discussionevent).
+    Discussion {
+        /// The action executed upon a discussion.
+        ///
+        /// Can be "created", "edited", "deleted", "pinned", "unpinned", "locked", "unlocked", "transferred",
+        /// "category_changed", "answered", "unanswered", "labeled", "unlabeled", "closed", or "reopened".
+        action: String,
+        /// The discussion number.
+        number: u64,
+        /// The discussion's title.
+        title: String,
+        /// The discussion body's text.
+        body: String,
+        /// The name of the discussion's category.
+        category: String,
+    },
+    /// A [DiscussionCommentEvent](https://docs.github.com/en/rest/using-the-rest-api/github-event-types#discussioncommentevent).
+    DiscussionComment {
+        /// The action executed upon a discussion comment.
+        ///
+        /// Can be "created", "edited", or "deleted".
+        action: String,
+        /// The discussion number.
+        discussion: u64,
+        /// The discussion comment's body text.
+        body: String,
+        /// The discussion comment's ID.
+        id: u64,
+    },
     /// A [ForkEvent](https://developer.github.com/v3/activity/events/types#forkevent).
     Fork {
         /// The fork's slug.
         new_slug: String,
     },
+    /// A [ForkApplyEvent](https://developer.github.com/v3/activity/events/types#forkapplyevent).
+    ///
+    /// A patch was applied in the Fork Queue.
+    ForkApply {
+        /// The branch name the patch was applied to.
+        head: String,
+        /// The SHA of the branch before the patch was applied.
+        before: String,
+        /// The SHA of the branch after the patch was applied.
+        after: String,
+    },
     /// A [GollumEvent](https://developer.github.com/v3/activity/events/types#gollumevent) (a.k.a. a WikiEvent).
     Gollum {
         /// The affected Wiki pages.
@@ -80,11 +143,11 @@ pub enum EventPayload {
     /// An [IssueCommentEvent](https://developer.github.com/v3/activity/events/types#issuecommentevent).
     IssueComment {
         /// The action executed upon a comment.
-        ///
-        /// Can be "created", "edited", or "deleted".
-        action: String,
+        action: IssueCommentAction,
         /// The issue number.
         issue: u64,
+        /// Whether the issue is a pull request, i.e. the comment was made in the PR's conversation.
+        pull_request: bool,
         /// The issue comment's body text.
         body: String,
         /// The issue comment's ID.
@@ -93,10 +156,7 @@ pub enum EventPayload {
     /// An [IssuesEvent](https://developer.github.com/v3/activity/events/types#issuesevent).
     Issues {
         /// The action executed upon an issue.
-        ///
-        /// Can be "assigned", "unassigned", "labeled", "unlabeled", "opened", "edited", "milestoned", "demilestoned",
-        /// "closed", or "reopened".
-        action: String,
+        action: IssuesAction,
         /// The issue number.
         number: u64,
         /// The issue's title.
@@ -105,13 +165,15 @@ pub enum EventPayload {
         body: String,
         /// The labels the issue has upon it.
         labels: Vec<String>,
+        /// The logins of the users assigned to the issue.
+        assignees: Vec<String>,
+        /// The title of the milestone the issue belongs to, if any.
+        milestone: Option<String>,
     },
     /// A [MemberEvent](https://developer.github.com/v3/activity/events/types#memberevent).
     Member {
         /// The action executed upon a user.
-        ///
-        /// Can be only "added".
-        action: String,
+        action: MemberAction,
         /// The user the action was performed upon.
         user: String,
     },
@@ -123,11 +185,9 @@ pub enum EventPayload {
     PullRequest {
         /// The action executed upon a PR.
         ///
-        /// Can be "assigned", "unassigned", "labeled", "unlabeled", "opened", "edited", "closed", or "reopened".
-        ///
-        /// If the action is "closed" and the `merged` is `false`, the pull request was closed with
-        /// unmerged commits. If the action is "closed" and `merged` is `true`, the pull request was merged.
-        action: String,
+        /// If the action is `Closed` and the `merged` is `false`, the pull request was closed with
+        /// unmerged commits. If the action is `Closed` and `merged` is `true`, the pull request was merged.
+        action: PullRequestAction,
         /// The PR number.
         number: u64,
         /// The PR's title.
@@ -136,9 +196,31 @@ pub enum EventPayload {
         body: String,
         /// Whether the PR was merged.
         ///
-        /// If the action is "closed" and the `merged` is `false`, the pull request was closed with
-        /// unmerged commits. If the action is "closed" and `merged` is `true`, the pull request was merged.
+        /// If the action is `Closed` and the `merged` is `false`, the pull request was closed with
+        /// unmerged commits. If the action is `Closed` and `merged` is `true`, the pull request was merged.
         merged: bool,
+        /// The login of the user who merged the PR, if it was merged.
+        merged_by: Option<String>,
+        /// Whether the PR is a draft.
+        draft: bool,
+        /// The name of the branch the changes are pulled from.
+        ///
+        /// This can be in a different repository, if the PR was opened from a fork.
+        head: String,
+        /// The name of the branch the changes are pulled into.
+        base: String,
+        /// The amount of lines added, if known.
+        additions: Option<u64>,
+        /// The amount of lines removed, if known.
+        deletions: Option<u64>,
+        /// The amount of files changed, if known.
+        changed_files: Option<u64>,
+        /// The labels the PR has upon it.
+        labels: Vec<String>,
+        /// The logins of the users assigned to the PR.
+        assignees: Vec<String>,
+        /// The title of the milestone the PR belongs to, if any.
+        milestone: Option<String>,
     },
     /// A [PullRequestReviewEvent](https://developer.github.com/v3/activity/events/types#pullrequestreviewevent).
     PullRequestReview {
@@ -170,6 +252,19 @@ pub enum EventPayload {
         /// The PR review comment's ID.
         id: u64,
     },
+    /// A [PullRequestReviewThreadEvent](https://docs.github.com/en/rest/using-the-rest-api/github-event-types#pullrequestreviewthreadevent).
+    PullRequestReviewThread {
+        /// The action executed upon a PR review thread.
+        ///
+        /// Can be "resolved" or "unresolved".
+        action: String,
+        /// The PR number.
+        pr: u64,
+        /// The ID of the comment starting the thread.
+        ///
+        /// This is `None` if the thread has no comments.
+        first_comment: Option<u64>,
+    },
     /// A [PushEvent](https://developer.github.com/v3/activity/events/types#pushevent).
     Push {
         /// The full Git ref pushed to.
@@ -188,9 +283,7 @@ pub enum EventPayload {
     /// A [ReleaseEvent](https://developer.github.com/v3/activity/events/types#releaseevent).
     Release {
         /// The action executed upon a release.
-        ///
-        /// Can be only "published".
-        action: String,
+        action: ReleaseAction,
         /// The released tag's name.
         tag_name: String,
         /// The released branch's name.
@@ -208,12 +301,25 @@ pub enum EventPayload {
         /// This is `None` if the release is just a tag and not edited through the GitHub release editor.
         body: Option<String>,
     },
+    /// A [SponsorshipEvent](https://docs.github.com/en/rest/using-the-rest-api/github-event-types#sponsorshipevent).
+    Sponsorship {
+        /// The action executed upon a sponsorship.
+        ///
+        /// Can be "created", "cancelled", "edited", "tier_changed", "pending_cancellation", or "pending_tier_change".
+        action: String,
+        /// The sponsoring user.
+        sponsor: String,
+        /// The sponsored user or organisation.
+        sponsorable: String,
+        /// The sponsorship tier's name.
+        ///
+        /// This is `None` if the tier is private.
+        tier: Option<String>,
+    },
     /// A [WatchEvent](https://developer.github.com/v3/activity/events/types#watchevent), or, more aptly, a StarEvent.
     Watch {
         /// The action executed upon a repository star.
-        ///
-        /// Can only be "started".
-        action: String,
+        action: WatchAction,
     },
     /// An unhandled event.
     Other {
@@ -222,6 +328,44 @@ pub enum EventPayload {
     },
 }
 
+/// Formats an event without the time, with the messages from the catalogue.
+struct Summary<'a>(&'a Event, &'a Catalogue);
+
+/// The user who triggered an event.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Actor {
+    /// The user's ID, doesn't change when the user is renamed.
+    pub id: u64,
+    /// The user's username.
+    pub login: String,
+    /// The name to display for the user.
+    ///
+    /// This is usually the same as `login`, save for, e.g., GitHub Apps, where it lacks the `[bot]` suffix.
+    pub display_login: String,
+    /// The URL to the user's avatar image.
+    pub avatar_url: String,
+}
+
+/// The repository where an event was triggered.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Repo {
+    /// The repository's ID, doesn't change when the repository is renamed or transferred.
+    pub id: u64,
+    /// The repository's slug, e.g. `"nabijaczleweli/dishub"`.
+    pub name: String,
+}
+
+/// The organisation owning the repository where an event was triggered.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Org {
+    /// The organisation's ID, doesn't change when the organisation is renamed.
+    pub id: u64,
+    /// The organisation's name.
+    pub login: String,
+    /// The URL to the organisation's avatar image.
+    pub avatar_url: String,
+}
+
 /// A Wiki page affected by a [GollumEvent](https://developer.github.com/v3/activity/events/types#gollumevent).
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub struct GollumPayload {
@@ -258,20 +402,139 @@ pub struct Commit {
     pub author_email: String,
     /// Whether the commit is distinct.
     pub distinct: bool,
+    /// The people credited in the commit message's `Co-authored-by:` and `Signed-off-by:` trailers.
+    pub contributors: Vec<Contributor>,
+}
+
+/// A person credited in a commit message trailer.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Contributor {
+    /// The trailer the person was credited in.
+    pub kind: ContributorKind,
+    /// The person's name.
+    pub name: String,
+    /// The person's e-mail.
+    pub email: String,
+}
+
+/// A commit message trailer crediting a person.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum ContributorKind {
+    /// `Co-authored-by:`
+    CoAuthor,
+    /// `Signed-off-by:`
+    SignOff,
+}
+
+/// The action executed upon an issue in an [IssuesEvent](https://developer.github.com/v3/activity/events/types#issuesevent).
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub enum IssuesAction {
+    /// `"assigned"`
+    Assigned,
+    /// `"unassigned"`
+    Unassigned,
+    /// `"labeled"`
+    Labeled,
+    /// `"unlabeled"`
+    Unlabeled,
+    /// `"opened"`
+    Opened,
+    /// `"edited"`
+    Edited,
+    /// `"milestoned"`
+    Milestoned,
+    /// `"demilestoned"`
+    Demilestoned,
+    /// `"closed"`
+    Closed,
+    /// `"reopened"`
+    Reopened,
+    /// An action dishub doesn't know about, with its name as given by GitHub.
+    Unknown(String),
+}
+
+/// The action executed upon a comment in an [IssueCommentEvent](https://developer.github.com/v3/activity/events/types#issuecommentevent).
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub enum IssueCommentAction {
+    /// `"created"`
+    Created,
+    /// `"edited"`
+    Edited,
+    /// `"deleted"`
+    Deleted,
+    /// An action dishub doesn't know about, with its name as given by GitHub.
+    Unknown(String),
+}
+
+/// The action executed upon a user in a [MemberEvent](https://developer.github.com/v3/activity/events/types#memberevent).
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub enum MemberAction {
+    /// `"added"`
+    Added,
+    /// An action dishub doesn't know about, with its name as given by GitHub.
+    Unknown(String),
+}
+
+/// The action executed upon a PR in a [PullRequestEvent](https://developer.github.com/v3/activity/events/types#pullrequestevent).
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub enum PullRequestAction {
+    /// `"assigned"`
+    Assigned,
+    /// `"unassigned"`
+    Unassigned,
+    /// `"review_requested"`
+    ReviewRequested,
+    /// `"review_request_removed"`
+    ReviewRequestRemoved,
+    /// `"labeled"`
+    Labeled,
+    /// `"unlabeled"`
+    Unlabeled,
+    /// `"opened"`
+    Opened,
+    /// `"edited"`
+    Edited,
+    /// `"closed"`
+    Closed,
+    /// `"reopened"`
+    Reopened,
+    /// `"synchronize"`
+    Synchronize,
+    /// An action dishub doesn't know about, with its name as given by GitHub.
+    Unknown(String),
+}
+
+/// The action executed upon a release in a [ReleaseEvent](https://developer.github.com/v3/activity/events/types#releaseevent).
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub enum ReleaseAction {
+    /// `"published"`
+    Published,
+    /// An action dishub doesn't know about, with its name as given by GitHub.
+    Unknown(String),
+}
+
+/// The action executed upon a repository star in a [WatchEvent](https://developer.github.com/v3/activity/events/types#watchevent).
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub enum WatchAction {
+    /// `"started"`
+    Started,
+    /// An action dishub doesn't know about, with its name as given by GitHub.
+    Unknown(String),
 }
 
 
 impl Event {
     /// Parse a raw JSON GitHub Events API response.
     ///
-    /// The JSON is not checked for correctness, so be wary.
+    /// Fails on the first malformed event, use `parse_lenient()` to keep the well-formed ones.
     ///
     /// # Examples
     ///
     /// ```
     /// # extern crate chrono;
     /// # extern crate dishub;
-    /// # use dishub::ops::{EventPayload, Event};
+    /// # extern crate json;
+    /// # use dishub::ops::{EventPayload, WatchAction, Event, Actor, Repo};
     /// # use chrono::DateTime;
     /// # fn main() {
     /// // Shaved to minimum for brevity
@@ -280,9 +543,12 @@ impl Event {
     ///                       "id": "4831774905",
     ///                       "type": "WatchEvent",
     ///                       "actor": {
-    ///                         "display_login": "carllhw"
+    ///                         "id": 5784597,
+    ///                         "login": "carllhw",
+    ///                         "avatar_url": "https://avatars.githubusercontent.com/u/5784597?"
     ///                       },
     ///                       "repo": {
+    ///                         "id": 71928026,
     ///                         "name": "nabijaczleweli/cargo-update"
     ///                       },
     ///                       "payload": {
@@ -294,9 +560,12 @@ impl Event {
     ///                       "id": "4831775201",
     ///                       "type": "WatchEvent",
     ///                       "actor": {
-    ///                         "display_login": "Byron-TW"
+    ///                         "id": 13521493,
+    ///                         "login": "Byron-TW",
+    ///                         "avatar_url": "https://avatars.githubusercontent.com/u/13521493?"
     ///                       },
     ///                       "repo": {
+    ///                         "id": 71928026,
     ///                         "name": "nabijaczleweli/cargo-update"
     ///                       },
     ///                       "payload": {
@@ -305,42 +574,203 @@ impl Event {
     ///                       "created_at": "2016-11-09T06:12:26Z"
     ///                     }
     ///                   ]"#;
-    /// assert_eq!(Event::parse(response), vec![
+    /// assert_eq!(Event::parse(response), Ok(vec![
     ///            Event {
     ///                created_at: DateTime::parse_from_rfc2822("Tue, 8 Nov 2016 03:10:26 +0000").unwrap(),
-    ///                actor: "carllhw".to_string(),
-    ///                repo: "nabijaczleweli/cargo-update".to_string(),
+    ///                actor: Actor {
+    ///                    id: 5784597,
+    ///                    login: "carllhw".to_string(),
+    ///                    display_login: "carllhw".to_string(),
+    ///                    avatar_url: "https://avatars.githubusercontent.com/u/5784597?".to_string(),
+    ///                },
+    ///                repo: Repo {
+    ///                    id: 71928026,
+    ///                    name: "nabijaczleweli/cargo-update".to_string(),
+    ///                },
+    ///                org: None,
     ///                id: 4831774905,
     ///                payload: EventPayload::Watch {
-    ///                    action: "started".to_string(),
+    ///                    action: WatchAction::Started,
     ///                },
+    ///                raw: json::parse(response).unwrap()[0].clone(),
     ///            },
     ///            Event {
     ///                created_at: DateTime::parse_from_rfc2822("Wed, 9 Nov 2016 06:12:26 +0000").unwrap(),
-    ///                actor: "Byron-TW".to_string(),
-    ///                repo: "nabijaczleweli/cargo-update".to_string(),
+    ///                actor: Actor {
+    ///                    id: 13521493,
+    ///                    login: "Byron-TW".to_string(),
+    ///                    display_login: "Byron-TW".to_string(),
+    ///                    avatar_url: "https://avatars.githubusercontent.com/u/13521493?".to_string(),
+    ///                },
+    ///                repo: Repo {
+    ///                    id: 71928026,
+    ///                    name: "nabijaczleweli/cargo-update".to_string(),
+    ///                },
+    ///                org: None,
     ///                id: 4831775201,
     ///                payload: EventPayload::Watch {
-    ///                    action: "started".to_string(),
+    ///                    action: WatchAction::Started,
     ///                },
+    ///                raw: json::parse(response).unwrap()[1].clone(),
     ///            }
-    ///        ]);
+    ///        ]));
     /// # }
     /// ```
-    pub fn parse(what: &str) -> Vec<Event> {
-        json::parse(what)
-            .unwrap()
-            .members()
-            .map(|j| {
-                Event {
-                    created_at: DateTime::parse_from_rfc3339(j["created_at"].as_str().unwrap()).unwrap(),
-                    actor: j["actor"]["display_login"].as_str().unwrap().to_string(),
-                    repo: j["repo"]["name"].as_str().unwrap().to_string(),
-                    id: u64::from_str(j["id"].as_str().unwrap()).unwrap(),
-                    payload: EventPayload::from(j["type"].as_str().unwrap(), &j["payload"]),
+    pub fn parse(what: &str) -> Result<Vec<Event>, Error> {
+        let events = try!(parse_event_list(what));
+        events.members().map(Event::from_github_json).collect()
+    }
+
+    /// Parse a raw JSON GitHub Events API response, skipping over malformed events.
+    ///
+    /// Events with a malformed payload are degraded to `EventPayload::Other`,
+    /// events whose ID, type, time, actor or repository are malformed are skipped entirely.
+    ///
+    /// The second element of the returned tuple contains the reasons for each degraded or skipped event.
+    ///
+    /// Fails only if the response as a whole isn't a JSON array.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dishub::ops::{EventPayload, Event};
+    /// # use dishub::Error;
+    /// let response = r#"[{
+    ///                     "id": "4831774905",
+    ///                     "type": "WatchEvent",
+    ///                     "actor": { "id": 5784597, "login": "carllhw", "avatar_url": "https://avatars.githubusercontent.com/u/5784597?" },
+    ///                     "repo": { "id": 71928026, "name": "nabijaczleweli/cargo-update" },
+    ///                     "payload": { "action": null },
+    ///                     "created_at": "2016-11-08T03:10:26Z"
+    ///                   },
+    ///                   {
+    ///                     "id": "Byron-TW",
+    ///                     "type": "WatchEvent"
+    ///                   }]"#;
+    /// let (events, errors) = Event::parse_lenient(response).unwrap();
+    /// assert_eq!(events.len(), 1);
+    /// assert_eq!(events[0].payload, EventPayload::Other { event_type: "WatchEvent".to_string() });
+    /// assert_eq!(errors,
+    ///            vec![Error::EventParsingFailed {
+    ///                     id: "4831774905".to_string(),
+    ///                     tp: "WatchEvent".to_string(),
+    ///                     path: "payload.action".to_string(),
+    ///                 },
+    ///                 Error::EventParsingFailed {
+    ///                     id: "Byron-TW".to_string(),
+    ///                     tp: "WatchEvent".to_string(),
+    ///                     path: "id".to_string(),
+    ///                 }]);
+    /// ```
+    pub fn parse_lenient(what: &str) -> Result<(Vec<Event>, Vec<Error>), Error> {
+        let events = try!(parse_event_list(what));
+
+        let mut parsed = vec![];
+        let mut errors = vec![];
+        for j in events.members() {
+            match Event::from_github_json(j) {
+                Ok(ev) => parsed.push(ev),
+                Err(Error::EventParsingFailed { id, tp, path }) => {
+                    if path.starts_with("payload.") {
+                        match Event::from_github_json_with_payload(j, |tpe, _| Ok(EventPayload::Other { event_type: tpe.to_string() })) {
+                            Ok(ev) => parsed.push(ev),
+                            Err(err) => errors.push(err),
+                        }
+                    }
+                    errors.push(Error::EventParsingFailed {
+                        id: id,
+                        tp: tp,
+                        path: path,
+                    });
                 }
-            })
-            .collect()
+                Err(err) => errors.push(err),
+            }
+        }
+
+        Ok((parsed, errors))
+    }
+
+    fn from_github_json(j: &JsonValue) -> Result<Event, Error> {
+        Event::from_github_json_with_payload(j, EventPayload::from)
+    }
+
+    fn from_github_json_with_payload<F: FnOnce(&str, &JsonValue) -> Result<EventPayload, Error>>(j: &JsonValue, payload: F) -> Result<Event, Error> {
+        let id_s = j["id"].as_str().map(str::to_string).unwrap_or_else(|| j["id"].dump());
+        let tp_s = j["type"].as_str().unwrap_or("unknown").to_string();
+        let malformed = |path: &str| {
+            Error::EventParsingFailed {
+                id: id_s.clone(),
+                tp: tp_s.clone(),
+                path: path.to_string(),
+            }
+        };
+
+        let id = try!(j["id"].as_str().and_then(|id| u64::from_str(id).ok()).ok_or_else(|| malformed("id")));
+        let tpe = try!(j["type"].as_str().ok_or_else(|| malformed("type")));
+        let created_at = try!(j["created_at"].as_str().and_then(|ca| DateTime::parse_from_rfc3339(ca).ok()).ok_or_else(|| malformed("created_at")));
+        let actor = try!(Actor::from_github_json(&j["actor"]).map_err(|p| malformed(&format!("actor.{}", p))));
+        let repo = try!(Repo::from_github_json(&j["repo"]).map_err(|p| malformed(&format!("repo.{}", p))));
+        let org = if j["org"].is_null() {
+            None
+        } else {
+            Some(try!(Org::from_github_json(&j["org"]).map_err(|p| malformed(&format!("org.{}", p)))))
+        };
+        let payload = try!(payload(tpe, &j["payload"]).map_err(|e| match e {
+            Error::EventParsingFailed { path, .. } => malformed(&path),
+            e => e,
+        }));
+
+        Ok(Event {
+            created_at: created_at,
+            actor: actor,
+            repo: repo,
+            org: org,
+            id: id,
+            payload: payload,
+            raw: j.clone(),
+        })
+    }
+
+    /// Describe what happened in the event, i.e. its `Display` output without the leading time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dishub::ops::Event;
+    /// let response = r#"[{
+    ///                     "id": "4831774905",
+    ///                     "type": "WatchEvent",
+    ///                     "actor": { "id": 5784597, "login": "carllhw", "avatar_url": "https://avatars.githubusercontent.com/u/5784597?" },
+    ///                     "repo": { "id": 71928026, "name": "nabijaczleweli/cargo-update" },
+    ///                     "payload": { "action": "started" },
+    ///                     "created_at": "2016-11-08T03:10:26Z"
+    ///                   }]"#;
+    /// assert_eq!(Event::parse(response).unwrap()[0].summary(), "carllhw starred nabijaczleweli/cargo-update");
+    /// ```
+    pub fn summary(&self) -> String {
+        Summary(self, Catalogue::english()).to_string()
+    }
+
+    /// Describe what happened in the event with the messages from the specified catalogue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dishub::ops::locale::Catalogue;
+    /// # use dishub::ops::Event;
+    /// let response = r#"[{
+    ///                     "id": "4831774905",
+    ///                     "type": "WatchEvent",
+    ///                     "actor": { "id": 5784597, "login": "carllhw", "avatar_url": "https://avatars.githubusercontent.com/u/5784597?" },
+    ///                     "repo": { "id": 71928026, "name": "nabijaczleweli/cargo-update" },
+    ///                     "payload": { "action": "started" },
+    ///                     "created_at": "2016-11-08T03:10:26Z"
+    ///                   }]"#;
+    /// assert_eq!(Event::parse(response).unwrap()[0].summary_in(&Catalogue::builtin("pl").unwrap()),
+    ///            "carllhw dał(a) gwiazdkę nabijaczleweli/cargo-update");
+    /// ```
+    pub fn summary_in(&self, catalogue: &Catalogue) -> String {
+        Summary(self, catalogue).to_string()
     }
 
     /// Get the reference URLs for an event.
@@ -357,263 +787,1805 @@ impl Event {
     /// let response = r#"[{
     ///                     "id": "4831774905",
     ///                     "type": "WatchEvent",
-    ///                     "actor": { "display_login": "carllhw" },
-    ///                     "repo": { "name": "nabijaczleweli/cargo-update" },
+    ///                     "actor": { "id": 5784597, "login": "carllhw", "avatar_url": "https://avatars.githubusercontent.com/u/5784597?" },
+    ///                     "repo": { "id": 71928026, "name": "nabijaczleweli/cargo-update" },
     ///                     "payload": { "action": "started" },
     ///                     "created_at": "2016-11-08T03:10:26Z"
     ///                   }]"#;
-    /// assert_eq!(Event::parse(response)[0].urls(),
+    /// assert_eq!(Event::parse(response).unwrap()[0].urls(),
     ///            vec!["https://github.com/nabijaczleweli/cargo-update/stargazers".to_string()]);
     /// # }
     pub fn urls(&self) -> Vec<String> {
+        self.urls_at(GITHUB_WEB_URL)
+    }
+
+    /// Get the URLs of the event on the GitHub instance with the specified web interface base URL, see `urls()`.
+    pub fn urls_at(&self, web_url: &str) -> Vec<String> {
         match self.payload {
             EventPayload::CommitComment { ref commit_id, id, .. } => {
-                vec![format!("https://github.com/{}/commit/{}#commitcomment-{}", self.repo, commit_id, id)]
+                vec![format!("{}/{}/commit/{}#commitcomment-{}", web_url, self.repo, commit_id, id)]
             }
             EventPayload::Create { ref ref_name, .. } => {
                 match *ref_name {
-                    Some(ref ref_name) => vec![format!("https://github.com/{}/compare/{}", self.repo, ref_name)],
-                    None => vec![format!("https://github.com/{}", self.repo)],
+                    Some(ref ref_name) => vec![format!("{}/{}/compare/{}", web_url, self.repo, ref_name)],
+                    None => vec![format!("{}/{}", web_url, self.repo)],
                 }
             }
             EventPayload::Delete { .. } => vec![],
-            EventPayload::Fork { ref new_slug } => vec![format!("https://github.com/{}", new_slug)],
-            EventPayload::Gollum { ref pages } => pages.iter().map(|p| format!("https://github.com{}", p.html_url)).collect(),
-            EventPayload::IssueComment { issue, id, .. } => vec![format!("https://github.com/{}/issues/{}#issuecomment-{}", self.repo, issue, id)],
-            EventPayload::Issues { number, .. } => vec![format!("https://github.com/{}/issues/{}", self.repo, number)],
+            EventPayload::Discussion { number, .. } => vec![format!("{}/{}/discussions/{}", web_url, self.repo, number)],
+            EventPayload::DiscussionComment { discussion, id, .. } => {
+                vec![format!("{}/{}/discussions/{}#discussioncomment-{}", web_url, self.repo, discussion, id)]
+            }
+            EventPayload::Fork { ref new_slug } => vec![format!("{}/{}", web_url, new_slug)],
+            EventPayload::ForkApply { ref before, ref after, .. } => vec![format!("{}/{}/compare/{}...{}", web_url, self.repo, before, after)],
+            EventPayload::Gollum { ref pages } => pages.iter().map(|p| format!("{}{}", web_url, p.html_url)).collect(),
+            EventPayload::IssueComment { issue, pull_request, id, .. } => {
+                vec![format!("{}/{}/{}/{}#issuecomment-{}",
+                             web_url,
+                             self.repo,
+                             if pull_request { "pull" } else { "issues" },
+                             issue,
+                             id)]
+            }
+            EventPayload::Issues { number, .. } => vec![format!("{}/{}/issues/{}", web_url, self.repo, number)],
             EventPayload::Member { .. } => vec![],
-            EventPayload::Public => vec![format!("https://github.com/{}", self.repo)],
-            EventPayload::PullRequest { number, .. } => vec![format!("https://github.com/{}/pull/{}", self.repo, number)],
-            EventPayload::PullRequestReview { pr, id, .. } => vec![format!("https://github.com/{}/pull/{}#pullrequestreview-{}", self.repo, pr, id)],
-            EventPayload::PullRequestReviewComment { pr, id, .. } => vec![format!("https://github.com/{}/pull/{}#discussion_r{}", self.repo, pr, id)],
-            EventPayload::Push { ref prev_head, ref new_head, .. } => vec![format!("https://github.com/{}/compare/{}...{}", self.repo, prev_head, new_head)],
-            EventPayload::Release { ref tag_name, .. } => vec![format!("https://github.com/{}/releases/tag/{}", self.repo, tag_name)],
-            EventPayload::Watch { .. } => vec![format!("https://github.com/{}/stargazers", self.repo)],
-            EventPayload::Other { .. } => vec![],
+            EventPayload::Public => vec![format!("{}/{}", web_url, self.repo)],
+            EventPayload::PullRequest { number, .. } => vec![format!("{}/{}/pull/{}", web_url, self.repo, number)],
+            EventPayload::PullRequestReview { pr, id, .. } => vec![format!("{}/{}/pull/{}#pullrequestreview-{}", web_url, self.repo, pr, id)],
+            EventPayload::PullRequestReviewComment { pr, id, .. } => vec![format!("{}/{}/pull/{}#discussion_r{}", web_url, self.repo, pr, id)],
+            EventPayload::PullRequestReviewThread { pr, first_comment, .. } => {
+                match first_comment {
+                    Some(id) => vec![format!("{}/{}/pull/{}#discussion_r{}", web_url, self.repo, pr, id)],
+                    None => vec![format!("{}/{}/pull/{}", web_url, self.repo, pr)],
+                }
+            }
+            EventPayload::Push { ref prev_head, ref new_head, .. } => vec![format!("{}/{}/compare/{}...{}", web_url, self.repo, prev_head, new_head)],
+            EventPayload::Release { ref tag_name, .. } => vec![format!("{}/{}/releases/tag/{}", web_url, self.repo, tag_name)],
+            EventPayload::Sponsorship { ref sponsorable, .. } => vec![format!("{}/sponsors/{}", web_url, sponsorable)],
+            EventPayload::Watch { .. } => vec![format!("{}/{}/stargazers", web_url, self.repo)],
+            EventPayload::Other { .. } => common_payload_str(&self.raw["payload"], "html_url").map(|u| vec![u.to_string()]).unwrap_or_default(),
+        }
+    }
+
+    /// Get the user-written text of the event, if it has any, i.e. the body of the issue, PR, review, comment, discussion, or release.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dishub::ops::Event;
+    /// let response = r#"[{
+    ///                     "id": "4844096927",
+    ///                     "type": "IssuesEvent",
+    ///                     "actor": { "id": 346530, "login": "liigo", "avatar_url": "https://avatars.githubusercontent.com/u/346530?" },
+    ///                     "repo": { "id": 71928026, "name": "nabijaczleweli/cargo-update" },
+    ///                     "payload": {
+    ///                       "action": "opened",
+    ///                       "issue": { "number": 11, "title": "It", "body": "  It happens on Windows.\n", "labels": [], "assignees": [] }
+    ///                     },
+    ///                     "created_at": "2016-11-10T08:42:18Z"
+    ///                   }]"#;
+    /// assert_eq!(Event::parse(response).unwrap()[0].body(), Some("It happens on Windows."));
+    /// ```
+    pub fn body(&self) -> Option<&str> {
+        let body = match self.payload {
+            EventPayload::CommitComment { content: ref body, .. } |
+            EventPayload::Discussion { ref body, .. } |
+            EventPayload::DiscussionComment { ref body, .. } |
+            EventPayload::IssueComment { ref body, .. } |
+            EventPayload::Issues { ref body, .. } |
+            EventPayload::PullRequest { ref body, .. } |
+            EventPayload::PullRequestReview { ref body, .. } |
+            EventPayload::PullRequestReviewComment { ref body, .. } => body.trim(),
+            EventPayload::Release { body: Some(ref body), .. } => body.trim(),
+            _ => "",
+        };
+
+        if body.is_empty() {
+            None
+        } else {
+            Some(body)
+        }
+    }
+
+    /// Serialise the event to dishub's JSON representation, which `from_json()` reads back losslessly.
+    ///
+    /// The schema is as follows (the `payload` object is described in `EventPayload::to_json()`):
+    ///
+    /// ```json
+    /// {
+    ///   "id": 4831774905,
+    ///   "created_at": "2016-11-08T03:10:26+00:00",
+    ///   "actor": {
+    ///     "id": 5784597,
+    ///     "login": "carllhw",
+    ///     "display_login": "carllhw",
+    ///     "avatar_url": "https://avatars.githubusercontent.com/u/5784597?"
+    ///   },
+    ///   "repo": {
+    ///     "id": 71928026,
+    ///     "name": "nabijaczleweli/cargo-update"
+    ///   },
+    ///   "org": null,
+    ///   "payload": {
+    ///     "type": "Watch",
+    ///     "action": "started"
+    ///   },
+    ///   "raw": null
+    /// }
+    /// ```
+    ///
+    /// `created_at` is an RFC 3339 timestamp with the original offset,
+    /// `org`, if not `null`, is an object with the `"id"`, `"login"`, and `"avatar_url"` keys,
+    /// and `raw` is the original GitHub event JSON, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dishub::ops::Event;
+    /// let response = r#"[{
+    ///                     "id": "4831774905",
+    ///                     "type": "WatchEvent",
+    ///                     "actor": { "id": 5784597, "login": "carllhw", "avatar_url": "https://avatars.githubusercontent.com/u/5784597?" },
+    ///                     "repo": { "id": 71928026, "name": "nabijaczleweli/cargo-update" },
+    ///                     "payload": { "action": "started" },
+    ///                     "created_at": "2016-11-08T03:10:26Z"
+    ///                   }]"#;
+    /// let event = Event::parse(response).unwrap().remove(0);
+    ///
+    /// let json = event.to_json();
+    /// assert_eq!(json["created_at"], "2016-11-08T03:10:26+00:00");
+    /// assert_eq!(json["payload"]["type"], "Watch");
+    /// assert_eq!(Event::from_json(&json), Ok(event));
+    /// ```
+    pub fn to_json(&self) -> JsonValue {
+        object!{
+            "id" => self.id,
+            "created_at" => self.created_at.to_rfc3339(),
+            "actor" => self.actor.to_json(),
+            "repo" => self.repo.to_json(),
+            "org" => self.org.as_ref().map(Org::to_json),
+            "payload" => self.payload.to_json(),
+            "raw" => self.raw.clone(),
         }
     }
+
+    /// Deserialise an event from dishub's JSON representation, as produced by `to_json()`.
+    ///
+    /// A malformed event is reported as `Error::EventParsingFailed` with the path to the offending value,
+    /// `tp` being the payload's `"type"`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate dishub;
+    /// # extern crate json;
+    /// # use dishub::ops::Event;
+    /// # use dishub::Error;
+    /// # fn main() {
+    /// let event = json::parse(r#"{
+    ///                              "id": 4831774905,
+    ///                              "created_at": "2016-11-08T03:10:26+00:00",
+    ///                              "actor": { "id": 5784597, "login": "carllhw", "display_login": "carllhw",
+    ///                                         "avatar_url": "https://avatars.githubusercontent.com/u/5784597?" },
+    ///                              "repo": { "id": 71928026, "name": "nabijaczleweli/cargo-update" },
+    ///                              "org": null,
+    ///                              "payload": { "type": "Watch", "action": 12 },
+    ///                              "raw": null
+    ///                            }"#).unwrap();
+    /// assert_eq!(Event::from_json(&event),
+    ///            Err(Error::EventParsingFailed {
+    ///                id: "4831774905".to_string(),
+    ///                tp: "Watch".to_string(),
+    ///                path: "payload.action".to_string(),
+    ///            }));
+    /// # }
+    /// ```
+    pub fn from_json(j: &JsonValue) -> Result<Event, Error> {
+        Event::from_dishub_json(j).map_err(|path| {
+            Error::EventParsingFailed {
+                id: j["id"].dump(),
+                tp: j["payload"]["type"].as_str().unwrap_or("unknown").to_string(),
+                path: path,
+            }
+        })
+    }
+
+    fn from_dishub_json(j: &JsonValue) -> Result<Event, String> {
+        Ok(Event {
+            created_at: try!(j["created_at"].as_str().and_then(|ca| DateTime::parse_from_rfc3339(ca).ok()).ok_or_else(|| "created_at".to_string())),
+            actor: try!(Actor::from_dishub_json(&j["actor"]).map_err(|p| format!("actor.{}", p))),
+            repo: try!(Repo::from_dishub_json(&j["repo"]).map_err(|p| format!("repo.{}", p))),
+            org: if j["org"].is_null() {
+                None
+            } else {
+                Some(try!(Org::from_dishub_json(&j["org"]).map_err(|p| format!("org.{}", p))))
+            },
+            id: try!(json_u64(j, "id")),
+            payload: try!(EventPayload::from_dishub_json(&j["payload"]).map_err(|p| format!("payload.{}", p))),
+            raw: j["raw"].clone(),
+        })
+    }
 }
 
 impl EventPayload {
     /// Parse the payload from the `payload` key of the event and the type.
     ///
     /// Not meant to be used directly, use `Event::parse()` instead.
-    pub fn from(tpe: &str, payload: &JsonValue) -> EventPayload {
-        match tpe {
+    ///
+    /// A malformed payload is reported as `Error::EventParsingFailed` with an empty `id`,
+    /// the `path` is relative to the whole event, i.e. starts with `"payload."`.
+    pub fn from(tpe: &str, payload: &JsonValue) -> Result<EventPayload, Error> {
+        EventPayload::from_github_json(tpe, payload).map_err(|path| {
+            Error::EventParsingFailed {
+                id: String::new(),
+                tp: tpe.to_string(),
+                path: format!("payload.{}", path),
+            }
+        })
+    }
+
+    fn from_github_json(tpe: &str, payload: &JsonValue) -> Result<EventPayload, String> {
+        Ok(match tpe {
             "CommitCommentEvent" => {
                 EventPayload::CommitComment {
-                    content: payload["comment"]["body"].as_str().unwrap().to_string(),
-                    commit_id: payload["comment"]["commit_id"].as_str().unwrap().to_string(),
-                    id: payload["comment"]["id"].as_number().unwrap().into(),
+                    content: try!(json_str(payload, "comment.body")),
+                    commit_id: try!(json_str(payload, "comment.commit_id")),
+                    id: try!(json_u64(payload, "comment.id")),
                 }
             }
             "CreateEvent" => {
                 EventPayload::Create {
-                    ref_type: payload["ref_type"].as_str().unwrap().to_string(),
-                    ref_name: payload["ref"].as_str().map(str::to_string),
-                    master_branch: payload["master_branch"].as_str().unwrap().to_string(),
-                    repo_description: payload["description"].as_str().unwrap().to_string(),
+                    ref_type: try!(json_str(payload, "ref_type")),
+                    ref_name: try!(json_opt_str(payload, "ref")),
+                    master_branch: try!(json_str(payload, "master_branch")),
+                    repo_description: try!(json_opt_str(payload, "description")).unwrap_or_default(),
                 }
             }
             "DeleteEvent" => {
                 EventPayload::Delete {
-                    ref_type: payload["ref_type"].as_str().unwrap().to_string(),
-                    ref_name: payload["ref"].as_str().unwrap().to_string(),
+                    ref_type: try!(json_str(payload, "ref_type")),
+                    ref_name: try!(json_str(payload, "ref")),
+                }
+            }
+            "DiscussionEvent" => {
+                EventPayload::Discussion {
+                    action: try!(json_str(payload, "action")),
+                    number: try!(json_u64(payload, "discussion.number")),
+                    title: try!(json_str(payload, "discussion.title")),
+                    body: try!(json_opt_str(payload, "discussion.body")).unwrap_or_default(),
+                    category: try!(json_str(payload, "discussion.category.name")),
+                }
+            }
+            "DiscussionCommentEvent" => {
+                EventPayload::DiscussionComment {
+                    action: try!(json_str(payload, "action")),
+                    discussion: try!(json_u64(payload, "discussion.number")),
+                    body: try!(json_str(payload, "comment.body")),
+                    id: try!(json_u64(payload, "comment.id")),
+                }
+            }
+            "ForkEvent" => EventPayload::Fork { new_slug: try!(json_str(payload, "forkee.full_name")) },
+            "ForkApplyEvent" => {
+                EventPayload::ForkApply {
+                    head: try!(json_str(payload, "head")),
+                    before: try!(json_str(payload, "before")),
+                    after: try!(json_str(payload, "after")),
                 }
             }
-            "ForkEvent" => EventPayload::Fork { new_slug: payload["forkee"]["full_name"].as_str().unwrap().to_string() },
             "GollumEvent" => {
                 EventPayload::Gollum {
-                    pages: payload["pages"]
-                        .members()
-                        .map(|pg| {
-                            GollumPayload {
-                                page_name: pg["page_name"].as_str().unwrap().to_string(),
-                                title: pg["title"].as_str().unwrap().to_string(),
-                                action: pg["action"].as_str().unwrap().to_string(),
-                                sha: pg["sha"].as_str().unwrap().to_string(),
-                                html_url: pg["html_url"].as_str().unwrap().to_string(),
-                            }
+                    pages: try!(json_members(payload, "pages", |pg| {
+                        Ok(GollumPayload {
+                            page_name: try!(json_str(pg, "page_name")),
+                            title: try!(json_str(pg, "title")),
+                            action: try!(json_str(pg, "action")),
+                            sha: try!(json_str(pg, "sha")),
+                            html_url: try!(json_str(pg, "html_url")),
                         })
-                        .collect(),
+                    })),
                 }
             }
             "IssueCommentEvent" => {
                 EventPayload::IssueComment {
-                    action: payload["action"].as_str().unwrap().to_string(),
-                    issue: payload["issue"]["number"].as_number().unwrap().into(),
-                    body: payload["comment"]["body"].as_str().unwrap().to_string(),
-                    id: payload["comment"]["id"].as_number().unwrap().into(),
+                    action: IssueCommentAction::from(&try!(json_str(payload, "action"))[..]),
+                    issue: try!(json_u64(payload, "issue.number")),
+                    pull_request: !json_field(payload, "issue.pull_request").is_null(),
+                    body: try!(json_str(payload, "comment.body")),
+                    id: try!(json_u64(payload, "comment.id")),
                 }
             }
             "IssuesEvent" => {
                 EventPayload::Issues {
-                    action: payload["action"].as_str().unwrap().to_string(),
-                    number: payload["issue"]["number"].as_number().unwrap().into(),
-                    title: payload["issue"]["title"].as_str().unwrap().to_string(),
-                    body: payload["issue"]["body"].as_str().unwrap().to_string(),
-                    labels: payload["issue"]["labels"].members().map(|l| l["name"].as_str().unwrap().to_string()).collect(),
+                    action: IssuesAction::from(&try!(json_str(payload, "action"))[..]),
+                    number: try!(json_u64(payload, "issue.number")),
+                    title: try!(json_str(payload, "issue.title")),
+                    body: try!(json_opt_str(payload, "issue.body")).unwrap_or_default(),
+                    labels: try!(json_members(payload, "issue.labels", |l| json_str(l, "name"))),
+                    assignees: try!(json_opt_members(payload, "issue.assignees", |a| json_str(a, "login"))),
+                    milestone: try!(json_opt_str(payload, "issue.milestone.title")),
                 }
             }
             "MemberEvent" => {
                 EventPayload::Member {
-                    action: payload["action"].as_str().unwrap().to_string(),
-                    user: payload["member"]["login"].as_str().unwrap().to_string(),
+                    action: MemberAction::from(&try!(json_str(payload, "action"))[..]),
+                    user: try!(json_str(payload, "member.login")),
                 }
             }
             "PublicEvent" => EventPayload::Public,
             "PullRequestEvent" => {
                 EventPayload::PullRequest {
-                    action: payload["action"].as_str().unwrap().to_string(),
-                    number: payload["number"].as_number().unwrap().into(),
-                    title: payload["pull_request"]["title"].as_str().unwrap().to_string(),
-                    body: payload["pull_request"]["body"].as_str().unwrap().to_string(),
-                    merged: payload["pull_request"]["merged"].as_bool().unwrap(),
+                    action: PullRequestAction::from(&try!(json_str(payload, "action"))[..]),
+                    number: try!(json_u64(payload, "number")),
+                    title: try!(json_str(payload, "pull_request.title")),
+                    body: try!(json_opt_str(payload, "pull_request.body")).unwrap_or_default(),
+                    merged: try!(json_bool(payload, "pull_request.merged")),
+                    merged_by: try!(json_opt_str(payload, "pull_request.merged_by.login")),
+                    draft: try!(json_opt_bool(payload, "pull_request.draft")).unwrap_or(false),
+                    head: try!(json_str(payload, "pull_request.head.ref")),
+                    base: try!(json_str(payload, "pull_request.base.ref")),
+                    additions: try!(json_opt_u64(payload, "pull_request.additions")),
+                    deletions: try!(json_opt_u64(payload, "pull_request.deletions")),
+                    changed_files: try!(json_opt_u64(payload, "pull_request.changed_files")),
+                    labels: try!(json_opt_members(payload, "pull_request.labels", |l| json_str(l, "name"))),
+                    assignees: try!(json_opt_members(payload, "pull_request.assignees", |a| json_str(a, "login"))),
+                    milestone: try!(json_opt_str(payload, "pull_request.milestone.title")),
                 }
             }
             "PullRequestReviewEvent" => {
                 EventPayload::PullRequestReview {
-                    action: payload["action"].as_str().unwrap().to_string(),
-                    pr: payload["pull_request"]["number"].as_number().unwrap().into(),
-                    state: payload["review"]["state"].as_str().unwrap().to_string(),
-                    body: payload["review"]["body"].as_str().unwrap().to_string(),
-                    id: payload["review"]["id"].as_number().unwrap().into(),
+                    action: try!(json_str(payload, "action")),
+                    pr: try!(json_u64(payload, "pull_request.number")),
+                    state: try!(json_str(payload, "review.state")),
+                    body: try!(json_opt_str(payload, "review.body")).unwrap_or_default(),
+                    id: try!(json_u64(payload, "review.id")),
                 }
             }
             "PullRequestReviewCommentEvent" => {
                 EventPayload::PullRequestReviewComment {
-                    action: payload["action"].as_str().unwrap().to_string(),
-                    pr: payload["pull_request"]["number"].as_number().unwrap().into(),
-                    body: payload["comment"]["body"].as_str().unwrap().to_string(),
-                    id: payload["comment"]["id"].as_number().unwrap().into(),
+                    action: try!(json_str(payload, "action")),
+                    pr: try!(json_u64(payload, "pull_request.number")),
+                    body: try!(json_str(payload, "comment.body")),
+                    id: try!(json_u64(payload, "comment.id")),
+                }
+            }
+            "PullRequestReviewThreadEvent" => {
+                EventPayload::PullRequestReviewThread {
+                    action: try!(json_str(payload, "action")),
+                    pr: try!(json_u64(payload, "pull_request.number")),
+                    first_comment: try!(json_opt_members(payload, "thread.comments", |c| json_u64(c, "id"))).into_iter().next(),
                 }
             }
             "PushEvent" => {
                 EventPayload::Push {
-                    pushed_ref: payload["ref"].as_str().unwrap().to_string(),
-                    prev_head: payload["before"].as_str().unwrap().to_string(),
-                    new_head: payload["head"].as_str().unwrap().to_string(),
-                    size: payload["size"].as_number().unwrap().into(),
-                    distinct_size: payload["distinct_size"].as_number().unwrap().into(),
-                    commits: payload["commits"]
-                        .members()
-                        .map(|c| {
-                            Commit {
-                                sha: c["sha"].as_str().unwrap().to_string(),
-                                message: c["message"].as_str().unwrap().to_string(),
-                                author_name: c["author"]["name"].as_str().unwrap().to_string(),
-                                author_email: c["author"]["email"].as_str().unwrap().to_string(),
-                                distinct: c["distinct"].as_bool().unwrap(),
-                            }
+                    pushed_ref: try!(json_str(payload, "ref")),
+                    prev_head: try!(json_str(payload, "before")),
+                    new_head: try!(json_str(payload, "head")),
+                    size: try!(json_u64(payload, "size")),
+                    distinct_size: try!(json_u64(payload, "distinct_size")),
+                    commits: try!(json_members(payload, "commits", |c| {
+                        Ok(Commit {
+                            sha: try!(json_str(c, "sha")),
+                            message: try!(json_str(c, "message")),
+                            author_name: try!(json_str(c, "author.name")),
+                            author_email: try!(json_str(c, "author.email")),
+                            distinct: try!(json_bool(c, "distinct")),
+                            contributors: Commit::parse_contributors(&try!(json_str(c, "message"))),
                         })
-                        .collect(),
+                    })),
                 }
             }
             "ReleaseEvent" => {
                 EventPayload::Release {
-                    action: payload["action"].as_str().unwrap().to_string(),
-                    tag_name: payload["release"]["tag_name"].as_str().unwrap().to_string(),
-                    target: payload["release"]["target_commitish"].as_str().unwrap().to_string(),
-                    draft: payload["release"]["draft"].as_bool().unwrap(),
-                    prerelease: payload["release"]["prerelease"].as_bool().unwrap(),
-                    name: payload["release"]["name"].as_str().map(str::to_string),
-                    body: payload["release"]["body"].as_str().map(str::to_string),
+                    action: ReleaseAction::from(&try!(json_str(payload, "action"))[..]),
+                    tag_name: try!(json_str(payload, "release.tag_name")),
+                    target: try!(json_str(payload, "release.target_commitish")),
+                    draft: try!(json_bool(payload, "release.draft")),
+                    prerelease: try!(json_bool(payload, "release.prerelease")),
+                    name: try!(json_opt_str(payload, "release.name")),
+                    body: try!(json_opt_str(payload, "release.body")),
                 }
             }
-            "WatchEvent" => EventPayload::Watch { action: payload["action"].as_str().unwrap().to_string() },
+            "SponsorshipEvent" => {
+                EventPayload::Sponsorship {
+                    action: try!(json_str(payload, "action")),
+                    sponsor: try!(json_str(payload, "sponsorship.sponsor.login")),
+                    sponsorable: try!(json_str(payload, "sponsorship.sponsorable.login")),
+                    tier: try!(json_opt_str(payload, "sponsorship.tier.name")),
+                }
+            }
+            "WatchEvent" => EventPayload::Watch { action: WatchAction::from(&try!(json_str(payload, "action"))[..]) },
             t => EventPayload::Other { event_type: t.to_string() },
-        }
+        })
     }
-}
 
-impl fmt::Display for Event {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        try!(write!(f, "{}: ", self.created_at.format("%d.%m.%Y %r")));
+    /// Get the names of all payload types, as used for `"type"` in `to_json()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dishub::ops::EventPayload;
+    /// assert!(EventPayload::type_names().contains(&"Push"));
+    /// assert!(!EventPayload::type_names().contains(&"PushEvent"));
+    /// ```
+    pub fn type_names() -> &'static [&'static str] {
+        &["CommitComment",
+          "Create",
+          "Delete",
+          "Discussion",
+          "DiscussionComment",
+          "Fork",
+          "ForkApply",
+          "Gollum",
+          "IssueComment",
+          "Issues",
+          "Member",
+          "Public",
+          "PullRequest",
+          "PullRequestReview",
+          "PullRequestReviewComment",
+          "PullRequestReviewThread",
+          "Push",
+          "Release",
+          "Sponsorship",
+          "Watch",
+          "Other"]
+    }
 
-        match self.payload {
-            EventPayload::CommitComment { ref commit_id, .. } => {
-                try!(write!(f, "{} commented on {} in {}", self.actor, commit_id, self.repo));
+    /// Get the name of this payload's type, as used for `"type"` in `to_json()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dishub::ops::EventPayload;
+    /// assert_eq!(EventPayload::Public.type_name(), "Public");
+    /// ```
+    pub fn type_name(&self) -> &'static str {
+        match *self {
+            EventPayload::CommitComment { .. } => "CommitComment",
+            EventPayload::Create { .. } => "Create",
+            EventPayload::Delete { .. } => "Delete",
+            EventPayload::Discussion { .. } => "Discussion",
+            EventPayload::DiscussionComment { .. } => "DiscussionComment",
+            EventPayload::Fork { .. } => "Fork",
+            EventPayload::ForkApply { .. } => "ForkApply",
+            EventPayload::Gollum { .. } => "Gollum",
+            EventPayload::IssueComment { .. } => "IssueComment",
+            EventPayload::Issues { .. } => "Issues",
+            EventPayload::Member { .. } => "Member",
+            EventPayload::Public => "Public",
+            EventPayload::PullRequest { .. } => "PullRequest",
+            EventPayload::PullRequestReview { .. } => "PullRequestReview",
+            EventPayload::PullRequestReviewComment { .. } => "PullRequestReviewComment",
+            EventPayload::PullRequestReviewThread { .. } => "PullRequestReviewThread",
+            EventPayload::Push { .. } => "Push",
+            EventPayload::Release { .. } => "Release",
+            EventPayload::Sponsorship { .. } => "Sponsorship",
+            EventPayload::Watch { .. } => "Watch",
+            EventPayload::Other { .. } => "Other",
+        }
+    }
+
+    /// Serialise the payload to dishub's JSON representation, which `from_json()` reads back losslessly.
+    ///
+    /// The payload is an object with the variant's name under `"type"`, and each field under a key named the same as the field,
+    /// `Option`s are `null` if `None`, `Gollum`'s `pages` are serialised with `GollumPayload::to_json()`,
+    /// and `Push`'s `commits` with `Commit::to_json()`.
+    ///
+    /// For example:
+    ///
+    /// ```json
+    /// {
+    ///   "type": "Create",
+    ///   "ref_type": "repository",
+    ///   "ref_name": null,
+    ///   "master_branch": "master",
+    ///   "repo_description": "App for posting GitHub activity on Discord"
+    /// }
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dishub::ops::EventPayload;
+    /// let payload = EventPayload::Sponsorship {
+    ///     action: "created".to_string(),
+    ///     sponsor: "sehe".to_string(),
+    ///     sponsorable: "nabijaczleweli".to_string(),
+    ///     tier: None,
+    /// };
+    ///
+    /// let json = payload.to_json();
+    /// assert_eq!(json["type"], "Sponsorship");
+    /// assert_eq!(json["sponsor"], "sehe");
+    /// assert!(json["tier"].is_null());
+    /// assert_eq!(EventPayload::from_json(&json), Ok(payload));
+    /// ```
+    pub fn to_json(&self) -> JsonValue {
+        match *self {
+            EventPayload::CommitComment { ref content, ref commit_id, id } => {
+                object!{
+                    "type" => "CommitComment",
+                    "content" => content.as_str(),
+                    "commit_id" => commit_id.as_str(),
+                    "id" => id,
+                }
             }
-            EventPayload::Create { ref ref_type, ref ref_name, ref repo_description, .. } => {
-                try!(write!(f, "{} created {}", self.actor, ref_type));
-                if let Some(ref ref_name) = *ref_name {
-                    try!(write!(f, " {}", ref_name));
-                } else {
-                    try!(write!(f, " \"{}\"", repo_description));
+            EventPayload::Create { ref ref_type, ref ref_name, ref master_branch, ref repo_description } => {
+                object!{
+                    "type" => "Create",
+                    "ref_type" => ref_type.as_str(),
+                    "ref_name" => ref_name.as_ref().map(String::as_str),
+                    "master_branch" => master_branch.as_str(),
+                    "repo_description" => repo_description.as_str(),
                 }
             }
             EventPayload::Delete { ref ref_type, ref ref_name } => {
-                try!(write!(f, "{} deleted {} {}", self.actor, ref_type, ref_name));
+                object!{
+                    "type" => "Delete",
+                    "ref_type" => ref_type.as_str(),
+                    "ref_name" => ref_name.as_str(),
+                }
+            }
+            EventPayload::Discussion { ref action, number, ref title, ref body, ref category } => {
+                object!{
+                    "type" => "Discussion",
+                    "action" => action.as_str(),
+                    "number" => number,
+                    "title" => title.as_str(),
+                    "body" => body.as_str(),
+                    "category" => category.as_str(),
+                }
+            }
+            EventPayload::DiscussionComment { ref action, discussion, ref body, id } => {
+                object!{
+                    "type" => "DiscussionComment",
+                    "action" => action.as_str(),
+                    "discussion" => discussion,
+                    "body" => body.as_str(),
+                    "id" => id,
+                }
             }
             EventPayload::Fork { ref new_slug } => {
-                try!(write!(f, "{} forked {} to {}", self.actor, self.repo, new_slug));
+                object!{
+                    "type" => "Fork",
+                    "new_slug" => new_slug.as_str(),
+                }
+            }
+            EventPayload::ForkApply { ref head, ref before, ref after } => {
+                object!{
+                    "type" => "ForkApply",
+                    "head" => head.as_str(),
+                    "before" => before.as_str(),
+                    "after" => after.as_str(),
+                }
             }
             EventPayload::Gollum { ref pages } => {
-                try!(write!(f, "{} changed wiki on {}:", self.actor, self.repo));
-                for &GollumPayload { ref title, ref action, .. } in pages {
-                    try!(writeln!(f, ""));
-                    try!(write!(f, "  {} \"{}\"", action, title));
+                object!{
+                    "type" => "Gollum",
+                    "pages" => pages.iter().map(GollumPayload::to_json).collect::<Vec<_>>(),
                 }
             }
-            EventPayload::IssueComment { ref action, issue, .. } => {
-                try!(write!(f, "{} {} comment to #{} on {}", self.actor, action, issue, self.repo));
+            EventPayload::IssueComment { ref action, issue, pull_request, ref body, id } => {
+                object!{
+                    "type" => "IssueComment",
+                    "action" => action.as_str(),
+                    "issue" => issue,
+                    "pull_request" => pull_request,
+                    "body" => body.as_str(),
+                    "id" => id,
+                }
             }
-            EventPayload::Issues { ref action, number, ref title, .. } => {
-                try!(write!(f, "{} {} #{} on {}: \"{}\"", self.actor, action, number, self.repo, title));
+            EventPayload::Issues { ref action, number, ref title, ref body, ref labels, ref assignees, ref milestone } => {
+                object!{
+                    "type" => "Issues",
+                    "action" => action.as_str(),
+                    "number" => number,
+                    "title" => title.as_str(),
+                    "body" => body.as_str(),
+                    "labels" => labels.clone(),
+                    "assignees" => assignees.clone(),
+                    "milestone" => milestone.as_ref().map(String::as_str),
+                }
             }
             EventPayload::Member { ref action, ref user } => {
-                try!(write!(f, "{} {} {} to {}", self.actor, action, user, self.repo));
-            }
-            EventPayload::Public => {
-                try!(write!(f, "{} made {} public", self.actor, self.repo));
-            }
-            EventPayload::PullRequest { ref action, number, ref title, merged, .. } => {
-                try!(write!(f,
-                            "{} {} #{} on {}: \"{}\"",
-                            self.actor,
-                            if merged && action == "closed" {
-                                "merged"
-                            } else {
-                                action
-                            },
-                            number,
-                            self.repo,
-                            title));
+                object!{
+                    "type" => "Member",
+                    "action" => action.as_str(),
+                    "user" => user.as_str(),
+                }
+            }
+            EventPayload::Public => object!{ "type" => "Public" },
+            EventPayload::PullRequest { ref action,
+                                        number,
+                                        ref title,
+                                        ref body,
+                                        merged,
+                                        ref merged_by,
+                                        draft,
+                                        ref head,
+                                        ref base,
+                                        additions,
+                                        deletions,
+                                        changed_files,
+                                        ref labels,
+                                        ref assignees,
+                                        ref milestone } => {
+                object!{
+                    "type" => "PullRequest",
+                    "action" => action.as_str(),
+                    "number" => number,
+                    "title" => title.as_str(),
+                    "body" => body.as_str(),
+                    "merged" => merged,
+                    "merged_by" => merged_by.as_ref().map(String::as_str),
+                    "draft" => draft,
+                    "head" => head.as_str(),
+                    "base" => base.as_str(),
+                    "additions" => additions,
+                    "deletions" => deletions,
+                    "changed_files" => changed_files,
+                    "labels" => labels.clone(),
+                    "assignees" => assignees.clone(),
+                    "milestone" => milestone.as_ref().map(String::as_str),
+                }
+            }
+            EventPayload::PullRequestReview { ref action, pr, ref state, ref body, id } => {
+                object!{
+                    "type" => "PullRequestReview",
+                    "action" => action.as_str(),
+                    "pr" => pr,
+                    "state" => state.as_str(),
+                    "body" => body.as_str(),
+                    "id" => id,
+                }
+            }
+            EventPayload::PullRequestReviewComment { ref action, pr, ref body, id } => {
+                object!{
+                    "type" => "PullRequestReviewComment",
+                    "action" => action.as_str(),
+                    "pr" => pr,
+                    "body" => body.as_str(),
+                    "id" => id,
+                }
+            }
+            EventPayload::PullRequestReviewThread { ref action, pr, first_comment } => {
+                object!{
+                    "type" => "PullRequestReviewThread",
+                    "action" => action.as_str(),
+                    "pr" => pr,
+                    "first_comment" => first_comment,
+                }
+            }
+            EventPayload::Push { ref pushed_ref, ref prev_head, ref new_head, size, distinct_size, ref commits } => {
+                object!{
+                    "type" => "Push",
+                    "pushed_ref" => pushed_ref.as_str(),
+                    "prev_head" => prev_head.as_str(),
+                    "new_head" => new_head.as_str(),
+                    "size" => size,
+                    "distinct_size" => distinct_size,
+                    "commits" => commits.iter().map(Commit::to_json).collect::<Vec<_>>(),
+                }
+            }
+            EventPayload::Release { ref action, ref tag_name, ref target, draft, prerelease, ref name, ref body } => {
+                object!{
+                    "type" => "Release",
+                    "action" => action.as_str(),
+                    "tag_name" => tag_name.as_str(),
+                    "target" => target.as_str(),
+                    "draft" => draft,
+                    "prerelease" => prerelease,
+                    "name" => name.as_ref().map(String::as_str),
+                    "body" => body.as_ref().map(String::as_str),
+                }
+            }
+            EventPayload::Sponsorship { ref action, ref sponsor, ref sponsorable, ref tier } => {
+                object!{
+                    "type" => "Sponsorship",
+                    "action" => action.as_str(),
+                    "sponsor" => sponsor.as_str(),
+                    "sponsorable" => sponsorable.as_str(),
+                    "tier" => tier.as_ref().map(String::as_str),
+                }
+            }
+            EventPayload::Watch { ref action } => {
+                object!{
+                    "type" => "Watch",
+                    "action" => action.as_str(),
+                }
+            }
+            EventPayload::Other { ref event_type } => {
+                object!{
+                    "type" => "Other",
+                    "event_type" => event_type.as_str(),
+                }
+            }
+        }
+    }
+
+    /// Deserialise a payload from dishub's JSON representation, as produced by `to_json()`.
+    ///
+    /// A malformed payload is reported as `Error::EventParsingFailed` with an empty `id` and `tp` being the payload's `"type"`,
+    /// the `path` is relative to the whole event, i.e. starts with `"payload."`.
+    pub fn from_json(j: &JsonValue) -> Result<EventPayload, Error> {
+        EventPayload::from_dishub_json(j).map_err(|path| {
+            Error::EventParsingFailed {
+                id: String::new(),
+                tp: j["type"].as_str().unwrap_or("unknown").to_string(),
+                path: format!("payload.{}", path),
+            }
+        })
+    }
+
+    fn from_dishub_json(j: &JsonValue) -> Result<EventPayload, String> {
+        Ok(match &try!(json_str(j, "type"))[..] {
+            "CommitComment" => {
+                EventPayload::CommitComment {
+                    content: try!(json_str(j, "content")),
+                    commit_id: try!(json_str(j, "commit_id")),
+                    id: try!(json_u64(j, "id")),
+                }
+            }
+            "Create" => {
+                EventPayload::Create {
+                    ref_type: try!(json_str(j, "ref_type")),
+                    ref_name: try!(json_opt_str(j, "ref_name")),
+                    master_branch: try!(json_str(j, "master_branch")),
+                    repo_description: try!(json_str(j, "repo_description")),
+                }
+            }
+            "Delete" => {
+                EventPayload::Delete {
+                    ref_type: try!(json_str(j, "ref_type")),
+                    ref_name: try!(json_str(j, "ref_name")),
+                }
+            }
+            "Discussion" => {
+                EventPayload::Discussion {
+                    action: try!(json_str(j, "action")),
+                    number: try!(json_u64(j, "number")),
+                    title: try!(json_str(j, "title")),
+                    body: try!(json_str(j, "body")),
+                    category: try!(json_str(j, "category")),
+                }
+            }
+            "DiscussionComment" => {
+                EventPayload::DiscussionComment {
+                    action: try!(json_str(j, "action")),
+                    discussion: try!(json_u64(j, "discussion")),
+                    body: try!(json_str(j, "body")),
+                    id: try!(json_u64(j, "id")),
+                }
+            }
+            "Fork" => EventPayload::Fork { new_slug: try!(json_str(j, "new_slug")) },
+            "ForkApply" => {
+                EventPayload::ForkApply {
+                    head: try!(json_str(j, "head")),
+                    before: try!(json_str(j, "before")),
+                    after: try!(json_str(j, "after")),
+                }
+            }
+            "Gollum" => EventPayload::Gollum { pages: try!(json_members(j, "pages", GollumPayload::from_dishub_json)) },
+            "IssueComment" => {
+                EventPayload::IssueComment {
+                    action: IssueCommentAction::from(&try!(json_str(j, "action"))[..]),
+                    issue: try!(json_u64(j, "issue")),
+                    pull_request: try!(json_bool(j, "pull_request")),
+                    body: try!(json_str(j, "body")),
+                    id: try!(json_u64(j, "id")),
+                }
+            }
+            "Issues" => {
+                EventPayload::Issues {
+                    action: IssuesAction::from(&try!(json_str(j, "action"))[..]),
+                    number: try!(json_u64(j, "number")),
+                    title: try!(json_str(j, "title")),
+                    body: try!(json_str(j, "body")),
+                    labels: try!(json_str_members(j, "labels")),
+                    assignees: try!(json_str_members(j, "assignees")),
+                    milestone: try!(json_opt_str(j, "milestone")),
+                }
+            }
+            "Member" => {
+                EventPayload::Member {
+                    action: MemberAction::from(&try!(json_str(j, "action"))[..]),
+                    user: try!(json_str(j, "user")),
+                }
+            }
+            "Public" => EventPayload::Public,
+            "PullRequest" => {
+                EventPayload::PullRequest {
+                    action: PullRequestAction::from(&try!(json_str(j, "action"))[..]),
+                    number: try!(json_u64(j, "number")),
+                    title: try!(json_str(j, "title")),
+                    body: try!(json_str(j, "body")),
+                    merged: try!(json_bool(j, "merged")),
+                    merged_by: try!(json_opt_str(j, "merged_by")),
+                    draft: try!(json_bool(j, "draft")),
+                    head: try!(json_str(j, "head")),
+                    base: try!(json_str(j, "base")),
+                    additions: try!(json_opt_u64(j, "additions")),
+                    deletions: try!(json_opt_u64(j, "deletions")),
+                    changed_files: try!(json_opt_u64(j, "changed_files")),
+                    labels: try!(json_str_members(j, "labels")),
+                    assignees: try!(json_str_members(j, "assignees")),
+                    milestone: try!(json_opt_str(j, "milestone")),
+                }
+            }
+            "PullRequestReview" => {
+                EventPayload::PullRequestReview {
+                    action: try!(json_str(j, "action")),
+                    pr: try!(json_u64(j, "pr")),
+                    state: try!(json_str(j, "state")),
+                    body: try!(json_str(j, "body")),
+                    id: try!(json_u64(j, "id")),
+                }
+            }
+            "PullRequestReviewComment" => {
+                EventPayload::PullRequestReviewComment {
+                    action: try!(json_str(j, "action")),
+                    pr: try!(json_u64(j, "pr")),
+                    body: try!(json_str(j, "body")),
+                    id: try!(json_u64(j, "id")),
+                }
+            }
+            "PullRequestReviewThread" => {
+                EventPayload::PullRequestReviewThread {
+                    action: try!(json_str(j, "action")),
+                    pr: try!(json_u64(j, "pr")),
+                    first_comment: try!(json_opt_u64(j, "first_comment")),
+                }
+            }
+            "Push" => {
+                EventPayload::Push {
+                    pushed_ref: try!(json_str(j, "pushed_ref")),
+                    prev_head: try!(json_str(j, "prev_head")),
+                    new_head: try!(json_str(j, "new_head")),
+                    size: try!(json_u64(j, "size")),
+                    distinct_size: try!(json_u64(j, "distinct_size")),
+                    commits: try!(json_members(j, "commits", Commit::from_dishub_json)),
+                }
+            }
+            "Release" => {
+                EventPayload::Release {
+                    action: ReleaseAction::from(&try!(json_str(j, "action"))[..]),
+                    tag_name: try!(json_str(j, "tag_name")),
+                    target: try!(json_str(j, "target")),
+                    draft: try!(json_bool(j, "draft")),
+                    prerelease: try!(json_bool(j, "prerelease")),
+                    name: try!(json_opt_str(j, "name")),
+                    body: try!(json_opt_str(j, "body")),
+                }
+            }
+            "Sponsorship" => {
+                EventPayload::Sponsorship {
+                    action: try!(json_str(j, "action")),
+                    sponsor: try!(json_str(j, "sponsor")),
+                    sponsorable: try!(json_str(j, "sponsorable")),
+                    tier: try!(json_opt_str(j, "tier")),
+                }
+            }
+            "Watch" => EventPayload::Watch { action: WatchAction::from(&try!(json_str(j, "action"))[..]) },
+            "Other" => EventPayload::Other { event_type: try!(json_str(j, "event_type")) },
+            _ => return Err("type".to_string()),
+        })
+    }
+}
+
+impl Actor {
+    fn from_github_json(j: &JsonValue) -> Result<Actor, String> {
+        let login = try!(json_str(j, "login"));
+        Ok(Actor {
+            id: try!(json_u64(j, "id")),
+            display_login: try!(json_opt_str(j, "display_login")).unwrap_or_else(|| login.clone()),
+            login: login,
+            avatar_url: try!(json_str(j, "avatar_url")),
+        })
+    }
+
+    fn to_json(&self) -> JsonValue {
+        object!{
+            "id" => self.id,
+            "login" => self.login.as_str(),
+            "display_login" => self.display_login.as_str(),
+            "avatar_url" => self.avatar_url.as_str(),
+        }
+    }
+
+    fn from_dishub_json(j: &JsonValue) -> Result<Actor, String> {
+        Ok(Actor {
+            id: try!(json_u64(j, "id")),
+            login: try!(json_str(j, "login")),
+            display_login: try!(json_str(j, "display_login")),
+            avatar_url: try!(json_str(j, "avatar_url")),
+        })
+    }
+}
+
+impl Repo {
+    fn from_github_json(j: &JsonValue) -> Result<Repo, String> {
+        Ok(Repo {
+            id: try!(json_u64(j, "id")),
+            name: try!(json_str(j, "name")),
+        })
+    }
+
+    fn to_json(&self) -> JsonValue {
+        object!{
+            "id" => self.id,
+            "name" => self.name.as_str(),
+        }
+    }
+
+    fn from_dishub_json(j: &JsonValue) -> Result<Repo, String> {
+        Repo::from_github_json(j)
+    }
+}
+
+impl Org {
+    fn from_github_json(j: &JsonValue) -> Result<Org, String> {
+        Ok(Org {
+            id: try!(json_u64(j, "id")),
+            login: try!(json_str(j, "login")),
+            avatar_url: try!(json_str(j, "avatar_url")),
+        })
+    }
+
+    fn to_json(&self) -> JsonValue {
+        object!{
+            "id" => self.id,
+            "login" => self.login.as_str(),
+            "avatar_url" => self.avatar_url.as_str(),
+        }
+    }
+
+    fn from_dishub_json(j: &JsonValue) -> Result<Org, String> {
+        Org::from_github_json(j)
+    }
+}
+
+impl GollumPayload {
+    /// Serialise the Wiki page to dishub's JSON representation, which `from_json()` reads back losslessly.
+    ///
+    /// The page is an object with each field under a key named the same as the field.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dishub::ops::GollumPayload;
+    /// let page = GollumPayload {
+    ///     page_name: "Packages".to_string(),
+    ///     title: "Packages".to_string(),
+    ///     action: "edited".to_string(),
+    ///     sha: "bf842e3aa1fad56ef96e0256eccb3d9d8f288aab".to_string(),
+    ///     html_url: "/clibs/clib/wiki/Packages".to_string(),
+    /// };
+    ///
+    /// let json = page.to_json();
+    /// assert_eq!(json["page_name"], "Packages");
+    /// assert_eq!(GollumPayload::from_json(&json), Ok(page));
+    /// ```
+    pub fn to_json(&self) -> JsonValue {
+        object!{
+            "page_name" => self.page_name.as_str(),
+            "title" => self.title.as_str(),
+            "action" => self.action.as_str(),
+            "sha" => self.sha.as_str(),
+            "html_url" => self.html_url.as_str(),
+        }
+    }
+
+    /// Deserialise a Wiki page from dishub's JSON representation, as produced by `to_json()`.
+    ///
+    /// A malformed page is reported as `Error::EventParsingFailed` with an empty `id`, `tp` of `"Gollum"`,
+    /// and the `path` relative to the page.
+    pub fn from_json(j: &JsonValue) -> Result<GollumPayload, Error> {
+        GollumPayload::from_dishub_json(j).map_err(|path| {
+            Error::EventParsingFailed {
+                id: String::new(),
+                tp: "Gollum".to_string(),
+                path: path,
+            }
+        })
+    }
+
+    fn from_dishub_json(j: &JsonValue) -> Result<GollumPayload, String> {
+        Ok(GollumPayload {
+            page_name: try!(json_str(j, "page_name")),
+            title: try!(json_str(j, "title")),
+            action: try!(json_str(j, "action")),
+            sha: try!(json_str(j, "sha")),
+            html_url: try!(json_str(j, "html_url")),
+        })
+    }
+}
+
+impl Commit {
+    /// Serialise the commit to dishub's JSON representation, which `from_json()` reads back losslessly.
+    ///
+    /// The commit is an object with each field under a key named the same as the field,
+    /// each of the `contributors` is an object with the `"kind"` (the trailer name, e.g. `"Co-authored-by"`), `"name"`, and `"email"` keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dishub::ops::Commit;
+    /// let commit = Commit {
+    ///     sha: "46650797f4aec4d373c647062eb3df288ee7b8f2".to_string(),
+    ///     message: "Update docs for commits f69e263c6caf...40e07efdf879".to_string(),
+    ///     author_name: "Nabijaczleweli Autouploader Bot".to_string(),
+    ///     author_email: "nabijaczleweli@gmail.com".to_string(),
+    ///     distinct: true,
+    ///     contributors: vec![],
+    /// };
+    ///
+    /// let json = commit.to_json();
+    /// assert_eq!(json["distinct"], true);
+    /// assert_eq!(Commit::from_json(&json), Ok(commit));
+    /// ```
+    pub fn to_json(&self) -> JsonValue {
+        object!{
+            "sha" => self.sha.as_str(),
+            "message" => self.message.as_str(),
+            "author_name" => self.author_name.as_str(),
+            "author_email" => self.author_email.as_str(),
+            "distinct" => self.distinct,
+            "contributors" => self.contributors.iter().map(Contributor::to_json).collect::<Vec<_>>(),
+        }
+    }
+
+    /// Deserialise a commit from dishub's JSON representation, as produced by `to_json()`.
+    ///
+    /// A malformed commit is reported as `Error::EventParsingFailed` with an empty `id`, `tp` of `"Push"`,
+    /// and the `path` relative to the commit.
+    pub fn from_json(j: &JsonValue) -> Result<Commit, Error> {
+        Commit::from_dishub_json(j).map_err(|path| {
+            Error::EventParsingFailed {
+                id: String::new(),
+                tp: "Push".to_string(),
+                path: path,
+            }
+        })
+    }
+
+    fn from_dishub_json(j: &JsonValue) -> Result<Commit, String> {
+        Ok(Commit {
+            sha: try!(json_str(j, "sha")),
+            message: try!(json_str(j, "message")),
+            author_name: try!(json_str(j, "author_name")),
+            author_email: try!(json_str(j, "author_email")),
+            distinct: try!(json_bool(j, "distinct")),
+            contributors: try!(json_members(j, "contributors", Contributor::from_dishub_json)),
+        })
+    }
+
+    /// Parse the `Co-authored-by:` and `Signed-off-by:` trailers from the last paragraph of a commit message.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dishub::ops::{ContributorKind, Contributor, Commit};
+    /// assert_eq!(Commit::parse_contributors("Fix the thing\n\n\
+    ///                                        Co-authored-by: liigo <liigo@qq.com>\n\
+    ///                                        Signed-off-by: nabijaczleweli <nabijaczleweli@gmail.com>"),
+    ///            vec![Contributor {
+    ///                     kind: ContributorKind::CoAuthor,
+    ///                     name: "liigo".to_string(),
+    ///                     email: "liigo@qq.com".to_string(),
+    ///                 },
+    ///                 Contributor {
+    ///                     kind: ContributorKind::SignOff,
+    ///                     name: "nabijaczleweli".to_string(),
+    ///                     email: "nabijaczleweli@gmail.com".to_string(),
+    ///                 }]);
+    /// ```
+    pub fn parse_contributors(message: &str) -> Vec<Contributor> {
+        trailer_paragraph(message)
+            .1
+            .iter()
+            .filter_map(|l| TRAILER_RGX.captures(l))
+            .map(|c| {
+                Contributor {
+                    kind: if c.at(1).unwrap().to_lowercase() == "co-authored-by" {
+                        ContributorKind::CoAuthor
+                    } else {
+                        ContributorKind::SignOff
+                    },
+                    name: c.at(2).unwrap().to_string(),
+                    email: c.at(3).unwrap().to_string(),
+                }
+            })
+            .collect()
+    }
+
+    /// Get the abbreviated SHA, as displayed by GitHub.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dishub::ops::Commit;
+    /// let commit = Commit {
+    ///     sha: "46650797f4aec4d373c647062eb3df288ee7b8f2".to_string(),
+    ///     message: "Update docs for commits f69e263c6caf...40e07efdf879\n\nAutomatically.".to_string(),
+    ///     author_name: "Nabijaczleweli Autouploader Bot".to_string(),
+    ///     author_email: "nabijaczleweli@gmail.com".to_string(),
+    ///     distinct: true,
+    ///     contributors: vec![],
+    /// };
+    /// assert_eq!(commit.short_sha(), "4665079");
+    /// assert_eq!(commit.title(), "Update docs for commits f69e263c6caf...40e07efdf879");
+    /// ```
+    pub fn short_sha(&self) -> &str {
+        &self.sha[..cmp::min(self.sha.len(), 7)]
+    }
+
+    /// Get the first line of the commit message.
+    pub fn title(&self) -> &str {
+        self.message.lines().next().unwrap_or("").trim()
+    }
+
+    /// Get the commit's URL on GitHub, given the repository it was pushed to.
+    pub fn url(&self, repo: &Repo) -> String {
+        self.url_at(repo, GITHUB_WEB_URL)
+    }
+
+    /// Get the commit's URL on the GitHub instance with the specified web interface base URL, given the repository it was pushed to.
+    pub fn url_at(&self, repo: &Repo, web_url: &str) -> String {
+        format!("{}/{}/commit/{}", web_url, repo, self.sha)
+    }
+
+    /// Get the commit message with the `Co-authored-by:` and `Signed-off-by:` trailers removed, for display.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dishub::ops::Commit;
+    /// let commit = Commit {
+    ///     sha: "46650797f4aec4d373c647062eb3df288ee7b8f2".to_string(),
+    ///     message: "Fix the thing\n\nIt was broken.\n\nCo-authored-by: liigo <liigo@qq.com>".to_string(),
+    ///     author_name: "nabijaczleweli".to_string(),
+    ///     author_email: "nabijaczleweli@gmail.com".to_string(),
+    ///     distinct: true,
+    ///     contributors: vec![],
+    /// };
+    /// assert_eq!(commit.message_without_trailers(), "Fix the thing\n\nIt was broken.");
+    /// ```
+    pub fn message_without_trailers(&self) -> String {
+        let (body, trailers) = trailer_paragraph(&self.message);
+        if !trailers.iter().any(|l| TRAILER_RGX.is_match(l)) {
+            return self.message.clone();
+        }
+
+        let mut message = body.join("\n").trim_right().to_string();
+        let kept = trailers.into_iter().filter(|l| !TRAILER_RGX.is_match(l)).collect::<Vec<_>>();
+        if kept.iter().any(|l| !l.trim().is_empty()) {
+            message.push_str("\n\n");
+            message.push_str(kept.join("\n").trim());
+        }
+        message
+    }
+
+    /// Get the names of everyone credited with the commit, i.e. the author and all the contributors, without duplicate e-mails.
+    pub fn credited(&self) -> Vec<&str> {
+        let mut emails = vec![self.author_email.to_lowercase()];
+        let mut names = vec![&self.author_name[..]];
+        for c in &self.contributors {
+            let email = c.email.to_lowercase();
+            if !emails.contains(&email) {
+                emails.push(email);
+                names.push(&c.name);
+            }
+        }
+        names
+    }
+}
+
+impl Contributor {
+    fn to_json(&self) -> JsonValue {
+        object!{
+            "kind" => self.kind.as_str(),
+            "name" => self.name.as_str(),
+            "email" => self.email.as_str(),
+        }
+    }
+
+    fn from_dishub_json(j: &JsonValue) -> Result<Contributor, String> {
+        Ok(Contributor {
+            kind: match &try!(json_str(j, "kind"))[..] {
+                "Co-authored-by" => ContributorKind::CoAuthor,
+                "Signed-off-by" => ContributorKind::SignOff,
+                _ => return Err("kind".to_string()),
+            },
+            name: try!(json_str(j, "name")),
+            email: try!(json_str(j, "email")),
+        })
+    }
+}
+
+impl ContributorKind {
+    /// Get the name of the trailer, e.g. `"Co-authored-by"`.
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            ContributorKind::CoAuthor => "Co-authored-by",
+            ContributorKind::SignOff => "Signed-off-by",
+        }
+    }
+}
+
+impl IssuesAction {
+    /// Get the action's name as given by GitHub.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dishub::ops::IssuesAction;
+    /// assert_eq!(IssuesAction::Demilestoned.as_str(), "demilestoned");
+    /// assert_eq!(IssuesAction::Unknown("transferred".to_string()).as_str(), "transferred");
+    /// ```
+    pub fn as_str(&self) -> &str {
+        match *self {
+            IssuesAction::Assigned => "assigned",
+            IssuesAction::Unassigned => "unassigned",
+            IssuesAction::Labeled => "labeled",
+            IssuesAction::Unlabeled => "unlabeled",
+            IssuesAction::Opened => "opened",
+            IssuesAction::Edited => "edited",
+            IssuesAction::Milestoned => "milestoned",
+            IssuesAction::Demilestoned => "demilestoned",
+            IssuesAction::Closed => "closed",
+            IssuesAction::Reopened => "reopened",
+            IssuesAction::Unknown(ref action) => action,
+        }
+    }
+}
+
+impl<'a> From<&'a str> for IssuesAction {
+    /// Parse an action name as given by GitHub, unrecognised names become `Unknown`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dishub::ops::IssuesAction;
+    /// assert_eq!(IssuesAction::from("opened"), IssuesAction::Opened);
+    /// assert_eq!(IssuesAction::from("transferred"), IssuesAction::Unknown("transferred".to_string()));
+    /// ```
+    fn from(action: &'a str) -> IssuesAction {
+        match action {
+            "assigned" => IssuesAction::Assigned,
+            "unassigned" => IssuesAction::Unassigned,
+            "labeled" => IssuesAction::Labeled,
+            "unlabeled" => IssuesAction::Unlabeled,
+            "opened" => IssuesAction::Opened,
+            "edited" => IssuesAction::Edited,
+            "milestoned" => IssuesAction::Milestoned,
+            "demilestoned" => IssuesAction::Demilestoned,
+            "closed" => IssuesAction::Closed,
+            "reopened" => IssuesAction::Reopened,
+            action => IssuesAction::Unknown(action.to_string()),
+        }
+    }
+}
+
+impl IssueCommentAction {
+    /// Get the action's name as given by GitHub.
+    pub fn as_str(&self) -> &str {
+        match *self {
+            IssueCommentAction::Created => "created",
+            IssueCommentAction::Edited => "edited",
+            IssueCommentAction::Deleted => "deleted",
+            IssueCommentAction::Unknown(ref action) => action,
+        }
+    }
+}
+
+impl<'a> From<&'a str> for IssueCommentAction {
+    fn from(action: &'a str) -> IssueCommentAction {
+        match action {
+            "created" => IssueCommentAction::Created,
+            "edited" => IssueCommentAction::Edited,
+            "deleted" => IssueCommentAction::Deleted,
+            action => IssueCommentAction::Unknown(action.to_string()),
+        }
+    }
+}
+
+impl MemberAction {
+    /// Get the action's name as given by GitHub.
+    pub fn as_str(&self) -> &str {
+        match *self {
+            MemberAction::Added => "added",
+            MemberAction::Unknown(ref action) => action,
+        }
+    }
+}
+
+impl<'a> From<&'a str> for MemberAction {
+    fn from(action: &'a str) -> MemberAction {
+        match action {
+            "added" => MemberAction::Added,
+            action => MemberAction::Unknown(action.to_string()),
+        }
+    }
+}
+
+impl PullRequestAction {
+    /// Get the action's name as given by GitHub.
+    pub fn as_str(&self) -> &str {
+        match *self {
+            PullRequestAction::Assigned => "assigned",
+            PullRequestAction::Unassigned => "unassigned",
+            PullRequestAction::ReviewRequested => "review_requested",
+            PullRequestAction::ReviewRequestRemoved => "review_request_removed",
+            PullRequestAction::Labeled => "labeled",
+            PullRequestAction::Unlabeled => "unlabeled",
+            PullRequestAction::Opened => "opened",
+            PullRequestAction::Edited => "edited",
+            PullRequestAction::Closed => "closed",
+            PullRequestAction::Reopened => "reopened",
+            PullRequestAction::Synchronize => "synchronize",
+            PullRequestAction::Unknown(ref action) => action,
+        }
+    }
+}
+
+impl<'a> From<&'a str> for PullRequestAction {
+    fn from(action: &'a str) -> PullRequestAction {
+        match action {
+            "assigned" => PullRequestAction::Assigned,
+            "unassigned" => PullRequestAction::Unassigned,
+            "review_requested" => PullRequestAction::ReviewRequested,
+            "review_request_removed" => PullRequestAction::ReviewRequestRemoved,
+            "labeled" => PullRequestAction::Labeled,
+            "unlabeled" => PullRequestAction::Unlabeled,
+            "opened" => PullRequestAction::Opened,
+            "edited" => PullRequestAction::Edited,
+            "closed" => PullRequestAction::Closed,
+            "reopened" => PullRequestAction::Reopened,
+            "synchronize" => PullRequestAction::Synchronize,
+            action => PullRequestAction::Unknown(action.to_string()),
+        }
+    }
+}
+
+impl ReleaseAction {
+    /// Get the action's name as given by GitHub.
+    pub fn as_str(&self) -> &str {
+        match *self {
+            ReleaseAction::Published => "published",
+            ReleaseAction::Unknown(ref action) => action,
+        }
+    }
+}
+
+impl<'a> From<&'a str> for ReleaseAction {
+    fn from(action: &'a str) -> ReleaseAction {
+        match action {
+            "published" => ReleaseAction::Published,
+            action => ReleaseAction::Unknown(action.to_string()),
+        }
+    }
+}
+
+impl WatchAction {
+    /// Get the action's name as given by GitHub.
+    pub fn as_str(&self) -> &str {
+        match *self {
+            WatchAction::Started => "started",
+            WatchAction::Unknown(ref action) => action,
+        }
+    }
+}
+
+impl<'a> From<&'a str> for WatchAction {
+    fn from(action: &'a str) -> WatchAction {
+        match action {
+            "started" => WatchAction::Started,
+            action => WatchAction::Unknown(action.to_string()),
+        }
+    }
+}
+
+impl Hash for Event {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.created_at.hash(state);
+        self.actor.hash(state);
+        self.repo.hash(state);
+        self.org.hash(state);
+        self.id.hash(state);
+        self.payload.hash(state);
+    }
+}
+
+impl fmt::Display for Actor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.display_login)
+    }
+}
+
+impl fmt::Display for Repo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+impl fmt::Display for Org {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.login)
+    }
+}
+
+impl fmt::Display for IssuesAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl fmt::Display for IssueCommentAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl fmt::Display for MemberAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl fmt::Display for PullRequestAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl fmt::Display for ReleaseAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl fmt::Display for WatchAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.created_at.format("%d.%m.%Y %r"), Summary(self, Catalogue::english()))
+    }
+}
+
+impl<'a> fmt::Display for Summary<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Summary(ev, c) = *self;
+        let (actor, repo) = (&ev.actor, &ev.repo);
+        // GitHub's words for what happened, translated if the catalogue does, optionally with underscores turned into spaces
+        let word = |kind: &str, word: &str, humanise: bool| {
+            c.word(kind, word).map(str::to_string).unwrap_or_else(|| if humanise { word.replace('_', " ") } else { word.to_string() })
+        };
+
+        let summary = match ev.payload {
+            EventPayload::CommitComment { ref commit_id, .. } => {
+                c.format("commit_comment", None, 1, &[("actor", actor), ("commit", commit_id), ("repo", repo)])
+            }
+            EventPayload::Create { ref ref_type, ref ref_name, ref repo_description, .. } => {
+                let tpe = word("ref_type", ref_type, false);
+                match *ref_name {
+                    Some(ref ref_name) => c.format("create", Some(ref_type), 1, &[("actor", actor), ("ref_type", &tpe), ("ref_name", ref_name)]),
+                    None => c.format("create_repository", None, 1, &[("actor", actor), ("ref_type", &tpe), ("description", repo_description)]),
+                }
+            }
+            EventPayload::Delete { ref ref_type, ref ref_name } => {
+                c.format("delete",
+                         Some(ref_type),
+                         1,
+                         &[("actor", actor), ("ref_type", &word("ref_type", ref_type, false)), ("ref_name", ref_name)])
+            }
+            EventPayload::Discussion { ref action, number, ref title, .. } => {
+                c.format("discussion",
+                         Some(action),
+                         1,
+                         &[("actor", actor), ("action", &word("action", action, true)), ("number", &number), ("repo", repo), ("title", title)])
+            }
+            EventPayload::DiscussionComment { ref action, discussion, .. } => {
+                c.format("discussion_comment",
+                         Some(action),
+                         1,
+                         &[("actor", actor), ("action", &word("action", action, false)), ("number", &discussion), ("repo", repo)])
+            }
+            EventPayload::Fork { ref new_slug } => c.format("fork", None, 1, &[("actor", actor), ("repo", repo), ("fork", new_slug)]),
+            EventPayload::ForkApply { ref head, .. } => c.format("fork_apply", None, 1, &[("actor", actor), ("head", head), ("repo", repo)]),
+            EventPayload::Gollum { ref pages } => {
+                let mut summary = c.format("gollum", None, 1, &[("actor", actor), ("repo", repo)]);
+                for &GollumPayload { ref title, ref action, .. } in pages {
+                    summary.push_str("\n  ");
+                    summary.push_str(&c.format("gollum_page", Some(action), 1, &[("action", &word("action", action, false)), ("title", title)]));
+                }
+                summary
+            }
+            EventPayload::IssueComment { ref action, issue, .. } => {
+                c.format("issue_comment",
+                         Some(action.as_str()),
+                         1,
+                         &[("actor", actor), ("action", &word("action", action.as_str(), false)), ("number", &issue), ("repo", repo)])
+            }
+            EventPayload::Issues { ref action, number, ref title, .. } => {
+                c.format("issues",
+                         Some(action.as_str()),
+                         1,
+                         &[("actor", actor), ("action", &word("action", action.as_str(), false)), ("number", &number), ("repo", repo), ("title", title)])
+            }
+            EventPayload::Member { ref action, ref user } => {
+                c.format("member",
+                         Some(action.as_str()),
+                         1,
+                         &[("actor", actor), ("action", &word("action", action.as_str(), false)), ("user", user), ("repo", repo)])
+            }
+            EventPayload::Public => c.format("public", None, 1, &[("actor", actor), ("repo", repo)]),
+            EventPayload::PullRequest { ref action, number, ref title, merged, draft, ref head, ref base, additions, deletions, .. } => {
+                let opened = *action == PullRequestAction::Opened;
+                let action = if merged && *action == PullRequestAction::Closed {
+                    "merged"
+                } else {
+                    action.as_str()
+                };
+                let pull_request = c.format(if draft { "pull_request_draft" } else { "pull_request" },
+                                            Some(action),
+                                            1,
+                                            &[("actor", actor),
+                                              ("action", &word("action", action, false)),
+                                              ("number", &number),
+                                              ("head", head),
+                                              ("base", base),
+                                              ("repo", repo),
+                                              ("title", title)]);
+                match (additions, deletions) {
+                    (Some(additions), Some(deletions)) if opened => {
+                        c.format("pull_request_stats",
+                                 None,
+                                 1,
+                                 &[("pull_request", &pull_request), ("additions", &additions), ("deletions", &deletions)])
+                    }
+                    _ => pull_request,
+                }
             }
             EventPayload::PullRequestReview { ref action, pr, ref state, .. } => {
-                try!(write!(f, "{} {} as {} #{} on {}", self.actor, action, state, pr, self.repo));
+                c.format("pull_request_review",
+                         Some(action),
+                         1,
+                         &[("actor", actor),
+                           ("action", &word("action", action, false)),
+                           ("state", &word("state", state, false)),
+                           ("number", &pr),
+                           ("repo", repo)])
             }
             EventPayload::PullRequestReviewComment { ref action, pr, .. } => {
-                try!(write!(f, "{} {} comment to #{} on {}", self.actor, action, pr, self.repo));
+                c.format("pull_request_review_comment",
+                         Some(action),
+                         1,
+                         &[("actor", actor), ("action", &word("action", action, false)), ("number", &pr), ("repo", repo)])
             }
-            EventPayload::Push { ref pushed_ref, distinct_size, .. } => {
-                try!(write!(f,
-                            "{} pushed {} commit{} to {} in {}",
-                            self.actor,
-                            distinct_size,
-                            if distinct_size != 1 { "s" } else { "" },
-                            pushed_ref.split('/').last().unwrap(),
-                            self.repo));
+            EventPayload::PullRequestReviewThread { ref action, pr, .. } => {
+                c.format("pull_request_review_thread",
+                         Some(action),
+                         1,
+                         &[("actor", actor), ("action", &word("action", action, false)), ("number", &pr), ("repo", repo)])
             }
-            EventPayload::Release { ref action, ref tag_name, ref target, draft, prerelease, ref name, .. } => {
-                try!(write!(f, "{} {} {} from {}", self.actor, action, tag_name, target));
-                if draft {
-                    try!(write!(f, " as a draft"));
-                } else if prerelease {
-                    try!(write!(f, " as a prerelease"));
+            EventPayload::Push { ref pushed_ref, distinct_size, ref commits, .. } => {
+                let mut credited: Vec<&str> = vec![];
+                for name in commits.iter().filter(|c| c.distinct).flat_map(|c| c.credited()) {
+                    if !credited.contains(&name) {
+                        credited.push(name);
+                    }
                 }
-                if let Some(ref name) = *name {
-                    try!(write!(f, " named {}", name));
+
+                c.format(if credited.is_empty() { "push" } else { "push_by" },
+                         None,
+                         distinct_size,
+                         &[("actor", actor),
+                           ("count", &distinct_size),
+                           ("authors", &join_names(&credited, c)),
+                           ("branch", &pushed_ref.split('/').last().unwrap()),
+                           ("repo", repo)])
+            }
+            EventPayload::Release { ref action, ref tag_name, ref target, draft, prerelease, ref name, .. } => {
+                let release = c.format(if draft {
+                                           "release_draft"
+                                       } else if prerelease {
+                                           "release_prerelease"
+                                       } else {
+                                           "release"
+                                       },
+                                       Some(action.as_str()),
+                                       1,
+                                       &[("actor", actor), ("action", &word("action", action.as_str(), false)), ("tag", tag_name), ("target", target)]);
+                match *name {
+                    Some(ref name) => c.format("release_name", None, 1, &[("release", &release), ("name", name)]),
+                    None => release,
                 }
             }
-            EventPayload::Watch { .. } => {
-                try!(write!(f, "{} starred {}", self.actor, self.repo));
+            EventPayload::Sponsorship { ref action, ref sponsor, ref sponsorable, ref tier } => {
+                let action_word = word("action", action, true);
+                let mut args: Vec<(&str, &fmt::Display)> = vec![("actor", actor), ("action", &action_word), ("sponsorable", sponsorable), ("sponsor", sponsor)];
+                if let Some(ref tier) = *tier {
+                    args.push(("tier", tier));
+                }
+                c.format(if tier.is_some() { "sponsorship_tier" } else { "sponsorship" }, Some(action), 1, &args)
             }
+            EventPayload::Watch { .. } => c.format("watch", None, 1, &[("actor", actor), ("repo", repo)]),
             EventPayload::Other { ref event_type } => {
-                try!(write!(f, "{} invoked an unsupported event on {}: {}", self.actor, self.repo, event_type));
+                let payload = &ev.raw["payload"];
+                let action = payload["action"].as_str().unwrap_or("invoked");
+                let action_word = word("action", action, true);
+                let event = humanise_event_type(event_type);
+                match common_payload_str(payload, "title").or_else(|| common_payload_str(payload, "name")) {
+                    Some(title) => {
+                        c.format("other_titled",
+                                 Some(action),
+                                 1,
+                                 &[("actor", actor), ("action", &action_word), ("event", &event), ("repo", repo), ("title", &title)])
+                    }
+                    None => c.format("other", Some(action), 1, &[("actor", actor), ("action", &action_word), ("event", &event), ("repo", repo)]),
+                }
             }
+        };
+
+        f.write_str(&summary)
+    }
+}
+
+
+fn parse_event_list(what: &str) -> Result<JsonValue, Error> {
+    match json::parse(what) {
+        Ok(ref events) if !events.is_array() => {
+            Err(Error::FileParsingFailed {
+                desc: "GitHub events",
+                errors: vec!["the response is not an array".to_string()],
+            })
         }
+        Ok(events) => Ok(events),
+        Err(err) => {
+            Err(Error::FileParsingFailed {
+                desc: "GitHub events",
+                errors: vec![err.to_string()],
+            })
+        }
+    }
+}
+
+/// Get the value at the specified dot-separated path.
+fn json_field<'j>(j: &'j JsonValue, path: &str) -> &'j JsonValue {
+    path.split('.').fold(j, |j, k| &j[k])
+}
+
+// The `json_*()` functions below return the path to the malformed value on failure.
+
+fn json_str(j: &JsonValue, path: &str) -> Result<String, String> {
+    json_field(j, path).as_str().map(str::to_string).ok_or_else(|| path.to_string())
+}
+
+/// Like `json_str()`, but a missing or `null` value yields `None`.
+fn json_opt_str(j: &JsonValue, path: &str) -> Result<Option<String>, String> {
+    let val = json_field(j, path);
+    if val.is_null() {
+        Ok(None)
+    } else {
+        val.as_str().map(|s| Some(s.to_string())).ok_or_else(|| path.to_string())
+    }
+}
+
+fn json_u64(j: &JsonValue, path: &str) -> Result<u64, String> {
+    json_field(j, path).as_u64().ok_or_else(|| path.to_string())
+}
+
+/// Like `json_u64()`, but a missing or `null` value yields `None`.
+fn json_opt_u64(j: &JsonValue, path: &str) -> Result<Option<u64>, String> {
+    let val = json_field(j, path);
+    if val.is_null() {
+        Ok(None)
+    } else {
+        val.as_u64().map(Some).ok_or_else(|| path.to_string())
+    }
+}
+
+fn json_bool(j: &JsonValue, path: &str) -> Result<bool, String> {
+    json_field(j, path).as_bool().ok_or_else(|| path.to_string())
+}
+
+/// Like `json_bool()`, but a missing or `null` value yields `None`.
+fn json_opt_bool(j: &JsonValue, path: &str) -> Result<Option<bool>, String> {
+    let val = json_field(j, path);
+    if val.is_null() {
+        Ok(None)
+    } else {
+        val.as_bool().map(Some).ok_or_else(|| path.to_string())
+    }
+}
+
+/// Parse each element of the array at the specified path, the returned path will contain the failed element's index.
+///
+/// `f` can return an empty path if the element itself is malformed.
+fn json_members<T, F: Fn(&JsonValue) -> Result<T, String>>(j: &JsonValue, path: &str, f: F) -> Result<Vec<T>, String> {
+    let arr = json_field(j, path);
+    if !arr.is_array() {
+        return Err(path.to_string());
+    }
+
+    arr.members()
+        .enumerate()
+        .map(|(i, m)| {
+            f(m).map_err(|p| if p.is_empty() {
+                format!("{}[{}]", path, i)
+            } else {
+                format!("{}[{}].{}", path, i, p)
+            })
+        })
+        .collect()
+}
+
+/// Like `json_members()`, but a missing or `null` array yields no elements.
+fn json_opt_members<T, F: Fn(&JsonValue) -> Result<T, String>>(j: &JsonValue, path: &str, f: F) -> Result<Vec<T>, String> {
+    if json_field(j, path).is_null() {
+        Ok(vec![])
+    } else {
+        json_members(j, path, f)
+    }
+}
+
+/// Get the array of strings at the specified path.
+fn json_str_members(j: &JsonValue, path: &str) -> Result<Vec<String>, String> {
+    json_members(j, path, |s| s.as_str().map(str::to_string).ok_or_else(String::new))
+}
+
+/// Get the string under `key` in the payload or in any object directly inside it.
+///
+/// Used for unknown events, where, e.g., the title can be under `issue.title` or `discussion.title`.
+fn common_payload_str<'j>(payload: &'j JsonValue, key: &str) -> Option<&'j str> {
+    payload[key].as_str().or_else(|| payload.entries().filter_map(|(_, v)| v[key].as_str()).next())
+}
+
+/// Turn an event type into lowercase words, e.g. `"MergeQueueEntryEvent"` -> `"merge queue entry"`.
+fn humanise_event_type(tpe: &str) -> String {
+    let tpe = if tpe.ends_with("Event") && tpe.len() > "Event".len() {
+        &tpe[..tpe.len() - "Event".len()]
+    } else {
+        tpe
+    };
+
+    let mut out = String::new();
+    for c in tpe.chars() {
+        if c.is_uppercase() && !out.is_empty() {
+            out.push(' ');
+        }
+        out.extend(c.to_lowercase());
+    }
+    out
+}
+
+/// Split a commit message into the lines before its last paragraph and the lines of the last paragraph, which can contain trailers.
+///
+/// The last paragraph is empty if the message has only one.
+fn trailer_paragraph(message: &str) -> (Vec<&str>, Vec<&str>) {
+    let mut lines = message.lines().collect::<Vec<_>>();
+    while lines.last().map(|l| l.trim().is_empty()).unwrap_or(false) {
+        lines.pop();
+    }
+
+    match lines.iter().rposition(|l| l.trim().is_empty()) {
+        Some(idx) => {
+            let trailers = lines.split_off(idx + 1);
+            (lines, trailers)
+        }
+        None => (lines, vec![]),
+    }
+}
 
-        Ok(())
+/// Join names into a list with the catalogue's `"list"` message, e.g. `["A", "B", "C"]` -> `"A, B and C"` in English.
+fn join_names(names: &[&str], catalogue: &Catalogue) -> String {
+    match names.split_last() {
+        Some((last, rest)) if !rest.is_empty() => catalogue.format("list", None, 1, &[("list", &rest.join(", ")), ("last", last)]),
+        Some((last, _)) => last.to_string(),
+        None => String::new(),
     }
 }
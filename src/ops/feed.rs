@@ -1,5 +1,10 @@
-use self::super::{AppTokens, Event, read_toml_file, github};
+use self::super::render::{TemplateRenderer, RenderOptions, TimeFormat, TimeStyle, Renderer, Template, Timezone, Preview, Format};
+use self::super::{AppTokens, EventPayload, Event, read_toml_file, github};
+use self::super::github::GitHubUrls;
+use self::super::retry::RetryPolicy;
+use self::super::locale::Catalogue;
 use chrono::{FixedOffset, Duration, DateTime, Local};
+use std::collections::BTreeMap;
 use self::super::super::Error;
 use toml::encode_str;
 use std::path::Path;
@@ -28,6 +33,56 @@ pub struct Feed {
     pub next_min: Option<DateTime<FixedOffset>>,
     /// Latest event's ID, this is required because GH API returns *all* events despite passing an ETag.
     pub latest_event: Option<u64>,
+
+    /// How to render the events before posting them.
+    ///
+    /// Corresponds to `format` in `feeds.toml`, `Format::Plain` if not specified.
+    pub format: Format,
+    /// Templates to render events with instead of `format`, keyed by payload type name, see `EventPayload::type_name()`.
+    ///
+    /// Corresponds to the `templates` table in `feeds.toml`.
+    pub templates: BTreeMap<String, Template>,
+    /// How to display event times.
+    ///
+    /// Corresponds to `timezone` and `time_format` in `feeds.toml`, see `Timezone::from_name()` and `TimeStyle::from_name()`,
+    /// `TimeFormat::default()` if not specified.
+    pub time: TimeFormat,
+    /// Repositories whose events are posted without escaping markdown and mentions, see `RenderOptions::trusted`.
+    ///
+    /// Corresponds to `trusted` in `feeds.toml`, empty if not specified.
+    pub trusted: Vec<String>,
+    /// How many characters of event bodies to include, if any, see `RenderOptions::excerpt`.
+    ///
+    /// Corresponds to `excerpt` in `feeds.toml`, none if not specified.
+    pub excerpt: Option<usize>,
+    /// How many of a push's commits to list, see `RenderOptions::max_commits`.
+    ///
+    /// Corresponds to `max_commits` in `feeds.toml`, `5` if not specified.
+    pub max_commits: usize,
+    /// Whether to only list commits pushed for the first time, see `RenderOptions::distinct_commits_only`.
+    ///
+    /// Corresponds to `distinct_commits_only` in `feeds.toml`, `false` if not specified.
+    pub distinct_commits_only: bool,
+    /// Which URLs Discord shows previews for, keyed by payload type name or `"default"`, see `RenderOptions::previews`.
+    ///
+    /// Corresponds to the `previews` table in `feeds.toml`, none if not specified.
+    pub previews: BTreeMap<String, Preview>,
+    /// The base URL of the API of the GitHub instance the subject is on, see `GitHubUrls::api`.
+    ///
+    /// Corresponds to `api_url` in `feeds.toml`, the installation's (see `github::urls()`) if not specified.
+    pub api_url: Option<String>,
+    /// The base URL of the web interface of the GitHub instance the subject is on, see `GitHubUrls::web`.
+    ///
+    /// Corresponds to `web_url` in `feeds.toml`, derived from `api_url` (see `GitHubUrls::web_url_for()`) if only that's specified,
+    /// and the installation's (see `github::urls()`) if neither is.
+    pub web_url: Option<String>,
+    /// The name of the catalogue to describe events with, see `Catalogue::load()`.
+    ///
+    /// Corresponds to `locale` in `feeds.toml`, the channel's locale in the `channel_locales` table
+    /// or English if not specified.
+    pub locale: Option<String>,
+    /// The catalogue to describe events with, loaded per `locale` or the channel's locale.
+    pub catalogue: Catalogue,
 }
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq, RustcEncodable, RustcDecodable)]
@@ -41,11 +96,25 @@ pub struct FeedForSerialisation {
     pub latest: Option<String>,
     pub next_min: Option<String>,
     pub latest_event: Option<u64>,
+
+    pub format: Option<String>,
+    pub templates: Option<BTreeMap<String, String>>,
+    pub timezone: Option<String>,
+    pub time_format: Option<String>,
+    pub trusted: Option<Vec<String>>,
+    pub excerpt: Option<usize>,
+    pub max_commits: Option<usize>,
+    pub distinct_commits_only: Option<bool>,
+    pub previews: Option<BTreeMap<String, String>>,
+    pub api_url: Option<String>,
+    pub web_url: Option<String>,
+    pub locale: Option<String>,
 }
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq, RustcEncodable, RustcDecodable)]
 struct Feeds {
     feed: Vec<FeedForSerialisation>,
+    channel_locales: Option<BTreeMap<String, String>>,
 }
 
 impl Feed {
@@ -59,43 +128,206 @@ impl Feed {
             latest: None,
             next_min: None,
             latest_event: None,
+            format: Format::Plain,
+            templates: BTreeMap::new(),
+            time: TimeFormat::default(),
+            trusted: vec![],
+            excerpt: None,
+            max_commits: RenderOptions::default().max_commits,
+            distinct_commits_only: false,
+            previews: BTreeMap::new(),
+            api_url: None,
+            web_url: None,
+            locale: None,
+            catalogue: Catalogue::default(),
         }
     }
 
     /// Read the application feeds from the specified file.
+    ///
+    /// Their catalogues are loaded from the `locales` directory next to it, or shipped with dishub, see `Catalogue::load()`.
     pub fn read(p: &Path) -> Result<Vec<Feed>, Error> {
         let feeds: Feeds = try!(read_toml_file(p, "Followed feeds"));
-        Ok(feeds.feed.into_iter().map(FeedForSerialisation::into).collect())
+        let config_dir = p.parent().unwrap_or_else(|| Path::new("."));
+        let channel_locales = feeds.channel_locales.unwrap_or_default();
+
+        let mut errors: Vec<_> = feeds.feed
+            .iter()
+            .filter_map(|f| f.format.as_ref().map(|fmt| (&f.subject, fmt)))
+            .filter(|&(_, fmt)| Format::from_name(fmt).is_none())
+            .map(|(subject, fmt)| format!("Unknown format \"{}\" for {}", fmt, subject))
+            .collect();
+        for channel in channel_locales.keys().filter(|c| c.parse::<u64>().is_err()) {
+            errors.push(format!("Invalid channel ID \"{}\" in channel_locales", channel));
+        }
+
+        let mut catalogues = BTreeMap::new();
+        let mut locales = vec![];
+        for f in &feeds.feed {
+            let locale = f.locale.as_ref().or_else(|| channel_locales.get(&f.channel.to_string()));
+            if let Some(locale) = locale {
+                if !catalogues.contains_key(locale) {
+                    match Catalogue::load(locale, config_dir) {
+                        Ok(catalogue) => {
+                            catalogues.insert(locale.clone(), catalogue);
+                        }
+                        Err(errs) => errors.extend(errs.into_iter().map(|e| format!("{} for {}", e, f.subject))),
+                    }
+                }
+            }
+            locales.push(locale.cloned());
+
+            if let Some(ref tz) = f.timezone {
+                if Timezone::from_name(tz).is_none() {
+                    errors.push(format!("Unknown timezone \"{}\" for {}", tz, f.subject));
+                }
+            }
+            if let Some(ref fmt) = f.time_format {
+                if TimeStyle::from_name(fmt).is_none() {
+                    errors.push(format!("Invalid time format \"{}\" for {}", fmt, f.subject));
+                }
+            }
+            for repo in f.trusted.iter().flat_map(|t| t) {
+                let parts: Vec<_> = repo.split('/').collect();
+                if parts.len() != 2 || parts.iter().any(|p| p.is_empty()) {
+                    errors.push(format!("Invalid trusted repository \"{}\" for {}", repo, f.subject));
+                }
+            }
+            if f.excerpt == Some(0) {
+                errors.push(format!("Excerpt length 0 for {}, remove it to disable excerpts", f.subject));
+            }
+            for (tp, template) in f.templates.iter().flat_map(|t| t) {
+                if !EventPayload::type_names().contains(&&tp[..]) {
+                    errors.push(format!("Unknown event type \"{}\" for template for {}", tp, f.subject));
+                } else if let Err(e) = Template::parse(template) {
+                    errors.push(format!("{} template for {}: {}", tp, f.subject, e));
+                }
+            }
+            for (tp, preview) in f.previews.iter().flat_map(|p| p) {
+                if tp != "default" && !EventPayload::type_names().contains(&&tp[..]) {
+                    errors.push(format!("Unknown event type \"{}\" for previews for {}", tp, f.subject));
+                } else if Preview::from_name(preview).is_none() {
+                    errors.push(format!("Invalid preview \"{}\" for {} for {}", preview, tp, f.subject));
+                }
+            }
+            for &(url, desc) in &[(&f.api_url, "API"), (&f.web_url, "web")] {
+                if let Some(ref url) = *url {
+                    if GitHubUrls::base_url(url).is_none() {
+                        errors.push(format!("Invalid {} URL \"{}\" for {}", desc, url, f.subject));
+                    }
+                }
+            }
+            match (f.api_url.as_ref().and_then(|url| GitHubUrls::base_url(url)), &f.web_url) {
+                (Some(ref api), &None) if GitHubUrls::web_url_for(api).is_none() => {
+                    errors.push(format!("Can't tell the web URL for API URL \"{}\" for {}, set web_url as well", api, f.subject))
+                }
+                (None, &Some(ref web)) if f.api_url.is_none() => errors.push(format!("Web URL \"{}\" without api_url for {}", web, f.subject)),
+                _ => {}
+            }
+        }
+        if !errors.is_empty() {
+            return Err(Error::FileParsingFailed {
+                desc: "Followed feeds",
+                errors: errors,
+            });
+        }
+
+        Ok(feeds.feed
+            .into_iter()
+            .zip(locales)
+            .map(|(f, locale)| {
+                let mut feed: Feed = f.into();
+                if let Some(catalogue) = locale.and_then(|l| catalogues.get(&l)) {
+                    feed.catalogue = catalogue.clone();
+                }
+                feed
+            })
+            .collect())
     }
 
     /// Save the application feeds to the specified file.
+    ///
+    /// The `channel_locales` table already in the file, if any, is kept.
     pub fn write(feeds: Vec<Feed>, p: &Path) {
-        File::create(p).unwrap().write_all(encode_str(&Feeds { feed: feeds.into_iter().map(FeedForSerialisation::from).collect() }).as_bytes()).unwrap();
+        let channel_locales = read_toml_file::<Feeds>(p, "Followed feeds").ok().and_then(|f| f.channel_locales);
+        File::create(p)
+            .unwrap()
+            .write_all(encode_str(&Feeds {
+                    feed: feeds.into_iter().map(FeedForSerialisation::from).collect(),
+                    channel_locales: channel_locales,
+                })
+                .as_bytes())
+            .unwrap();
+    }
+
+    /// Get the renderer for this feed's events: `format`'s, unless there's a template for the event type.
+    pub fn renderer(&self) -> Box<Renderer> {
+        if self.templates.is_empty() {
+            self.format.renderer()
+        } else {
+            Box::new(TemplateRenderer {
+                templates: self.templates.clone(),
+                fallback: self.format.renderer(),
+            })
+        }
     }
 
-    pub fn poll(&mut self, tkn: &AppTokens) -> Result<Vec<Event>, Error> {
-        let (mut events, next) = if self.e_tag.is_none() {
-            let (ctnt, etag, next) = try!(if !self.subject.contains('/') {
-                github::poll_user_events_new(&self.subject, tkn)
+    /// Get the options to render this feed's events with.
+    pub fn render_options(&self) -> RenderOptions {
+        RenderOptions {
+            time: self.time.clone(),
+            trusted: self.trusted.clone(),
+            excerpt: self.excerpt,
+            max_commits: self.max_commits,
+            distinct_commits_only: self.distinct_commits_only,
+            catalogue: self.catalogue.clone(),
+            previews: self.previews.clone(),
+            web_url: self.github_urls().web,
+        }
+    }
+
+    /// Get the GitHub instance this feed's subject is on, per `api_url` and `web_url`, or the installation's.
+    pub fn github_urls(&self) -> GitHubUrls {
+        match self.api_url {
+            Some(ref api) => {
+                GitHubUrls {
+                    api: api.clone(),
+                    web: self.web_url.clone().or_else(|| GitHubUrls::web_url_for(api)).unwrap_or_else(|| github::urls().web),
+                }
+            }
+            None => github::urls(),
+        }
+    }
+
+    /// Get the new events in this feed, oldest first, updating the polling state,
+    /// and whether some events since the previous poll were missed.
+    ///
+    /// Older pages of events are polled (see `github::poll_events_next()`) until one with `latest_event` or older turns up
+    /// (event IDs increase over time); if none does in GitHub's `EVENTS_WINDOW`, the events in between are missed.
+    ///
+    /// Transiently failed requests are retried per `retry`, and malformed events are skipped or degraded (see `Event::parse_lenient()`),
+    /// both reported to `warnings`.
+    pub fn poll<W: Write>(&mut self, tkn: &AppTokens, retry: &RetryPolicy, warnings: &mut W) -> Result<(Vec<Event>, bool), Error> {
+        let api_url = self.github_urls().api;
+        // The new ETag is only stored once all the pages are in, lest a failure in-between lose the first page's events
+        let (mut events, e_tag, mut next_page, next) = if self.e_tag.is_none() {
+            let (page, next) = try!(retry.run(warnings, || if !self.subject.contains('/') {
+                github::poll_user_events_new(&self.subject, &api_url, tkn)
             } else {
-                github::poll_repo_events_new(&self.subject, tkn)
-            });
+                github::poll_repo_events_new(&self.subject, &api_url, tkn)
+            }));
 
-            self.e_tag = Some(etag);
-            (Event::parse(&ctnt), next)
+            (try!(Feed::parse_events(&page.body, warnings)), page.etag, page.next_page, next)
         } else {
-            let (ctnt_etag, next) = try!(if !self.subject.contains('/') {
-                github::poll_user_events_update(&self.subject, self.e_tag.as_ref().unwrap(), tkn)
+            let (page, next) = try!(retry.run(warnings, || if !self.subject.contains('/') {
+                github::poll_user_events_update(&self.subject, &api_url, self.e_tag.as_ref().unwrap(), tkn)
             } else {
-                github::poll_repo_events_update(&self.subject, self.e_tag.as_ref().unwrap(), tkn)
-            });
+                github::poll_repo_events_update(&self.subject, &api_url, self.e_tag.as_ref().unwrap(), tkn)
+            }));
 
-            match ctnt_etag {
-                Some((ctnt, etag)) => {
-                    self.e_tag = Some(etag);
-                    (Event::parse(&ctnt), next)
-                }
-                None => (vec![], next),
+            match page {
+                Some(page) => (try!(Feed::parse_events(&page.body, warnings)), page.etag, page.next_page, next),
+                None => (vec![], self.e_tag.clone(), None, next),
             }
         };
 
@@ -105,13 +337,42 @@ impl Feed {
         self.latest = Some(now);
         self.next_min = Some(now + Duration::seconds(next as i64));
 
-        events.reverse();
+        let mut missed = false;
         if let Some(latest_event_id) = self.latest_event {
-            events = events.into_iter().skip_while(|ev| ev.id != latest_event_id).skip(1).collect();
+            while !events.is_empty() && !events.iter().any(|ev| ev.id <= latest_event_id) {
+                match next_page.take() {
+                    Some(ref url) if events.len() < github::EVENTS_WINDOW => {
+                        let page = try!(retry.run(warnings, || github::poll_events_next(url, &api_url, tkn)));
+                        // Events posted since shift the pages, so the start of this one can repeat the end of the previous one
+                        for ev in try!(Feed::parse_events(&page.body, warnings)) {
+                            if !events.iter().any(|e| e.id == ev.id) {
+                                events.push(ev);
+                            }
+                        }
+                        next_page = page.next_page;
+                    }
+                    _ => {
+                        missed = true;
+                        break;
+                    }
+                }
+            }
+            events.retain(|ev| ev.id > latest_event_id);
         }
+
+        self.e_tag = e_tag;
+        events.reverse();
         if !events.is_empty() {
             self.latest_event = Some(events[events.len() - 1].id);
         }
+        Ok((events, missed))
+    }
+
+    fn parse_events<W: Write>(ctnt: &str, warnings: &mut W) -> Result<Vec<Event>, Error> {
+        let (events, errors) = try!(Event::parse_lenient(ctnt));
+        for err in errors {
+            err.print_error(warnings);
+        }
         Ok(events)
     }
 }
@@ -126,6 +387,50 @@ impl From<Feed> for FeedForSerialisation {
             latest: f.latest.map(|dt| dt.to_rfc3339()),
             next_min: f.next_min.map(|dt| dt.to_rfc3339()),
             latest_event: f.latest_event,
+            format: if f.format == Format::Plain {
+                None
+            } else {
+                Some(f.format.name().to_string())
+            },
+            templates: if f.templates.is_empty() {
+                None
+            } else {
+                Some(f.templates.into_iter().map(|(tp, t)| (tp, t.source().to_string())).collect())
+            },
+            timezone: if f.time.timezone == Timezone::Original {
+                None
+            } else {
+                Some(f.time.timezone.name())
+            },
+            time_format: if f.time.style == TimeFormat::default().style {
+                None
+            } else {
+                Some(f.time.style.name())
+            },
+            trusted: if f.trusted.is_empty() {
+                None
+            } else {
+                Some(f.trusted)
+            },
+            excerpt: f.excerpt,
+            max_commits: if f.max_commits == RenderOptions::default().max_commits {
+                None
+            } else {
+                Some(f.max_commits)
+            },
+            distinct_commits_only: if f.distinct_commits_only {
+                Some(true)
+            } else {
+                None
+            },
+            previews: if f.previews.is_empty() {
+                None
+            } else {
+                Some(f.previews.into_iter().map(|(tp, p)| (tp, p.name())).collect())
+            },
+            api_url: f.api_url,
+            web_url: f.web_url,
+            locale: f.locale,
         }
     }
 }
@@ -140,6 +445,21 @@ impl Into<Feed> for FeedForSerialisation {
             latest: self.latest.map(|dts| DateTime::parse_from_rfc3339(&dts).unwrap()),
             next_min: self.next_min.map(|dts| DateTime::parse_from_rfc3339(&dts).unwrap()),
             latest_event: self.latest_event,
+            format: self.format.map(|fmt| Format::from_name(&fmt).unwrap()).unwrap_or(Format::Plain),
+            templates: self.templates.unwrap_or_default().into_iter().map(|(tp, t)| (tp, Template::parse(&t).unwrap())).collect(),
+            time: TimeFormat {
+                timezone: self.timezone.map(|tz| Timezone::from_name(&tz).unwrap()).unwrap_or(Timezone::Original),
+                style: self.time_format.map(|fmt| TimeStyle::from_name(&fmt).unwrap()).unwrap_or_else(|| TimeFormat::default().style),
+            },
+            trusted: self.trusted.unwrap_or_default(),
+            excerpt: self.excerpt,
+            max_commits: self.max_commits.unwrap_or(RenderOptions::default().max_commits),
+            distinct_commits_only: self.distinct_commits_only.unwrap_or(false),
+            previews: self.previews.unwrap_or_default().into_iter().map(|(tp, p)| (tp, Preview::from_name(&p).unwrap())).collect(),
+            api_url: self.api_url.map(|url| GitHubUrls::base_url(&url).unwrap()),
+            web_url: self.web_url.map(|url| GitHubUrls::base_url(&url).unwrap()),
+            locale: self.locale,
+            catalogue: Catalogue::default(),
         }
     }
 }
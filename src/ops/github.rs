@@ -4,25 +4,269 @@
 //!
 //! The GitHub authentication is used just to get a bigger rate limit,
 //! so if you don't need to make a lot of requests just pass an empty string.
+//! Each instance is only sent its own token, if any, see `AppTokens::github_token()`.
+//!
+//! Each function takes the base URL of the API of the GitHub instance to query, like github.com's `util::GITHUB_API_URL`,
+//! or a GitHub Enterprise Server's `https://ghe.example.com/api/v3`, see `GitHubUrls`.
+//!
+//! Requests are made with `ops::http`, unsuccessful responses are returned as errors with GitHub's message, see `response_error()`,
+//! and requests without a response as `Error::Network` or `Error::Timeout`; all can be retried with `ops::retry::RetryPolicy`.
 
 
-use hyper::header::{Authorization, IfNoneMatch, EntityTag, UserAgent, Bearer, ETag};
-use self::super::super::util::GITHUB_USER_AGENT;
+use hyper::header::{Authorization, IfNoneMatch, EntityTag, UserAgent, Headers, Bearer, ETag};
+use chrono::{FixedOffset, NaiveDateTime, Duration, DateTime, Local};
+use self::super::super::util::{GITHUB_USER_AGENT, GITHUB_API_URL, GITHUB_WEB_URL};
 use hyper::status::StatusCode;
 use self::super::super::Error;
+use self::super::http::{self, Response};
 use self::super::AppTokens;
+use std::collections::BTreeMap;
+use std::sync::{RwLock, Mutex};
 use self::headers::*;
-use hyper::Client;
-use std::io::Read;
+use std::fmt;
+use json;
 
 
 mod headers {
     header! {
         (XPollInterval, "X-Poll-Interval") => [u64]
     }
+    header! {
+        (XRateLimitLimit, "X-RateLimit-Limit") => [u64]
+    }
+    header! {
+        (XRateLimitRemaining, "X-RateLimit-Remaining") => [u64]
+    }
+    header! {
+        (XRateLimitReset, "X-RateLimit-Reset") => [i64]
+    }
+    header! {
+        (RetryAfter, "Retry-After") => [u64]
+    }
+}
+
+
+/// How many of the latest events GitHub keeps, and so can be paged through, per user or repository.
+pub const EVENTS_WINDOW: usize = 300;
+
+
+lazy_static! {
+    static ref URLS: RwLock<GitHubUrls> = RwLock::new(GitHubUrls::default());
+    static ref RATE_LIMITS: Mutex<BTreeMap<String, RateLimit>> = Mutex::new(BTreeMap::new());
+    static ref RETRY_AFTER: Mutex<BTreeMap<String, DateTime<FixedOffset>>> = Mutex::new(BTreeMap::new());
+}
+
+
+/// Where a GitHub instance is.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct GitHubUrls {
+    /// The base URL of the instance's API, without a trailing slash. Default: `util::GITHUB_API_URL`
+    pub api: String,
+    /// The base URL of the instance's web interface, without a trailing slash. Default: `util::GITHUB_WEB_URL`
+    pub web: String,
+}
+
+impl GitHubUrls {
+    /// Get the base URL from a configured one, stripping trailing slashes, or `None` if it's not an HTTP(S) URL.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dishub::ops::github::GitHubUrls;
+    /// assert_eq!(GitHubUrls::base_url("https://ghe.example.com/api/v3/"), Some("https://ghe.example.com/api/v3".to_string()));
+    /// assert_eq!(GitHubUrls::base_url("ghe.example.com"), None);
+    /// ```
+    pub fn base_url(url: &str) -> Option<String> {
+        let url = url.trim().trim_end_matches('/');
+        let lower = url.to_lowercase();
+        if (lower.starts_with("https://") && url.len() > "https://".len()) || (lower.starts_with("http://") && url.len() > "http://".len()) {
+            Some(url.to_string())
+        } else {
+            None
+        }
+    }
+
+    /// Get the base URL of the web interface of the instance with the specified API base URL, if it can be told:
+    /// without the `/api/v3` of a GitHub Enterprise Server, or the `api.` of github.com and other instances like it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dishub::ops::github::GitHubUrls;
+    /// assert_eq!(GitHubUrls::web_url_for("https://ghe.example.com/api/v3"), Some("https://ghe.example.com".to_string()));
+    /// assert_eq!(GitHubUrls::web_url_for("https://api.github.com"), Some("https://github.com".to_string()));
+    /// assert_eq!(GitHubUrls::web_url_for("https://git.example.com/github-api"), None);
+    /// ```
+    pub fn web_url_for(api: &str) -> Option<String> {
+        let lower = api.to_lowercase();
+        if lower.ends_with("/api/v3") {
+            return Some(api[..api.len() - "/api/v3".len()].to_string());
+        }
+
+        lower.find("://api.").map(|scheme_end| format!("{}://{}", &api[..scheme_end], &api[scheme_end + "://api.".len()..]))
+    }
+}
+
+impl Default for GitHubUrls {
+    fn default() -> GitHubUrls {
+        GitHubUrls {
+            api: GITHUB_API_URL.to_string(),
+            web: GITHUB_WEB_URL.to_string(),
+        }
+    }
 }
 
 
+/// A page of events polled from GitHub.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct EventsPage {
+    /// The raw JSON response.
+    pub body: String,
+    /// The event bundle's ETag, if GitHub sent one.
+    pub etag: Option<String>,
+    /// The URL of the next page of older events, if any, see `poll_events_next()`.
+    pub next_page: Option<String>,
+}
+
+
+/// GitHub's rate limit, as of the latest response.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct RateLimit {
+    /// How many requests can be made per hour, from `X-RateLimit-Limit`.
+    pub limit: u64,
+    /// How many requests are left until `reset`, from `X-RateLimit-Remaining`.
+    pub remaining: u64,
+    /// When the rate limit resets, from `X-RateLimit-Reset`.
+    pub reset: DateTime<FixedOffset>,
+}
+
+impl RateLimit {
+    /// Get the rate limit from the specified values of the `X-RateLimit-Limit`, `X-RateLimit-Remaining`,
+    /// and `X-RateLimit-Reset` (in seconds since the epoch) headers, if all are there.
+    ///
+    /// `reset` is in the same timezone as `now`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate chrono;
+    /// # extern crate dishub;
+    /// # use dishub::ops::github::RateLimit;
+    /// # use chrono::DateTime;
+    /// # fn main() {
+    /// let now = DateTime::parse_from_rfc3339("2016-11-10T08:42:18+01:00").unwrap();
+    /// assert_eq!(RateLimit::from_headers(Some(5000), Some(4990), Some(1478765338), &now),
+    ///            Some(RateLimit {
+    ///                limit: 5000,
+    ///                remaining: 4990,
+    ///                reset: DateTime::parse_from_rfc3339("2016-11-10T09:08:58+01:00").unwrap(),
+    ///            }));
+    /// assert_eq!(RateLimit::from_headers(Some(5000), None, None, &now), None);
+    /// # }
+    /// ```
+    pub fn from_headers(limit: Option<u64>, remaining: Option<u64>, reset: Option<i64>, now: &DateTime<FixedOffset>) -> Option<RateLimit> {
+        match (limit, remaining, reset) {
+            (Some(limit), Some(remaining), Some(reset)) => {
+                Some(RateLimit {
+                    limit: limit,
+                    remaining: remaining,
+                    reset: DateTime::from_utc(NaiveDateTime::from_timestamp(reset, 0), *now.offset()),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Get the time requests are paused until at the specified time, if they are, which is `reset` if the rate limit is used up.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate chrono;
+    /// # extern crate dishub;
+    /// # use dishub::ops::github::RateLimit;
+    /// # use chrono::{Duration, DateTime};
+    /// # fn main() {
+    /// let now = DateTime::parse_from_rfc3339("2016-11-10T08:42:18+01:00").unwrap();
+    /// let mut rl = RateLimit {
+    ///     limit: 60,
+    ///     remaining: 1,
+    ///     reset: now + Duration::minutes(30),
+    /// };
+    /// assert_eq!(rl.paused_until(&now), None);
+    ///
+    /// rl.remaining = 0;
+    /// assert_eq!(rl.paused_until(&now), Some(now + Duration::minutes(30)));
+    /// assert_eq!(rl.paused_until(&(now + Duration::hours(1))), None);
+    /// # }
+    /// ```
+    pub fn paused_until(&self, now: &DateTime<FixedOffset>) -> Option<DateTime<FixedOffset>> {
+        if self.remaining == 0 && self.reset > *now {
+            Some(self.reset)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for RateLimit {
+    /// Displays the remaining budget, as in `"4990/5000 requests left, resetting at 09:08:58"`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{} requests left, resetting at {}", self.remaining, self.limit, self.reset.format("%H:%M:%S"))
+    }
+}
+
+
+/// Set the GitHub instance feeds are on unless they specify otherwise.
+///
+/// # Examples
+///
+/// ```
+/// # use dishub::ops::github::{self, GitHubUrls};
+/// github::configure(GitHubUrls {
+///     api: "https://ghe.example.com/api/v3".to_string(),
+///     web: "https://ghe.example.com".to_string(),
+/// });
+/// assert_eq!(github::urls().web, "https://ghe.example.com");
+/// ```
+pub fn configure(urls: GitHubUrls) {
+    *URLS.write().unwrap() = urls;
+}
+
+/// Get the GitHub instance feeds are on unless they specify otherwise, github.com by default.
+pub fn urls() -> GitHubUrls {
+    URLS.read().unwrap().clone()
+}
+
+/// Get the rate limit of the GitHub instance with the specified API base URL as of the latest response, if any was received yet.
+///
+/// The rate limit is tracked across all requests to the instance.
+pub fn rate_limit(api_url: &str) -> Option<RateLimit> {
+    RATE_LIMITS.lock().unwrap().get(api_url).cloned()
+}
+
+/// Get the rate limits of all GitHub instances any responses were received from yet, by API base URL.
+pub fn rate_limits() -> BTreeMap<String, RateLimit> {
+    RATE_LIMITS.lock().unwrap().clone()
+}
+
+/// Get the time requests to the GitHub instance with the specified API base URL are paused until, if they are.
+///
+/// That's when the rate limit resets if it's used up (see `rate_limit()`), or when GitHub said to retry after,
+/// which it does for secondary rate limits, whichever's later.
+///
+/// While requests are paused they fail with `Error::GitHubRateLimited` without being sent.
+pub fn paused_until(api_url: &str) -> Option<DateTime<FixedOffset>> {
+    let now = now();
+    let reset = rate_limit(api_url).and_then(|rl| rl.paused_until(&now));
+    let retry_after = RETRY_AFTER.lock().unwrap().get(api_url).and_then(|&ra| if ra > now { Some(ra) } else { None });
+
+    match (reset, retry_after) {
+        (Some(reset), Some(retry_after)) => Some(if reset > retry_after { reset } else { retry_after }),
+        (reset, retry_after) => reset.or(retry_after),
+    }
+}
+
 /// Check whether a user with the specified name exists.
 ///
 /// # Examples
@@ -30,15 +274,17 @@ mod headers {
 /// ```
 /// # use dishub::ops::AppTokens;
 /// # use dishub::ops::github::user_exists;
+/// # use dishub::util::GITHUB_API_URL;
 /// # let tokens = AppTokens {
 /// #     github: "".to_string(),
 /// #     discord: "".to_string(),
+/// #     github_instances: None,
 /// # };
-/// let response = user_exists("nabijaczleweli", &tokens);
+/// let response = user_exists("nabijaczleweli", GITHUB_API_URL, &tokens);
 /// assert_eq!(response, Ok(true));
 /// ```
-pub fn user_exists(uname: &str, tokens: &AppTokens) -> Result<bool, Error> {
-    exists(format!("https://api.github.com/users/{}", uname), tokens, "GitHub user information")
+pub fn user_exists(uname: &str, api_url: &str, tokens: &AppTokens) -> Result<bool, Error> {
+    exists(api_url, format!("{}/users/{}", api_url, uname), tokens, "GitHub user information")
 }
 
 /// Check whether a repository with the specified slug exists.
@@ -48,24 +294,23 @@ pub fn user_exists(uname: &str, tokens: &AppTokens) -> Result<bool, Error> {
 /// ```
 /// # use dishub::ops::AppTokens;
 /// # use dishub::ops::github::repo_exists;
+/// # use dishub::util::GITHUB_API_URL;
 /// # let tokens = AppTokens {
 /// #     github: "".to_string(),
 /// #     discord: "".to_string(),
+/// #     github_instances: None,
 /// # };
-/// let response = repo_exists("nabijaczleweli/dishub", &tokens);
+/// let response = repo_exists("nabijaczleweli/dishub", GITHUB_API_URL, &tokens);
 /// assert_eq!(response, Ok(true));
 /// ```
-pub fn repo_exists(slug: &str, tokens: &AppTokens) -> Result<bool, Error> {
-    exists(format!("https://api.github.com/repos/{}", slug), tokens, "GitHub repository")
+pub fn repo_exists(slug: &str, api_url: &str, tokens: &AppTokens) -> Result<bool, Error> {
+    exists(api_url, format!("{}/repos/{}", api_url, slug), tokens, "GitHub repository")
 }
 
 /// Get the events for a user when you don't have an ETag (which is to say - for the first time).
 ///
-/// The returned tuple contains:
-///
-///   * The raw JSON response,
-///   * The event bundle's ETag,
-///   * The next minimum amount of milliseconds polling the same event queue is permitted.
+/// The returned tuple contains the first page of events
+/// and the next minimum amount of milliseconds polling the same event queue is permitted.
 ///
 /// You should use this only once and use `poll_user_events_update()` afterwards.
 ///
@@ -74,23 +319,22 @@ pub fn repo_exists(slug: &str, tokens: &AppTokens) -> Result<bool, Error> {
 /// ```no_run
 /// # use dishub::ops::AppTokens;
 /// # use dishub::ops::github::poll_user_events_new;
+/// # use dishub::util::GITHUB_API_URL;
 /// # let tokens = AppTokens {
 /// #     github: "".to_string(),
 /// #     discord: "".to_string(),
+/// #     github_instances: None,
 /// # };
-/// let (response, etag, next) = poll_user_events_new("nabijaczleweli", &tokens).unwrap();
+/// let (page, next) = poll_user_events_new("nabijaczleweli", GITHUB_API_URL, &tokens).unwrap();
 /// ```
-pub fn poll_user_events_new(uname: &str, tokens: &AppTokens) -> Result<(String, String, u64), Error> {
-    poll_events_new(format!("https://api.github.com/users/{}/events", uname), tokens, "GitHub user events")
+pub fn poll_user_events_new(uname: &str, api_url: &str, tokens: &AppTokens) -> Result<(EventsPage, u64), Error> {
+    poll_events_new(api_url, format!("{}/users/{}/events", api_url, uname), tokens, "GitHub user events")
 }
 
 /// Get the events for a repository when you don't have an ETag (which is to say - for the first time).
 ///
-/// The returned tuple contains:
-///
-///   * The raw JSON response,
-///   * The event bundle's ETag,
-///   * The next minimum amount of milliseconds polling the same event queue is permitted.
+/// The returned tuple contains the first page of events
+/// and the next minimum amount of milliseconds polling the same event queue is permitted.
 ///
 /// You should use this only once and use `poll_repo_events_update()` afterwards.
 ///
@@ -99,23 +343,22 @@ pub fn poll_user_events_new(uname: &str, tokens: &AppTokens) -> Result<(String,
 /// ```no_run
 /// # use dishub::ops::AppTokens;
 /// # use dishub::ops::github::poll_repo_events_new;
+/// # use dishub::util::GITHUB_API_URL;
 /// # let tokens = AppTokens {
 /// #     github: "".to_string(),
 /// #     discord: "".to_string(),
+/// #     github_instances: None,
 /// # };
-/// let (response, etag, next) = poll_repo_events_new("nabijaczleweli/dishub", &tokens).unwrap();
+/// let (page, next) = poll_repo_events_new("nabijaczleweli/dishub", GITHUB_API_URL, &tokens).unwrap();
 /// ```
-pub fn poll_repo_events_new(slug: &str, tokens: &AppTokens) -> Result<(String, String, u64), Error> {
-    poll_events_new(format!("https://api.github.com/repos/{}/events", slug), tokens, "GitHub repo events")
+pub fn poll_repo_events_new(slug: &str, api_url: &str, tokens: &AppTokens) -> Result<(EventsPage, u64), Error> {
+    poll_events_new(api_url, format!("{}/repos/{}/events", api_url, slug), tokens, "GitHub repo events")
 }
 
 /// Get the events for a user when you already have an ETag (which is to say - after the first time).
 ///
 /// If the event list hasn't changed the first element of the returned tuple will be `None`,
-/// otherwise it's a tuple of:
-///
-///   * The raw JSON response,
-///   * The event bundle's new ETag.
+/// otherwise it's the first page of events, with the new ETag.
 ///
 /// The second element always constains the next minimum amount of milliseconds polling the same event queue is permitted.
 ///
@@ -124,27 +367,26 @@ pub fn poll_repo_events_new(slug: &str, tokens: &AppTokens) -> Result<(String, S
 /// ```no_run
 /// # use dishub::ops::AppTokens;
 /// # use dishub::ops::github::poll_user_events_update;
+/// # use dishub::util::GITHUB_API_URL;
 /// # let tokens = AppTokens {
 /// #     github: "".to_string(),
 /// #     discord: "".to_string(),
+/// #     github_instances: None,
 /// # };
 /// # let prev_etag = "9c1bac04e0735a8cba6a7b277b70c19f";
-/// let (changed, next) = poll_user_events_update("nabijaczleweli", prev_etag, &tokens).unwrap();
-/// if let Some((response, etag)) = changed {
+/// let (changed, next) = poll_user_events_update("nabijaczleweli", GITHUB_API_URL, prev_etag, &tokens).unwrap();
+/// if let Some(page) = changed {
 ///     // The feed changed
 /// }
 /// ```
-pub fn poll_user_events_update(uname: &str, e_tag: &str, tokens: &AppTokens) -> Result<(Option<(String, String)>, u64), Error> {
-    poll_events_update(format!("https://api.github.com/users/{}/events", uname), e_tag, tokens, "GitHub user events")
+pub fn poll_user_events_update(uname: &str, api_url: &str, e_tag: &str, tokens: &AppTokens) -> Result<(Option<EventsPage>, u64), Error> {
+    poll_events_update(api_url, format!("{}/users/{}/events", api_url, uname), e_tag, tokens, "GitHub user events")
 }
 
 /// Get the events for a repository when you already have an ETag (which is to say - after the first time).
 ///
 /// If the event list hasn't changed the first element of the returned tuple will be `None`,
-/// otherwise it's a tuple of:
-///
-///   * The raw JSON response,
-///   * The event bundle's new ETag.
+/// otherwise it's the first page of events, with the new ETag.
 ///
 /// The second element always constains the next minimum amount of milliseconds polling the same event queue is permitted.
 ///
@@ -153,80 +395,244 @@ pub fn poll_user_events_update(uname: &str, e_tag: &str, tokens: &AppTokens) ->
 /// ```no_run
 /// # use dishub::ops::AppTokens;
 /// # use dishub::ops::github::poll_repo_events_update;
+/// # use dishub::util::GITHUB_API_URL;
 /// # let tokens = AppTokens {
 /// #     github: "".to_string(),
 /// #     discord: "".to_string(),
+/// #     github_instances: None,
 /// # };
 /// # let prev_etag = "4797f0ad2ee145181045fe69c61676e6";
-/// let (changed, next) = poll_repo_events_update("nabijaczleweli/dishub", prev_etag, &tokens).unwrap();
-/// if let Some((response, etag)) = changed {
+/// let (changed, next) = poll_repo_events_update("nabijaczleweli/dishub", GITHUB_API_URL, prev_etag, &tokens).unwrap();
+/// if let Some(page) = changed {
 ///     // The feed changed
 /// }
 /// ```
-pub fn poll_repo_events_update(slug: &str, e_tag: &str, tokens: &AppTokens) -> Result<(Option<(String, String)>, u64), Error> {
-    poll_events_update(format!("https://api.github.com/repos/{}/events", slug), e_tag, tokens, "GitHub user events")
-}
-
-fn exists(url: String, tokens: &AppTokens, desc: &'static str) -> Result<bool, Error> {
-    Client::new()
-        .get(&url)
-        .header(Authorization(Bearer { token: tokens.github.clone() }))
-        .header(UserAgent(GITHUB_USER_AGENT.to_string()))
-        .send()
-        .map_err(|_| {
-            Error::Io {
-                desc: desc,
-                op: "get",
+pub fn poll_repo_events_update(slug: &str, api_url: &str, e_tag: &str, tokens: &AppTokens) -> Result<(Option<EventsPage>, u64), Error> {
+    poll_events_update(api_url, format!("{}/repos/{}/events", api_url, slug), e_tag, tokens, "GitHub repo events")
+}
+
+/// Get a further page of events from the GitHub instance with the specified API base URL,
+/// as linked to by the previous page, see `poll_user_events_new()` and friends.
+///
+/// GitHub only keeps the latest `EVENTS_WINDOW` events, so there's no next page past those.
+///
+/// URLs not under `api_url` are rejected with `Error::Io`, so that the token isn't sent to another host.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use dishub::ops::AppTokens;
+/// # use dishub::ops::github::poll_events_next;
+/// # use dishub::util::GITHUB_API_URL;
+/// # let tokens = AppTokens {
+/// #     github: "".to_string(),
+/// #     discord: "".to_string(),
+/// #     github_instances: None,
+/// # };
+/// let page = poll_events_next("https://api.github.com/repositories/71928026/events?page=2", GITHUB_API_URL, &tokens).unwrap();
+/// ```
+pub fn poll_events_next(url: &str, api_url: &str, tokens: &AppTokens) -> Result<EventsPage, Error> {
+    if !on_instance(url, api_url) {
+        return Err(Error::Io {
+            desc: "GitHub events page",
+            op: "poll",
+        });
+    }
+
+    let resp = try!(send(api_url, url, headers(api_url, tokens), "GitHub events page", "poll"));
+    read_page(api_url, resp, url)
+}
+
+/// Get the URL of the next page from the value of a `Link` header, if there is one.
+///
+/// # Examples
+///
+/// ```
+/// # use dishub::ops::github::next_page;
+/// let link = r#"<https://api.github.com/repositories/1/events?page=2>; rel="next", <https://api.github.com/repositories/1/events?page=10>; rel="last""#;
+/// assert_eq!(next_page(link), Some("https://api.github.com/repositories/1/events?page=2".to_string()));
+/// assert_eq!(next_page(r#"<https://api.github.com/repositories/1/events?page=1>; rel="first""#), None);
+/// ```
+pub fn next_page(link: &str) -> Option<String> {
+    link.split(',')
+        .filter_map(|link| {
+            let mut parts = link.split(';').map(str::trim);
+            let url = parts.next().unwrap_or("");
+            if url.starts_with('<') && url.ends_with('>') &&
+               parts.any(|p| p.replace(' ', "") == "rel=\"next\"" || p.replace(' ', "") == "rel=next") {
+                Some(url[1..url.len() - 1].to_string())
+            } else {
+                None
             }
         })
-        .map(|r| r.status != StatusCode::NotFound)
-}
-
-fn poll_events_new(url: String, tokens: &AppTokens, desc: &'static str) -> Result<(String, String, u64), Error> {
-    Client::new()
-        .get(&url)
-        .header(Authorization(Bearer { token: tokens.github.clone() }))
-        .header(UserAgent(GITHUB_USER_AGENT.to_string()))
-        .send()
-        .map_err(|_| {
-            Error::Io {
-                desc: desc,
-                op: "poll",
+        .next()
+}
+
+/// Get the error for a GitHub response with the specified status code and body, if the status is one.
+///
+/// Successful statuses and 304 Not Modified aren't errors,
+/// otherwise the status is mapped to the matching `Error` variant, with the `message` GitHub sent in the body, if any.
+///
+/// 429 Too Many Requests, and 403 Forbidden with a message about a (primary or secondary) rate limit,
+/// are `Error::GitHubRateLimited`, with `until` left for the caller to fill in from the rate limit, see `rate_limit()`.
+///
+/// # Examples
+///
+/// ```
+/// # use dishub::ops::github::response_error;
+/// # use dishub::Error;
+/// assert_eq!(response_error(200, "https://api.github.com/users/nabijaczleweli", "{}"), None);
+/// assert_eq!(response_error(404, "https://api.github.com/users/3f0ada6056fe3fc", r#"{"message": "Not Found"}"#),
+///            Some(Error::GitHubNotFound {
+///                status: 404,
+///                url: "https://api.github.com/users/3f0ada6056fe3fc".to_string(),
+///                message: Some("Not Found".to_string()),
+///            }));
+/// ```
+pub fn response_error(status: u16, url: &str, body: &str) -> Option<Error> {
+    if status / 100 == 2 || status == 304 {
+        return None;
+    }
+
+    let url = url.to_string();
+    let message = json::parse(body).ok().and_then(|b| b["message"].as_str().map(str::to_string));
+    Some(match status {
+        403 | 429 if message.as_ref().map(|m| m.to_lowercase().contains("rate limit")).unwrap_or(status == 429) => {
+            Error::GitHubRateLimited {
+                url: url,
+                message: message,
+                until: None,
             }
-        })
-        .map(|mut r| {
-            let mut buf = String::new();
-            r.read_to_string(&mut buf).unwrap();
+        }
+        401 => Error::GitHubUnauthorised { status: status, url: url, message: message },
+        403 => Error::GitHubForbidden { status: status, url: url, message: message },
+        404 => Error::GitHubNotFound { status: status, url: url, message: message },
+        _ if status / 100 == 5 => Error::GitHubServerError { status: status, url: url, message: message },
+        _ => Error::GitHubUnexpectedStatus { status: status, url: url, message: message },
+    })
+}
 
-            let etag: &ETag = r.headers.get().unwrap();
-            let poll_interval: &XPollInterval = r.headers.get().unwrap();
-            (buf, etag.tag().to_string(), **poll_interval)
-        })
+
+fn exists(api_url: &str, url: String, tokens: &AppTokens, desc: &'static str) -> Result<bool, Error> {
+    let resp = try!(send(api_url, &url, headers(api_url, tokens), desc, "get"));
+
+    if resp.status == StatusCode::NotFound {
+        Ok(false)
+    } else {
+        read_response(api_url, resp, &url).map(|_| true)
+    }
 }
 
-fn poll_events_update(url: String, etag: &str, tokens: &AppTokens, desc: &'static str) -> Result<(Option<(String, String)>, u64), Error> {
-    Client::new()
-        .get(&url)
-        .header(Authorization(Bearer { token: tokens.github.clone() }))
-        .header(UserAgent(GITHUB_USER_AGENT.to_string()))
-        .header(IfNoneMatch::Items(vec![EntityTag::new(false, etag.to_string())]))
-        .send()
-        .map_err(|_| {
-            Error::Io {
-                desc: desc,
-                op: "poll",
-            }
-        })
-        .map(|mut r| {
-            (if r.status == StatusCode::NotModified {
-                None
-            } else {
-                let mut buf = String::new();
-                r.read_to_string(&mut buf).unwrap();
+fn poll_events_new(api_url: &str, url: String, tokens: &AppTokens, desc: &'static str) -> Result<(EventsPage, u64), Error> {
+    let resp = try!(send(api_url, &url, headers(api_url, tokens), desc, "poll"));
 
-                let etag: &ETag = r.headers.get().unwrap();
-                Some((buf, etag.tag().to_string()))
-            },
-             r.headers.get::<XPollInterval>().map(|r| **r).unwrap_or(60))
-        })
+    let poll_interval = response_poll_interval(&resp);
+    read_page(api_url, resp, &url).map(|page| (page, poll_interval))
+}
+
+fn poll_events_update(api_url: &str, url: String, etag: &str, tokens: &AppTokens, desc: &'static str) -> Result<(Option<EventsPage>, u64), Error> {
+    let mut headers = headers(api_url, tokens);
+    headers.set(IfNoneMatch::Items(vec![EntityTag::new(false, etag.to_string())]));
+    let resp = try!(send(api_url, &url, headers, desc, "poll"));
+
+    let poll_interval = response_poll_interval(&resp);
+    if resp.status == StatusCode::NotModified {
+        Ok((None, poll_interval))
+    } else {
+        read_page(api_url, resp, &url).map(|page| (Some(page), poll_interval))
+    }
+}
+
+fn headers(api_url: &str, tokens: &AppTokens) -> Headers {
+    let mut headers = Headers::new();
+    if let Some(token) = tokens.github_token(api_url) {
+        headers.set(Authorization(Bearer { token: token.to_string() }));
+    }
+    headers.set(UserAgent(GITHUB_USER_AGENT.to_string()));
+    headers
+}
+
+/// Send the request, unless requests to the instance are paused by its rate limit, and track the rate limit from the response.
+fn send(api_url: &str, url: &str, headers: Headers, desc: &'static str, op: &'static str) -> Result<Response, Error> {
+    if let Some(until) = paused_until(api_url) {
+        return Err(Error::GitHubRateLimited {
+            url: url.to_string(),
+            message: None,
+            until: Some(until),
+        });
+    }
+
+    let resp = try!(http::get(url, headers, desc, op));
+
+    let now = now();
+    let headers = &resp.headers;
+    if let Some(rate_limit) = RateLimit::from_headers(headers.get::<XRateLimitLimit>().map(|h| **h),
+                                                      headers.get::<XRateLimitRemaining>().map(|h| **h),
+                                                      headers.get::<XRateLimitReset>().map(|h| **h),
+                                                      &now) {
+        RATE_LIMITS.lock().unwrap().insert(api_url.to_string(), rate_limit);
+    }
+    if let Some(retry_after) = headers.get::<RetryAfter>() {
+        RETRY_AFTER.lock().unwrap().insert(api_url.to_string(), now + Duration::seconds(**retry_after as i64));
+    }
+
+    Ok(resp)
+}
+
+/// Get the response's poll interval, defaulting to a minute.
+fn response_poll_interval(resp: &Response) -> u64 {
+    resp.headers.get::<XPollInterval>().map(|pi| **pi).unwrap_or(60)
+}
+
+/// Get the page of events in the response, with its ETag, if any, and the URL of the next page, if any, from its `Link` header,
+/// ignoring one not on the instance.
+fn read_page(api_url: &str, resp: Response, url: &str) -> Result<EventsPage, Error> {
+    let etag = resp.headers.get::<ETag>().map(|etag| etag.tag().to_string());
+    let next_page = resp.headers
+        .get_raw("Link")
+        .into_iter()
+        .flat_map(|links| links)
+        .filter_map(|link| next_page(&String::from_utf8_lossy(link)))
+        .next()
+        .and_then(|next| if on_instance(&next, api_url) { Some(next) } else { None });
+    read_response(api_url, resp, url).map(|body| {
+        EventsPage {
+            body: body,
+            etag: etag,
+            next_page: next_page,
+        }
+    })
+}
+
+/// Get the response's body, or its error, see `response_error()`.
+///
+/// Requests to the instance are paused for a minute after hitting a rate limit GitHub didn't say when resets.
+fn read_response(api_url: &str, resp: Response, url: &str) -> Result<String, Error> {
+    match response_error(resp.status.to_u16(), url, &resp.body) {
+        Some(Error::GitHubRateLimited { url, message, until: None }) => {
+            let until = paused_until(api_url).unwrap_or_else(|| {
+                let until = now() + Duration::seconds(60);
+                RETRY_AFTER.lock().unwrap().insert(api_url.to_string(), until);
+                until
+            });
+
+            Err(Error::GitHubRateLimited {
+                url: url,
+                message: message,
+                until: Some(until),
+            })
+        }
+        Some(err) => Err(err),
+        None => Ok(resp.body),
+    }
+}
+
+/// Check whether the URL is under the instance's API base URL.
+fn on_instance(url: &str, api_url: &str) -> bool {
+    url.starts_with(api_url) && (url.len() == api_url.len() || url[api_url.len()..].starts_with('/') || url[api_url.len()..].starts_with('?'))
+}
+
+fn now() -> DateTime<FixedOffset> {
+    let now = Local::now();
+    now.with_timezone(now.offset())
 }
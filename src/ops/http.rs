@@ -0,0 +1,229 @@
+//! This module contains the HTTP client all requests to GitHub and Discord are made with, and its configuration.
+//!
+//! Each request is bounded by the connect, read, and overall timeouts in the configuration set with `configure()`,
+//! and fails with `Error::Timeout` if any of them is exceeded.
+//!
+//! Servers' certificates are checked against the system's CAs, and the configured CA bundle, if any.
+//!
+//! Requests are made through the configured proxy, if any, unless the host is in the configuration's `no_proxy` list.
+
+
+use openssl::ssl::{SSL_OP_NO_COMPRESSION, SSL_OP_NO_SSLV2, SSL_OP_NO_SSLV3, SslContext, SslMethod};
+use hyper::net::{HttpsConnector, OpensslClient};
+use hyper::error::Error as HyperError;
+use std::sync::mpsc::{RecvTimeoutError, channel};
+use self::super::proxy::{self, Proxy};
+use hyper::status::StatusCode;
+use self::super::super::Error;
+use hyper::header::Headers;
+use hyper::method::Method;
+use std::io::{self, Read};
+use std::net::TcpStream;
+use std::time::Duration;
+use std::path::PathBuf;
+use std::sync::RwLock;
+use hyper::Client;
+use std::thread;
+
+
+lazy_static! {
+    static ref CONFIG: RwLock<HttpConfig> = RwLock::new(HttpConfig::default());
+}
+
+
+/// How to make HTTP requests.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct HttpConfig {
+    /// How long to wait for a connection to be established. Default: 10 seconds
+    pub connect_timeout: Duration,
+    /// How long to wait for each read from, or write to, a connection. Default: 30 seconds
+    pub read_timeout: Duration,
+    /// How long to wait for a whole request, including reading the response. Default: 60 seconds
+    pub timeout: Duration,
+    /// The proxy to make requests through, if any. Default: none
+    pub proxy: Option<Proxy>,
+    /// Hosts to connect to directly, even with a proxy, see `Proxy::bypassed()`. Default: none
+    pub no_proxy: Vec<String>,
+    /// A file with PEM certificates to trust in addition to the system's, if any. Default: none
+    pub ca_bundle: Option<PathBuf>,
+}
+
+impl Default for HttpConfig {
+    fn default() -> HttpConfig {
+        HttpConfig {
+            connect_timeout: Duration::from_secs(10),
+            read_timeout: Duration::from_secs(30),
+            timeout: Duration::from_secs(60),
+            proxy: None,
+            no_proxy: vec![],
+            ca_bundle: None,
+        }
+    }
+}
+
+/// A response to an HTTP request, read in full.
+#[derive(Debug, Clone)]
+pub struct Response {
+    /// The response's status code.
+    pub status: StatusCode,
+    /// The response's headers.
+    pub headers: Headers,
+    /// The response's body.
+    pub body: String,
+}
+
+
+/// Set the configuration to make all subsequent requests with.
+///
+/// # Examples
+///
+/// ```
+/// # use dishub::ops::http::{self, HttpConfig};
+/// # use std::time::Duration;
+/// http::configure(HttpConfig {
+///     timeout: Duration::from_secs(120),
+///     ..HttpConfig::default()
+/// });
+/// assert_eq!(http::config().timeout, Duration::from_secs(120));
+/// ```
+pub fn configure(config: HttpConfig) {
+    *CONFIG.write().unwrap() = config;
+}
+
+/// Get the configuration requests are made with.
+pub fn config() -> HttpConfig {
+    CONFIG.read().unwrap().clone()
+}
+
+/// Make a GET request for the specified URL with the specified headers.
+///
+/// `desc` and `op` describe the request in errors, like `Error::Io`'s.
+pub fn get(url: &str, headers: Headers, desc: &'static str, op: &'static str) -> Result<Response, Error> {
+    request(Method::Get, url, headers, None, desc, op)
+}
+
+/// Make a POST request to the specified URL with the specified headers and body.
+///
+/// `desc` and `op` describe the request in errors, like `Error::Io`'s.
+pub fn post(url: &str, headers: Headers, body: String, desc: &'static str, op: &'static str) -> Result<Response, Error> {
+    request(Method::Post, url, headers, Some(body), desc, op)
+}
+
+/// Get the error for a request to the specified URL that failed without a response.
+///
+/// Exceeded timeouts are `Error::Timeout`, other I/O errors, like resetting the connection or failing to resolve the host,
+/// are `Error::Network`, both transient, the proxy refusing the connection is `Error::ProxyRefused`, and the rest are `Error::Io`.
+pub fn request_error(err: &HyperError, desc: &'static str, op: &'static str, url: &str) -> Error {
+    match *err {
+        HyperError::Io(ref err) if err.kind() == io::ErrorKind::PermissionDenied => {
+            Error::ProxyRefused {
+                desc: desc,
+                url: url.to_string(),
+                reason: err.to_string(),
+            }
+        }
+        HyperError::Io(ref err) if err.kind() == io::ErrorKind::TimedOut || err.kind() == io::ErrorKind::WouldBlock => {
+            Error::Timeout {
+                desc: desc,
+                op: op,
+                url: url.to_string(),
+            }
+        }
+        HyperError::Io(_) => {
+            Error::Network {
+                desc: desc,
+                op: op,
+                url: url.to_string(),
+            }
+        }
+        _ => {
+            Error::Io {
+                desc: desc,
+                op: op,
+            }
+        }
+    }
+}
+
+
+/// The request is made on its own thread, so it can be abandoned once the overall timeout is exceeded,
+/// after which the thread ends at the latest when the read timeout is.
+fn request(method: Method, url: &str, headers: Headers, body: Option<String>, desc: &'static str, op: &'static str) -> Result<Response, Error> {
+    let config = config();
+    let (tx, rx) = channel();
+
+    let thread_url = url.to_string();
+    let thread_config = config.clone();
+    thread::spawn(move || {
+        let client = match client(&thread_config) {
+            Ok(client) => client,
+            Err(err) => {
+                let _ = tx.send(Err(err));
+                return;
+            }
+        };
+        let mut req = client.request(method, &thread_url[..]).headers(headers);
+        if let Some(ref body) = body {
+            req = req.body(&body[..]);
+        }
+
+        let _ = tx.send(req.send().and_then(|mut resp| {
+            let mut body = String::new();
+            try!(resp.read_to_string(&mut body));
+            Ok(Response {
+                status: resp.status,
+                headers: resp.headers.clone(),
+                body: body,
+            })
+        }));
+    });
+
+    match rx.recv_timeout(config.timeout) {
+        Ok(Ok(resp)) => Ok(resp),
+        Ok(Err(err)) => Err(request_error(&err, desc, op, url)),
+        Err(RecvTimeoutError::Timeout) => {
+            Err(Error::Timeout {
+                desc: desc,
+                op: op,
+                url: url.to_string(),
+            })
+        }
+        Err(RecvTimeoutError::Disconnected) => {
+            Err(Error::Io {
+                desc: desc,
+                op: op,
+            })
+        }
+    }
+}
+
+fn client(config: &HttpConfig) -> Result<Client, HyperError> {
+    let connect_config = config.clone();
+    let mut client = Client::with_connector(HttpsConnector::with_connector(try!(tls_client(config)),
+                                                                           move |host: &str, port: u16, _: &str| connect(host, port, &connect_config)));
+    client.set_read_timeout(Some(config.read_timeout));
+    client.set_write_timeout(Some(config.read_timeout));
+    Ok(client)
+}
+
+/// hyper's default TLS client, trusting the CA bundle, if any, as well.
+fn tls_client(config: &HttpConfig) -> Result<OpensslClient, HyperError> {
+    match config.ca_bundle {
+        None => Ok(OpensslClient::default()),
+        Some(ref bundle) => {
+            let mut ctx = try!(SslContext::new(SslMethod::Sslv23));
+            try!(ctx.set_default_verify_paths());
+            try!(ctx.set_CA_file(bundle));
+            ctx.set_options(SSL_OP_NO_SSLV2 | SSL_OP_NO_SSLV3 | SSL_OP_NO_COMPRESSION);
+            try!(ctx.set_cipher_list("ALL!EXPORT!EXPORT40!EXPORT56!aNULL!LOW!RC4@STRENGTH"));
+            Ok(OpensslClient::new(ctx))
+        }
+    }
+}
+
+fn connect(host: &str, port: u16, config: &HttpConfig) -> io::Result<TcpStream> {
+    match config.proxy {
+        Some(ref proxy) if !Proxy::bypassed(&config.no_proxy, host) => proxy.connect(host, port, config.connect_timeout, config.read_timeout),
+        _ => proxy::connect_direct(host, port, config.connect_timeout),
+    }
+}
@@ -66,11 +66,13 @@ pub fn verify(config_dir: &(String, PathBuf), force: bool) -> Result<PathBuf, Er
 ///     AppTokens {
 ///         github: "994c365aec1700f5783bac697e2347ffd2268e1e".to_string(),
 ///         discord: "a8233f2465e4e27b36c3e9a9.5ec9ab.e8fe135112b5f4d678bd1d221f8".to_string(),
+///         github_instances: None,
 ///     });
 /// ```
 pub fn get_data<R: BufRead, W: Write>(input: &mut R, output: &mut W) -> AppTokens {
     AppTokens {
         github: prompt_exact_len(input, output, "GitHub OAuth token", |_| true, 40).unwrap(),
         discord: prompt_exact_len(input, output, "Discord bot token", |_| true, 59).unwrap(),
+        github_instances: None,
     }
 }
@@ -0,0 +1,369 @@
+//! Message catalogues, translating event summaries and the other words renderers add.
+//!
+//! A catalogue is a TOML file with the name of its `plural` rule (see `PluralRule::from_name()`)
+//! and a `messages` table of keys to messages, see `locales/en.toml` for all of them.
+//!
+//! `{placeholders}` in messages are replaced with the event's values, and messages depending on a count
+//! are arrays of the forms for each of the plural rule's categories.
+//!
+//! `"key.action"` (like `"issues.closed"`) overrides `"key"` for that action (or ref type, for create and delete events),
+//! and `"action.X"`, `"ref_type.X"`, and `"state.X"` translate the words inserted for `{action}`, `{ref_type}`, and `{state}`.
+//!
+//! Messages missing from a catalogue are taken from the English one.
+
+
+use std::collections::BTreeMap;
+use toml::{Parser, Value};
+use std::path::Path;
+use std::fs::File;
+use std::io::Read;
+use std::{fmt, ptr};
+
+
+/// The catalogues shipped with dishub, as `(name, source)`.
+pub static BUILTIN: &'static [(&'static str, &'static str)] = &[("en", include_str!("../../locales/en.toml")),
+                                                                  ("pl", include_str!("../../locales/pl.toml"))];
+
+lazy_static! {
+    static ref ENGLISH: Catalogue = Catalogue::parse_against("en", BUILTIN[0].1, None).unwrap();
+}
+
+
+/// How a language picks the form of a word for a count.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum PluralRule {
+    /// One form for 1, another for everything else, as in English, German, or Spanish.
+    English,
+    /// One form for 0 and 1, another for everything else, as in French or Portuguese.
+    French,
+    /// One form for 1, another for 2-4, 22-24, 32-34, &c., and a third for everything else, as in Polish.
+    Polish,
+    /// One form for 1, 21, 31, &c., another for 2-4, 22-24, 32-34, &c., and a third for everything else, as in Russian or Ukrainian.
+    Russian,
+    /// One form for 1, another for 2-4, and a third for everything else, as in Czech or Slovak.
+    Czech,
+    /// The same form for every count, as in Japanese or Chinese.
+    Japanese,
+}
+
+/// A set of translated messages.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Catalogue {
+    /// The catalogue's name, e.g. `"pl"`.
+    pub name: String,
+    /// How the catalogue's messages for counts are chosen.
+    pub plural: PluralRule,
+    /// The catalogue's messages by key, with a form per plural category for counts and just the one otherwise.
+    pub messages: BTreeMap<String, Vec<String>>,
+}
+
+impl PluralRule {
+    /// Get a plural rule by the name used in catalogues, the code of the language it's named after.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dishub::ops::locale::PluralRule;
+    /// assert_eq!(PluralRule::from_name("pl"), Some(PluralRule::Polish));
+    /// assert_eq!(PluralRule::from_name("polish"), None);
+    /// ```
+    pub fn from_name(name: &str) -> Option<PluralRule> {
+        match name {
+            "en" => Some(PluralRule::English),
+            "fr" => Some(PluralRule::French),
+            "pl" => Some(PluralRule::Polish),
+            "ru" => Some(PluralRule::Russian),
+            "cs" => Some(PluralRule::Czech),
+            "ja" => Some(PluralRule::Japanese),
+            _ => None,
+        }
+    }
+
+    /// Get the name of the plural rule, such that `PluralRule::from_name(rule.name()) == Some(rule)`.
+    pub fn name(&self) -> &'static str {
+        match *self {
+            PluralRule::English => "en",
+            PluralRule::French => "fr",
+            PluralRule::Polish => "pl",
+            PluralRule::Russian => "ru",
+            PluralRule::Czech => "cs",
+            PluralRule::Japanese => "ja",
+        }
+    }
+
+    /// Get how many forms messages for counts have under this rule.
+    pub fn forms(&self) -> usize {
+        match *self {
+            PluralRule::English | PluralRule::French => 2,
+            PluralRule::Polish | PluralRule::Russian | PluralRule::Czech => 3,
+            PluralRule::Japanese => 1,
+        }
+    }
+
+    /// Get the index of the form to use for the specified count.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dishub::ops::locale::PluralRule;
+    /// assert_eq!(PluralRule::Polish.form(1), 0);
+    /// assert_eq!(PluralRule::Polish.form(22), 1);
+    /// assert_eq!(PluralRule::Polish.form(12), 2);
+    /// assert_eq!(PluralRule::Polish.form(21), 2);
+    /// ```
+    pub fn form(&self, n: u64) -> usize {
+        let few = n % 10 >= 2 && n % 10 <= 4 && !(n % 100 >= 12 && n % 100 <= 14);
+        match *self {
+            PluralRule::English => if n == 1 { 0 } else { 1 },
+            PluralRule::French => if n <= 1 { 0 } else { 1 },
+            PluralRule::Polish => {
+                if n == 1 {
+                    0
+                } else if few {
+                    1
+                } else {
+                    2
+                }
+            }
+            PluralRule::Russian => {
+                if n % 10 == 1 && n % 100 != 11 {
+                    0
+                } else if few {
+                    1
+                } else {
+                    2
+                }
+            }
+            PluralRule::Czech => {
+                if n == 1 {
+                    0
+                } else if n >= 2 && n <= 4 {
+                    1
+                } else {
+                    2
+                }
+            }
+            PluralRule::Japanese => 0,
+        }
+    }
+}
+
+impl Catalogue {
+    /// Get the English catalogue, which all others fall back to.
+    pub fn english() -> &'static Catalogue {
+        &ENGLISH
+    }
+
+    /// Get the catalogue shipped with dishub with the specified name, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dishub::ops::locale::{Catalogue, PluralRule};
+    /// assert_eq!(Catalogue::builtin("pl").unwrap().plural, PluralRule::Polish);
+    /// assert_eq!(Catalogue::builtin("tlh"), None);
+    /// ```
+    pub fn builtin(name: &str) -> Option<Catalogue> {
+        BUILTIN.iter().find(|&&(n, _)| n == name).map(|&(n, src)| Catalogue::parse(n, src).unwrap())
+    }
+
+    /// Get the catalogue with the specified name, from `locales/<name>.toml` in the specified configuration directory
+    /// if it exists there, or shipped with dishub otherwise.
+    pub fn load(name: &str, config_dir: &Path) -> Result<Catalogue, Vec<String>> {
+        let path = config_dir.join("locales").join(format!("{}.toml", name));
+        if path.is_file() {
+            let mut buf = String::new();
+            try!(File::open(&path)
+                .and_then(|mut f| f.read_to_string(&mut buf))
+                .map_err(|e| vec![format!("Couldn't read locale \"{}\": {}", name, e)]));
+            Catalogue::parse(name, &buf).map_err(|errs| errs.into_iter().map(|e| format!("Locale \"{}\": {}", name, e)).collect())
+        } else {
+            Catalogue::builtin(name).ok_or_else(|| vec![format!("Unknown locale \"{}\"", name)])
+        }
+    }
+
+    /// Parse a catalogue from its TOML source, checking that its messages are ones the English catalogue has,
+    /// with the number of forms the plural rule needs for counts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dishub::ops::locale::Catalogue;
+    /// let catalogue = Catalogue::parse("en-pirate", r#"plural = "en"
+    ///                                                  [messages]
+    ///                                                  watch = "{actor} be starrin' {repo}, arr""#).unwrap();
+    /// assert_eq!(catalogue.get("watch", 1), Some("{actor} be starrin' {repo}, arr"));
+    ///
+    /// assert_eq!(Catalogue::parse("en-pirate", r#"plural = "en"
+    ///                                             [messages]
+    ///                                             plunder = "arr""#),
+    ///            Err(vec!["Unknown message \"plunder\"".to_string()]));
+    /// ```
+    pub fn parse(name: &str, source: &str) -> Result<Catalogue, Vec<String>> {
+        Catalogue::parse_against(name, source, Some(&ENGLISH))
+    }
+
+    /// Parse a catalogue, checking its messages against the specified one's if any,
+    /// otherwise taking all arrays to be messages for counts.
+    fn parse_against(name: &str, source: &str, base: Option<&Catalogue>) -> Result<Catalogue, Vec<String>> {
+        let mut parser = Parser::new(source);
+        let mut table = match parser.parse() {
+            Some(table) => table,
+            None => {
+                return Err(parser.errors
+                    .iter()
+                    .map(|e| {
+                        let (line, col) = parser.to_linecol(e.lo);
+                        format!("error: {}:{}: {}", line, col, e.desc)
+                    })
+                    .collect())
+            }
+        };
+
+        let mut errors = vec![];
+        let plural = match table.get("plural") {
+            Some(&Value::String(ref rule)) => {
+                PluralRule::from_name(rule).unwrap_or_else(|| {
+                    errors.push(format!("Unknown plural rule \"{}\"", rule));
+                    PluralRule::English
+                })
+            }
+            _ => {
+                errors.push("Missing plural rule".to_string());
+                PluralRule::English
+            }
+        };
+
+        let mut messages = BTreeMap::new();
+        match table.remove("messages") {
+            Some(Value::Table(msgs)) => {
+                for (key, msg) in msgs {
+                    let forms = match msg {
+                        Value::String(s) => vec![s],
+                        Value::Array(forms) => forms.into_iter().filter_map(|f| if let Value::String(s) = f { Some(s) } else { None }).collect(),
+                        _ => vec![],
+                    };
+
+                    let counted = match base {
+                        None => forms.len() != 1,
+                        Some(base) => {
+                            match base.base_key(&key).and_then(|k| base.messages.get(k)) {
+                                Some(en) => en.len() != 1,
+                                None if key.starts_with("action.") || key.starts_with("ref_type.") || key.starts_with("state.") => false,
+                                None => {
+                                    errors.push(format!("Unknown message \"{}\"", key));
+                                    continue;
+                                }
+                            }
+                        }
+                    };
+                    if counted && forms.len() != plural.forms() {
+                        errors.push(format!("Message \"{}\" needs {} forms for plural rule \"{}\"", key, plural.forms(), plural.name()));
+                    } else if !counted && forms.len() != 1 {
+                        errors.push(format!("Message \"{}\" isn't a string", key));
+                    } else {
+                        messages.insert(key, forms);
+                    }
+                }
+            }
+            _ => errors.push("Missing messages table".to_string()),
+        }
+
+        if errors.is_empty() {
+            Ok(Catalogue {
+                name: name.to_string(),
+                plural: plural,
+                messages: messages,
+            })
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Get the message with the specified key in the form for the specified count, from the English catalogue if this one lacks it,
+    /// or `None` if neither has it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dishub::ops::locale::Catalogue;
+    /// let polish = Catalogue::builtin("pl").unwrap();
+    /// assert_eq!(polish.get("more_commits", 1), Some("i {count} commit więcej"));
+    /// assert_eq!(polish.get("more_commits", 3), Some("i {count} commity więcej"));
+    /// assert_eq!(polish.get("more_commits", 5), Some("i {count} commitów więcej"));
+    /// ```
+    pub fn get(&self, key: &str, count: u64) -> Option<&str> {
+        match self.messages.get(key) {
+            Some(forms) => Some(&forms[self.plural.form(count).min(forms.len() - 1)]),
+            None if !ptr::eq(self, &*ENGLISH) => ENGLISH.get(key, count),
+            None => None,
+        }
+    }
+
+    /// Translate a word of the specified kind (`"action"`, `"ref_type"`, or `"state"`) inserted into messages, if the catalogue does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dishub::ops::locale::Catalogue;
+    /// assert_eq!(Catalogue::builtin("pl").unwrap().word("action", "closed"), Some("zamknął(-ęła)"));
+    /// assert_eq!(Catalogue::english().word("action", "closed"), None);
+    /// ```
+    pub fn word(&self, kind: &str, word: &str) -> Option<&str> {
+        self.get(&format!("{}.{}", kind, word), 1)
+    }
+
+    /// Format the message with the specified key, or `"key.action"` if there is one, in the form for the specified count,
+    /// replacing the `{placeholders}` with the values given for them.
+    ///
+    /// Unknown keys format to themselves, and unknown placeholders are left as-is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dishub::ops::locale::Catalogue;
+    /// let polish = Catalogue::builtin("pl").unwrap();
+    /// assert_eq!(polish.format("more_commits", None, 2, &[("count", &2)]), "i 2 commity więcej");
+    /// assert_eq!(polish.format("member", Some("removed"), 1, &[("actor", &"A"), ("user", &"B"), ("repo", &"A/c")]),
+    ///            "A usunął(-ęła) B z A/c");
+    /// ```
+    pub fn format(&self, key: &str, action: Option<&str>, count: u64, args: &[(&str, &fmt::Display)]) -> String {
+        let message = action.and_then(|a| self.get(&format!("{}.{}", key, a), count)).or_else(|| self.get(key, count)).unwrap_or(key);
+
+        let mut out = String::with_capacity(message.len());
+        let mut rest = message;
+        while let Some(start) = rest.find('{') {
+            out.push_str(&rest[..start]);
+            rest = &rest[start..];
+
+            let value = rest.find('}').and_then(|end| args.iter().find(|&&(name, _)| name == &rest[1..end]).map(|&(_, val)| (end, val)));
+            match value {
+                Some((end, val)) => {
+                    out.push_str(&val.to_string());
+                    rest = &rest[end + 1..];
+                }
+                None => {
+                    out.push('{');
+                    rest = &rest[1..];
+                }
+            }
+        }
+        out.push_str(rest);
+        out
+    }
+
+    /// Get the key of this catalogue's message the specified key is or overrides, i.e. the part before the action, if any.
+    fn base_key<'k>(&self, key: &'k str) -> Option<&'k str> {
+        if self.messages.contains_key(key) {
+            Some(key)
+        } else {
+            key.find('.').map(|dot| &key[..dot])
+        }
+    }
+}
+
+impl Default for Catalogue {
+    fn default() -> Catalogue {
+        ENGLISH.clone()
+    }
+}
@@ -13,16 +13,24 @@ use std::io::Read;
 mod feed;
 mod event;
 mod app_tokens;
+mod app_config;
 
 pub mod init;
+pub mod http;
+pub mod proxy;
+pub mod retry;
+pub mod locale;
 pub mod github;
+pub mod render;
 pub mod add_feeds;
 pub mod start_daemon;
 pub mod unfollow_feeds;
 
 pub use self::feed::Feed;
 pub use self::app_tokens::AppTokens;
-pub use self::event::{GollumPayload, EventPayload, Commit, Event};
+pub use self::app_config::AppConfig;
+pub use self::event::{IssueCommentAction, PullRequestAction, ContributorKind, GollumPayload, ReleaseAction, IssuesAction, EventPayload, MemberAction,
+                       WatchAction, Contributor, Commit, Event, Actor, Repo, Org};
 
 
 fn verify_file(fname: &str, should_exist: bool, config_dir: &(String, PathBuf), force: bool, producing_subsystem: &'static str) -> Result<PathBuf, Error> {
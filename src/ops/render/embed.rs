@@ -0,0 +1,317 @@
+use self::super::super::{PullRequestAction, IssuesAction, EventPayload, Event};
+use self::super::{EMBED_DESCRIPTION_LIMIT, EMBED_FIELD_VALUE_LIMIT, RenderOptions, Renderer, Message, more_commits, excerpt};
+use chrono::{FixedOffset, DateTime};
+use json::JsonValue;
+
+
+/// Colour of embeds for opened and reopened issues and PRs, GitHub's green.
+pub const COLOUR_OPENED: u32 = 0x2CBE4E;
+/// Colour of embeds for merged PRs, GitHub's purple.
+pub const COLOUR_MERGED: u32 = 0x6F42C1;
+/// Colour of embeds for closed issues and unmerged PRs, GitHub's red.
+pub const COLOUR_CLOSED: u32 = 0xCB2431;
+
+
+/// A [Discord embed](https://discord.com/developers/docs/resources/message#embed-object).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Embed {
+    /// Who the embed is by, displayed above the title.
+    pub author: Option<EmbedAuthor>,
+    /// The embed's title.
+    pub title: String,
+    /// Where the title links to, if anywhere.
+    pub url: Option<String>,
+    /// The embed's body text, can be empty.
+    pub description: String,
+    /// The colour of the embed's left border as `0xRRGGBB`, Discord's default if `None`.
+    pub colour: Option<u32>,
+    /// Name-value pairs displayed below the description.
+    pub fields: Vec<EmbedField>,
+    /// The time displayed in the embed's footer.
+    pub timestamp: DateTime<FixedOffset>,
+}
+
+/// The author of an `Embed`.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct EmbedAuthor {
+    /// The author's name.
+    pub name: String,
+    /// Where the author's name links to, if anywhere.
+    pub url: Option<String>,
+    /// The image displayed next to the author's name, if any.
+    pub icon_url: Option<String>,
+}
+
+/// A field of an `Embed`.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct EmbedField {
+    /// The field's name.
+    pub name: String,
+    /// The field's value.
+    pub value: String,
+    /// Whether the field can be displayed next to other inline fields.
+    pub inline: bool,
+}
+
+/// Renders events as Discord embeds.
+///
+/// The actor is the author, with their avatar, the repository is the title, linking to it on GitHub,
+/// and the event's summary, pushed commits (per `RenderOptions::listed_commits()`), and URLs are the description.
+///
+/// For opened issues and PRs and for published releases their body follows in the description,
+/// or, if `RenderOptions::excerpt` is set, an excerpt of the body of any event with one (see `Event::body()`),
+/// and the description and fields are cut down to Discord's limits if need be.
+///
+/// The labels and branches of issues and PRs are listed in fields, as are the branches of pushes and created or deleted branches.
+///
+/// Issues and PRs are coloured by what happened to them, see `EmbedRenderer::colour()`.
+///
+/// The time is left to Discord to display in each reader's timezone, so `RenderOptions::time` doesn't apply.
+///
+/// The repository name, description, body, and fields are escaped, but embeds never ping anyone, so the author isn't,
+/// and issues, users, and commits referenced in the description are linked, see `linkify()`.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate chrono;
+/// # extern crate dishub;
+/// # use dishub::ops::render::{EmbedRenderer, RenderOptions, EmbedAuthor, Renderer, Message, Embed};
+/// # use dishub::ops::Event;
+/// # use chrono::DateTime;
+/// # fn main() {
+/// let response = r#"[{
+///                     "id": "4831774905",
+///                     "type": "WatchEvent",
+///                     "actor": { "id": 5784597, "login": "carllhw", "avatar_url": "https://avatars.githubusercontent.com/u/5784597?" },
+///                     "repo": { "id": 71928026, "name": "nabijaczleweli/cargo-update" },
+///                     "payload": { "action": "started" },
+///                     "created_at": "2016-11-08T03:10:26Z"
+///                   }]"#;
+/// assert_eq!(EmbedRenderer.render(&Event::parse(response).unwrap()[0], &RenderOptions::default()),
+///            Message::Embed(Embed {
+///                author: Some(EmbedAuthor {
+///                    name: "carllhw".to_string(),
+///                    url: Some("https://github.com/carllhw".to_string()),
+///                    icon_url: Some("https://avatars.githubusercontent.com/u/5784597?".to_string()),
+///                }),
+///                title: "nabijaczleweli/cargo-update".to_string(),
+///                url: Some("https://github.com/nabijaczleweli/cargo-update".to_string()),
+///                description: "carllhw starred nabijaczleweli/cargo-update\n\
+///                              https://github.com/nabijaczleweli/cargo-update/stargazers".to_string(),
+///                colour: None,
+///                fields: vec![],
+///                timestamp: DateTime::parse_from_rfc3339("2016-11-08T03:10:26Z").unwrap(),
+///            }));
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct EmbedRenderer;
+
+impl Embed {
+    /// Get the embed object to send to Discord.
+    ///
+    /// Keys with nothing to put in them are omitted.
+    pub fn to_json(&self) -> JsonValue {
+        let mut embed = object!{
+            "title" => self.title.as_str(),
+            "timestamp" => self.timestamp.to_rfc3339(),
+        };
+        if let Some(ref author) = self.author {
+            embed["author"] = author.to_json();
+        }
+        if let Some(ref url) = self.url {
+            embed["url"] = url.as_str().into();
+        }
+        if !self.description.is_empty() {
+            embed["description"] = self.description.as_str().into();
+        }
+        if let Some(colour) = self.colour {
+            embed["color"] = colour.into();
+        }
+        if !self.fields.is_empty() {
+            embed["fields"] = self.fields.iter().map(EmbedField::to_json).collect::<Vec<_>>().into();
+        }
+        embed
+    }
+}
+
+impl EmbedAuthor {
+    fn to_json(&self) -> JsonValue {
+        let mut author = object!{
+            "name" => self.name.as_str(),
+        };
+        if let Some(ref url) = self.url {
+            author["url"] = url.as_str().into();
+        }
+        if let Some(ref icon_url) = self.icon_url {
+            author["icon_url"] = icon_url.as_str().into();
+        }
+        author
+    }
+}
+
+impl EmbedField {
+    fn new(name: &str, value: String, inline: bool) -> EmbedField {
+        EmbedField {
+            name: name.to_string(),
+            value: excerpt(&value, EMBED_FIELD_VALUE_LIMIT),
+            inline: inline,
+        }
+    }
+
+    fn to_json(&self) -> JsonValue {
+        object!{
+            "name" => self.name.as_str(),
+            "value" => self.value.as_str(),
+            "inline" => self.inline,
+        }
+    }
+}
+
+impl EmbedRenderer {
+    /// Get the colour of the embed for the specified event.
+    ///
+    /// Opened and reopened issues and PRs are `COLOUR_OPENED`, merged PRs are `COLOUR_MERGED`,
+    /// and closed issues and unmerged PRs are `COLOUR_CLOSED`; everything else gets Discord's default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dishub::ops::render::{EmbedRenderer, COLOUR_OPENED};
+    /// # use dishub::ops::Event;
+    /// let response = r#"[{
+    ///                     "id": "4844096927",
+    ///                     "type": "IssuesEvent",
+    ///                     "actor": { "id": 346530, "login": "liigo", "avatar_url": "https://avatars.githubusercontent.com/u/346530?" },
+    ///                     "repo": { "id": 71928026, "name": "nabijaczleweli/cargo-update" },
+    ///                     "payload": {
+    ///                       "action": "opened",
+    ///                       "issue": { "number": 11, "title": "'unknown error occurred': It", "body": "", "labels": [], "assignees": [] }
+    ///                     },
+    ///                     "created_at": "2016-11-10T08:42:18Z"
+    ///                   }]"#;
+    /// assert_eq!(EmbedRenderer::colour(&Event::parse(response).unwrap()[0]), Some(COLOUR_OPENED));
+    /// ```
+    pub fn colour(ev: &Event) -> Option<u32> {
+        match ev.payload {
+            EventPayload::Issues { ref action, .. } => {
+                match *action {
+                    IssuesAction::Opened | IssuesAction::Reopened => Some(COLOUR_OPENED),
+                    IssuesAction::Closed => Some(COLOUR_CLOSED),
+                    _ => None,
+                }
+            }
+            EventPayload::PullRequest { ref action, merged, .. } => {
+                match *action {
+                    PullRequestAction::Opened | PullRequestAction::Reopened => Some(COLOUR_OPENED),
+                    PullRequestAction::Closed if merged => Some(COLOUR_MERGED),
+                    PullRequestAction::Closed => Some(COLOUR_CLOSED),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn body(ev: &Event) -> Option<&str> {
+        match ev.payload {
+            EventPayload::Issues { action: IssuesAction::Opened, ref body, .. } |
+            EventPayload::PullRequest { action: PullRequestAction::Opened, ref body, .. } => Some(body),
+            EventPayload::Release { ref body, .. } => body.as_ref().map(|b| &b[..]),
+            _ => None,
+        }
+    }
+
+    fn fields(ev: &Event, options: &RenderOptions) -> Vec<EmbedField> {
+        let c = &options.catalogue;
+        let mut fields = vec![];
+        match ev.payload {
+            EventPayload::Create { ref ref_type, ref_name: Some(ref ref_name), .. } |
+            EventPayload::Delete { ref ref_type, ref ref_name } if ref_type == "branch" => {
+                fields.push(EmbedField::new(&c.format("field_branch", None, 1, &[]), options.escape(ev, ref_name), true));
+            }
+            EventPayload::Issues { action: IssuesAction::Opened, ref labels, ref assignees, ref milestone, .. } => {
+                EmbedRenderer::triage_fields(ev, options, labels, assignees, milestone, &mut fields);
+            }
+            EventPayload::PullRequest { ref action, merged, ref merged_by, ref head, ref base, ref labels, ref assignees, ref milestone, .. } => {
+                fields.push(EmbedField::new(&c.format("field_branches", None, 1, &[]), options.escape(ev, &format!("{} → {}", head, base)), true));
+                match *action {
+                    PullRequestAction::Opened => EmbedRenderer::triage_fields(ev, options, labels, assignees, milestone, &mut fields),
+                    PullRequestAction::Closed if merged => {
+                        if let Some(ref merged_by) = *merged_by {
+                            fields.push(EmbedField::new(&c.format("field_merged_by", None, 1, &[]), options.escape(ev, merged_by), true));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            EventPayload::Push { ref pushed_ref, .. } => {
+                fields.push(EmbedField::new(&c.format("field_branch", None, 1, &[]), options.escape(ev, pushed_ref.split('/').last().unwrap()), true));
+            }
+            _ => {}
+        }
+        fields
+    }
+
+    /// Add the labels, assignees, and milestone of a newly opened issue or PR, if it has any.
+    fn triage_fields(ev: &Event, options: &RenderOptions, labels: &[String], assignees: &[String], milestone: &Option<String>,
+                     fields: &mut Vec<EmbedField>) {
+        let c = &options.catalogue;
+        if !labels.is_empty() {
+            fields.push(EmbedField::new(&c.format("field_labels", None, 1, &[]), options.escape(ev, &labels.join(", ")), true));
+        }
+        if !assignees.is_empty() {
+            fields.push(EmbedField::new(&c.format("field_assignees", None, 1, &[]), options.escape(ev, &assignees.join(", ")), true));
+        }
+        if let Some(ref milestone) = *milestone {
+            fields.push(EmbedField::new(&c.format("field_milestone", None, 1, &[]), options.escape(ev, milestone), true));
+        }
+    }
+}
+
+impl Renderer for EmbedRenderer {
+    fn render(&self, ev: &Event, options: &RenderOptions) -> Message {
+        let (commits, more) = options.listed_commits(ev);
+        let mut description = options.linkify(ev, &ev.summary_in(&options.catalogue))
+            .lines()
+            .map(str::trim)
+            .map(str::to_string)
+            .chain(commits.into_iter().map(|c| {
+                format!("[`{}`]({}) {} - {}",
+                        c.short_sha(),
+                        c.url_at(&ev.repo, &options.web_url),
+                        options.linkify(ev, c.title()),
+                        options.escape(ev, &c.author_name))
+            }))
+            .chain(if more != 0 { Some(more_commits(more, &options.catalogue)) } else { None })
+            .chain(ev.urls_at(&options.web_url))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let body = match options.excerpt {
+            Some(max) => ev.body().map(|b| excerpt(b, max)),
+            None => EmbedRenderer::body(ev).map(str::trim).and_then(|b| if b.is_empty() { None } else { Some(b.to_string()) }),
+        };
+        if let Some(body) = body {
+            description.push_str("\n\n");
+            description.push_str(&options.linkify(ev, &body));
+        }
+        if description.chars().count() > EMBED_DESCRIPTION_LIMIT {
+            description = excerpt(&description, EMBED_DESCRIPTION_LIMIT);
+        }
+
+        Message::Embed(Embed {
+            author: Some(EmbedAuthor {
+                name: ev.actor.display_login.clone(),
+                url: Some(format!("{}/{}", options.web_url, ev.actor.login)),
+                icon_url: Some(ev.actor.avatar_url.clone()),
+            }),
+            title: options.escape(ev, &ev.repo.name),
+            url: Some(format!("{}/{}", options.web_url, ev.repo)),
+            description: description,
+            colour: EmbedRenderer::colour(ev),
+            fields: EmbedRenderer::fields(ev, options),
+            timestamp: ev.created_at,
+        })
+    }
+}
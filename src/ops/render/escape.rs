@@ -0,0 +1,39 @@
+/// Escape Discord markdown in and neutralise mentions in the specified user-controlled text.
+///
+/// Backslashes, `*`, `_`, `~`, `` ` ``, `|`, `<`, `[`, and `]` are escaped everywhere,
+/// and `>`, `#`, `-`, and `+` at the start of a line (after indentation), where they'd start a quote, heading or list.
+///
+/// A zero-width space is inserted after every `@`, so `@everyone`, `@here`, and role and user mentions don't ping anyone,
+/// the latter also having their `<` escaped.
+///
+/// # Examples
+///
+/// ```
+/// # use dishub::ops::render::escape_markdown;
+/// assert_eq!(escape_markdown("**@everyone** look at <#123>"), "\\*\\*@\u{200B}everyone\\*\\* look at \\<#123>");
+/// assert_eq!(escape_markdown("cargo_update\n> quoted # not a heading"), "cargo\\_update\n\\> quoted # not a heading");
+/// ```
+pub fn escape_markdown(s: &str) -> String {
+    escape_markdown_from(s, true)
+}
+
+/// Like `escape_markdown()`, but for text that starts mid-line if `line_start` is unset.
+pub fn escape_markdown_from(s: &str, mut line_start: bool) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' | '*' | '_' | '~' | '`' | '|' | '<' | '[' | ']' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '>' | '#' | '-' | '+' if line_start => {
+                out.push('\\');
+                out.push(c);
+            }
+            '@' => out.push_str("@\u{200B}"),
+            c => out.push(c),
+        }
+        line_start = c == '\n' || (line_start && (c == ' ' || c == '\t'));
+    }
+    out
+}
@@ -0,0 +1,96 @@
+use self::super::{RenderOptions, Renderer, Message, more_commits, excerpt};
+use self::super::super::Event;
+
+
+/// Renders events as an HTML fragment: a paragraph with the time and summary, followed by a list of links,
+/// and, if `RenderOptions::excerpt` is set, by an excerpt of the event's body in a `<blockquote>` in-between,
+/// as well as by a list of the pushed commits, per `RenderOptions::listed_commits()`.
+///
+/// This isn't useful for posting to Discord, but is for archiving events or passing them on to other tools.
+///
+/// User-controlled text is HTML-escaped instead of markdown-escaped, and, unless the event's repository is trusted,
+/// mentions in it are defused like `escape_markdown()` does.
+///
+/// # Examples
+///
+/// ```
+/// # use dishub::ops::render::{HtmlRenderer, RenderOptions, Renderer, Message};
+/// # use dishub::ops::Event;
+/// let response = r#"[{
+///                     "id": "4831774905",
+///                     "type": "WatchEvent",
+///                     "actor": { "id": 5784597, "login": "carllhw", "avatar_url": "https://avatars.githubusercontent.com/u/5784597?" },
+///                     "repo": { "id": 71928026, "name": "nabijaczleweli/cargo-update" },
+///                     "payload": { "action": "started" },
+///                     "created_at": "2016-11-08T03:10:26Z"
+///                   }]"#;
+/// assert_eq!(HtmlRenderer.render(&Event::parse(response).unwrap()[0], &RenderOptions::default()),
+///            Message::Text("<p><time datetime=\"2016-11-08T03:10:26+00:00\">08.11.2016 03:10:26 AM</time>: \
+///                           carllhw starred nabijaczleweli/cargo-update</p>\n\
+///                           <ul>\n  \
+///                             <li><a href=\"https://github.com/nabijaczleweli/cargo-update/stargazers\">\
+///                                 https://github.com/nabijaczleweli/cargo-update/stargazers</a></li>\n\
+///                           </ul>".to_string()));
+/// ```
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct HtmlRenderer;
+
+impl Renderer for HtmlRenderer {
+    fn render(&self, ev: &Event, options: &RenderOptions) -> Message {
+        let mentions = !options.trusts(&ev.repo.name);
+        let mut html = format!("<p><time datetime=\"{}\">{}</time>: {}</p>",
+                               ev.created_at.to_rfc3339(),
+                               escape(&options.time.format(&ev.created_at), false),
+                               escape(&ev.summary_in(&options.catalogue), mentions).replace('\n', "<br>\n"));
+        if let Some(body) = options.excerpt.and_then(|max| ev.body().map(|b| excerpt(b, max))) {
+            html.push_str(&format!("\n<blockquote>{}</blockquote>", escape(&body, mentions).replace('\n', "<br>\n")));
+        }
+
+        let (commits, more) = options.listed_commits(ev);
+        if !commits.is_empty() {
+            html.push_str("\n<ul class=\"commits\">");
+            for commit in commits {
+                html.push_str(&format!("\n  <li><a href=\"{}\"><code>{}</code></a> {} - {}</li>",
+                                       escape(&commit.url_at(&ev.repo, &options.web_url), false),
+                                       commit.short_sha(),
+                                       escape(commit.title(), mentions),
+                                       escape(&commit.author_name, mentions)));
+            }
+            if more != 0 {
+                html.push_str(&format!("\n  <li>{}</li>", more_commits(more, &options.catalogue)));
+            }
+            html.push_str("\n</ul>");
+        }
+
+        let urls = ev.urls_at(&options.web_url);
+        if !urls.is_empty() {
+            html.push_str("\n<ul>");
+            for url in urls {
+                let url = escape(&url, false);
+                html.push_str(&format!("\n  <li><a href=\"{}\">{}</a></li>", url, url));
+            }
+            html.push_str("\n</ul>");
+        }
+
+        Message::Text(html)
+    }
+}
+
+
+/// Escape the HTML special characters in the specified text, and, if `mentions` is set,
+/// insert a zero-width space after each `@` so that Discord doesn't ping anyone.
+fn escape(s: &str, mentions: bool) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            '@' if mentions => out.push_str("@\u{200B}"),
+            c => out.push(c),
+        }
+    }
+    out
+}
@@ -0,0 +1,158 @@
+/// The most characters Discord accepts in a message.
+pub const MESSAGE_LIMIT: usize = 2000;
+/// The most characters Discord accepts in an embed's description.
+pub const EMBED_DESCRIPTION_LIMIT: usize = 4096;
+/// The most characters Discord accepts in an embed field's value.
+pub const EMBED_FIELD_VALUE_LIMIT: usize = 1024;
+
+
+/// Cut the specified text down to at most `max` characters, ending it with an ellipsis if anything was cut.
+///
+/// The text is cut at whitespace where possible, and never within a character or before a combining one (like a diacritic,
+/// a zero-width joiner, a variation selector or a skin tone modifier), nor in a way that'd leave a dangling `\` escape,
+/// and code blocks cut open are closed.
+///
+/// # Examples
+///
+/// ```
+/// # use dishub::ops::render::excerpt;
+/// assert_eq!(excerpt("Short enough.", 20), "Short enough.");
+/// assert_eq!(excerpt("Zażółć gęślą jaźń, a potem jeszcze trochę.", 24), "Zażółć gęślą jaźń,…");
+/// assert_eq!(excerpt("Run\n```\ncargo install-update -a\ncargo install-update -l\n```", 40),
+///            "Run\n```\ncargo install-update -a…\n```");
+/// ```
+pub fn excerpt(text: &str, max: usize) -> String {
+    let text = text.trim();
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+
+    // Leave room for the ellipsis and a closing fence
+    let budget = if max > 5 { max - 5 } else { 1 };
+    let mut end = cut_point(text, budget);
+    if let Some(ws) = text[..end].rfind(char::is_whitespace) {
+        if text[..ws].chars().count() >= budget / 2 {
+            end = ws;
+        }
+    }
+
+    let mut cut = text[..end].trim_end().to_string();
+    if cut.chars().rev().take_while(|&c| c == '\\').count() % 2 == 1 {
+        cut.pop();
+    }
+    cut.push('…');
+    if cut.matches("```").count() % 2 == 1 {
+        cut.push_str("\n```");
+    }
+    cut
+}
+
+/// Split the specified text into messages of at most `limit` characters.
+///
+/// The text is split between lines where possible, and lines too long to fit are split at spaces, or anywhere if there are none;
+/// code blocks split across messages are closed at the end of one and reopened at the start of the next.
+///
+/// # Examples
+///
+/// ```
+/// # use dishub::ops::render::split_message;
+/// assert_eq!(split_message("Short enough.", 2000), vec!["Short enough.".to_string()]);
+/// assert_eq!(split_message("First line\nSecond line\nThird line", 25),
+///            vec!["First line\nSecond line".to_string(), "Third line".to_string()]);
+/// assert_eq!(split_message("```rust\nlet a = 1;\nlet b = 2;\n```", 25),
+///            vec!["```rust\nlet a = 1;\n```".to_string(), "```rust\nlet b = 2;\n```".to_string()]);
+/// ```
+pub fn split_message(text: &str, limit: usize) -> Vec<String> {
+    if text.chars().count() <= limit {
+        return vec![text.to_string()];
+    }
+
+    let mut messages = vec![];
+    let mut current = String::new();
+    let mut current_len = 0;
+    let mut started = false;
+    let mut fence: Option<&str> = None;
+    for line in text.split('\n') {
+        let is_fence = line.trim_start().starts_with("```");
+        // Room for closing the code block if it's open after this line
+        let closing_len = if fence.is_some() != is_fence { 4 } else { 0 };
+        let piece_limit = match fence {
+            Some(opener) if limit > opener.chars().count() + 6 => limit - opener.chars().count() - 5,
+            Some(_) => 1,
+            None => limit,
+        };
+
+        for piece in pieces(line, piece_limit) {
+            let piece_len = piece.chars().count();
+            if started && current_len + 1 + piece_len + closing_len > limit {
+                if fence.is_some() {
+                    current.push_str("\n```");
+                }
+                if !current.trim().is_empty() {
+                    messages.push(current);
+                }
+
+                current = fence.map(str::to_string).unwrap_or_default();
+                current_len = current.chars().count();
+                started = fence.is_some();
+            }
+
+            if started {
+                current.push('\n');
+                current_len += 1;
+            }
+            current.push_str(piece);
+            current_len += piece_len;
+            started = true;
+        }
+
+        if is_fence {
+            fence = if fence.is_some() { None } else { Some(line) };
+        }
+    }
+    if !current.trim().is_empty() {
+        messages.push(current);
+    }
+    messages
+}
+
+
+/// Split the line into pieces of at most `max` characters, at spaces if possible.
+fn pieces(line: &str, max: usize) -> Vec<&str> {
+    let mut pieces = vec![];
+    let mut rest = line;
+    while rest.chars().count() > max {
+        let hard = cut_point(rest, max);
+        match rest[..hard].rfind(' ') {
+            Some(space) if space != 0 => {
+                pieces.push(&rest[..space]);
+                rest = &rest[space + 1..];
+            }
+            _ => {
+                pieces.push(&rest[..hard]);
+                rest = &rest[hard..];
+            }
+        }
+    }
+    pieces.push(rest);
+    pieces
+}
+
+/// Get the byte index at most `max` characters into the text that doesn't separate a character from the combining ones after it
+/// or split a zero-width joiner sequence, or just after the first character if there's no such index.
+fn cut_point(text: &str, max: usize) -> usize {
+    let mut end = text.char_indices().nth(max).map(|(i, _)| i).unwrap_or(text.len());
+    while end != 0 && (text[end..].chars().next().map(is_combining).unwrap_or(false) || text[..end].ends_with('\u{200D}')) {
+        end = text[..end].char_indices().next_back().map(|(i, _)| i).unwrap_or(0);
+    }
+    if end == 0 {
+        text.char_indices().nth(1).map(|(i, _)| i).unwrap_or(text.len())
+    } else {
+        end
+    }
+}
+
+fn is_combining(c: char) -> bool {
+    (c >= '\u{0300}' && c <= '\u{036F}') || (c >= '\u{20D0}' && c <= '\u{20FF}') || (c >= '\u{FE00}' && c <= '\u{FE0F}') ||
+    (c >= '\u{1F3FB}' && c <= '\u{1F3FF}') || c == '\u{200D}'
+}
@@ -0,0 +1,138 @@
+use self::super::super::super::util::GITHUB_WEB_URL;
+use self::super::escape::escape_markdown_from;
+use self::super::super::Repo;
+use regex::Regex;
+
+
+lazy_static! {
+    static ref REFERENCE_RGX: Regex = Regex::new(concat!(r"(?:^|\.\.\.|[^\w/.@&#`-])(?P<ref>",
+                                                         r"(?P<repo>[A-Za-z0-9][A-Za-z0-9-]*/[A-Za-z0-9._-]+)",
+                                                         r"(?:#(?P<repo_issue>[0-9]+)|@(?P<repo_sha>[0-9a-f]{7,40}))|",
+                                                         r"#(?P<issue>[0-9]+)|",
+                                                         r"@(?P<user>[A-Za-z0-9](?:-?[A-Za-z0-9]){0,38})|",
+                                                         r"(?P<sha>[0-9a-f]{7,40})",
+                                                         r")\b"))
+        .unwrap();
+}
+
+
+/// Turn the references GitHub links in the specified text from the specified repository into markdown links to them,
+/// escaping the rest with `escape_markdown()` if `escape` is set.
+///
+/// These are issues and PRs as `#123` and `owner/repo#45`, users as `@user`, and commits as bare SHAs and `owner/repo@sha`,
+/// where the SHAs are 7 to 40 hexadecimal digits, with at least one letter and one digit, so words and numbers aren't linked.
+///
+/// References inside words, URLs, e-mail addresses and code spans aren't linked (save for both ends of `sha...sha` ranges),
+/// and the links are in angle brackets, so Discord doesn't show previews for them.
+///
+/// # Examples
+///
+/// ```
+/// # use dishub::ops::render::linkify;
+/// # use dishub::ops::Repo;
+/// let repo = Repo {
+///     id: 71928026,
+///     name: "nabijaczleweli/cargo-update".to_string(),
+/// };
+/// assert_eq!(linkify("Fixes #12, see rust-lang/cargo#45", &repo, false),
+///            "Fixes [#12](<https://github.com/nabijaczleweli/cargo-update/issues/12>), \
+///             see [rust-lang/cargo#45](<https://github.com/rust-lang/cargo/issues/45>)");
+/// assert_eq!(linkify("Thanks @liigo, for 8eab689, but not `#12` or a@b.com", &repo, true),
+///            "Thanks [@\u{200B}liigo](<https://github.com/liigo>), for \
+///             [8eab689](<https://github.com/nabijaczleweli/cargo-update/commit/8eab689>), but not \\`#12\\` or a@\u{200B}b.com");
+/// ```
+pub fn linkify(text: &str, repo: &Repo, escape: bool) -> String {
+    linkify_at(text, repo, GITHUB_WEB_URL, escape)
+}
+
+/// Turn the references in the specified text into markdown links to them on the GitHub instance with the specified web interface base URL,
+/// see `linkify()`.
+///
+/// # Examples
+///
+/// ```
+/// # use dishub::ops::render::linkify_at;
+/// # use dishub::ops::Repo;
+/// let repo = Repo {
+///     id: 71928026,
+///     name: "nabijaczleweli/cargo-update".to_string(),
+/// };
+/// assert_eq!(linkify_at("Fixes #12", &repo, "https://github.example.com", false),
+///            "Fixes [#12](<https://github.example.com/nabijaczleweli/cargo-update/issues/12>)");
+/// ```
+pub fn linkify_at(text: &str, repo: &Repo, web_url: &str, escape: bool) -> String {
+    let code = code_spans(text);
+    let mut out = String::with_capacity(text.len());
+    let mut last = 0;
+    for caps in REFERENCE_RGX.captures_iter(text) {
+        let (start, end) = caps.pos(1).unwrap();
+        if code.iter().any(|&(s, e)| start < e && end > s) {
+            continue;
+        }
+
+        let url = if let Some(number) = caps.name("issue") {
+            format!("{}/{}/issues/{}", web_url, repo.name, number)
+        } else if let Some(number) = caps.name("repo_issue") {
+            format!("{}/{}/issues/{}", web_url, caps.name("repo").unwrap(), number)
+        } else if let Some(user) = caps.name("user") {
+            format!("{}/{}", web_url, user)
+        } else if let Some(sha) = caps.name("repo_sha").into_iter().filter(|s| is_sha(s)).next() {
+            format!("{}/{}/commit/{}", web_url, caps.name("repo").unwrap(), sha)
+        } else if let Some(sha) = caps.name("sha").into_iter().filter(|s| is_sha(s)).next() {
+            format!("{}/{}/commit/{}", web_url, repo.name, sha)
+        } else {
+            continue;
+        };
+
+        out.push_str(&escape_segment(text, last, start, escape));
+        out.push('[');
+        if escape {
+            out.push_str(&escape_markdown_from(&text[start..end], false));
+        } else {
+            out.push_str(&text[start..end]);
+        }
+        out.push_str("](<");
+        out.push_str(&url);
+        out.push_str(">)");
+        last = end;
+    }
+    out.push_str(&escape_segment(text, last, text.len(), escape));
+    out
+}
+
+
+/// Escape the specified part of the text if `escape` is set, taking into account whether it starts a line.
+fn escape_segment(text: &str, start: usize, end: usize, escape: bool) -> String {
+    if escape {
+        escape_markdown_from(&text[start..end], text[..start].trim_end_matches(|c| c == ' ' || c == '\t').chars().last().map(|c| c == '\n').unwrap_or(true))
+    } else {
+        text[start..end].to_string()
+    }
+}
+
+/// Get the byte ranges of the code spans and blocks in the text, i.e. between matching runs of backticks.
+fn code_spans(text: &str) -> Vec<(usize, usize)> {
+    let mut spans = vec![];
+    let mut idx = 0;
+    while let Some(start) = text[idx..].find('`').map(|i| idx + i) {
+        let run = text[start..].chars().take_while(|&c| c == '`').count();
+        let fence = &text[start..start + run];
+
+        idx = start + run;
+        let mut search = idx;
+        while let Some(close) = text[search..].find(fence).map(|i| search + i) {
+            let close_run = text[close..].chars().take_while(|&c| c == '`').count();
+            if close_run == run {
+                spans.push((start, close + run));
+                idx = close + run;
+                break;
+            }
+            search = close + close_run;
+        }
+    }
+    spans
+}
+
+fn is_sha(s: &str) -> bool {
+    s.chars().any(|c| c.is_digit(10)) && s.chars().any(|c| c.is_alphabetic())
+}
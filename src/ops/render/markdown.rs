@@ -0,0 +1,69 @@
+use self::super::{RenderOptions, Renderer, Message, more_commits, excerpt};
+use self::super::super::Event;
+
+
+/// Renders events with Discord markdown: the time in italics, the first line of the summary in bold,
+/// and the URLs in angle brackets, so Discord doesn't show previews for them, save for those `RenderOptions::previews` specifies.
+///
+/// If `RenderOptions::excerpt` is set, an excerpt of the event's body follows the summary as a quote,
+/// and the pushed commits are listed with their SHAs linking to them, per `RenderOptions::listed_commits()`.
+///
+/// Issues, users, and commits referenced in the summary, excerpt, and commit titles are linked, see `linkify()`.
+///
+/// # Examples
+///
+/// ```
+/// # use dishub::ops::render::{MarkdownRenderer, RenderOptions, Renderer, Message};
+/// # use dishub::ops::Event;
+/// let response = r#"[{
+///                     "id": "4831774905",
+///                     "type": "WatchEvent",
+///                     "actor": { "id": 5784597, "login": "carllhw", "avatar_url": "https://avatars.githubusercontent.com/u/5784597?" },
+///                     "repo": { "id": 71928026, "name": "nabijaczleweli/cargo-update" },
+///                     "payload": { "action": "started" },
+///                     "created_at": "2016-11-08T03:10:26Z"
+///                   }]"#;
+/// assert_eq!(MarkdownRenderer.render(&Event::parse(response).unwrap()[0], &RenderOptions::default()),
+///            Message::Text("*08.11.2016 03:10:26 AM*\n\
+///                           **carllhw starred nabijaczleweli/cargo-update**\n\
+///                           <https://github.com/nabijaczleweli/cargo-update/stargazers>".to_string()));
+/// ```
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct MarkdownRenderer;
+
+impl Renderer for MarkdownRenderer {
+    fn render(&self, ev: &Event, options: &RenderOptions) -> Message {
+        let summary = options.linkify(ev, &ev.summary_in(&options.catalogue));
+        let mut lines = summary.lines();
+
+        let mut text = format!("*{}*\n**{}**", options.time.format(&ev.created_at), lines.next().unwrap_or(""));
+        for line in lines {
+            text.push('\n');
+            text.push_str(line);
+        }
+        if let Some(body) = options.excerpt.and_then(|max| ev.body().map(|b| excerpt(b, max))) {
+            for line in options.linkify(ev, &body).lines() {
+                text.push_str("\n> ");
+                text.push_str(line);
+            }
+        }
+
+        let (commits, more) = options.listed_commits(ev);
+        for commit in commits {
+            text.push_str(&format!("\n- [`{}`](<{}>) {} - {}",
+                                   commit.short_sha(),
+                                   commit.url_at(&ev.repo, &options.web_url),
+                                   options.linkify(ev, commit.title()),
+                                   options.escape(ev, &commit.author_name)));
+        }
+        if more != 0 {
+            text.push_str(&format!("\n*{}*", more_commits(more, &options.catalogue)));
+        }
+        for url in options.urls(ev) {
+            text.push('\n');
+            text.push_str(&url);
+        }
+
+        Message::Text(text)
+    }
+}
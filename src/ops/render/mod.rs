@@ -0,0 +1,385 @@
+//! Turning events into messages to post.
+//!
+//! A `Renderer` takes an `Event` and produces a `Message`, which is either text or a Discord embed.
+//!
+//! The built-in renderers are chosen per-feed with `Format` (the `format` key in `feeds.toml`),
+//! but anything implementing `Renderer` can be used with `ops::start_daemon::render_messages()`.
+//!
+//! Discord parses markdown and mentions in everything posted,
+//! so the built-in renderers escape user-controlled text with `escape_markdown()`,
+//! unless it's from a repository listed in `RenderOptions::trusted`,
+//! and the markdown and embed renderers link the issues, users, and commits referenced in it with `linkify()`.
+//!
+//! # Examples
+//!
+//! A renderer posting only the actor's name:
+//!
+//! ```
+//! # extern crate dishub;
+//! # extern crate json;
+//! # use dishub::ops::render::{RenderOptions, Renderer, Message};
+//! # use dishub::ops::Event;
+//! struct ActorRenderer;
+//!
+//! impl Renderer for ActorRenderer {
+//!     fn render(&self, ev: &Event, _: &RenderOptions) -> Message {
+//!         Message::Text(ev.actor.login.clone())
+//!     }
+//! }
+//!
+//! # fn main() {
+//! # let response = r#"[{
+//! #                     "id": "4831774905",
+//! #                     "type": "WatchEvent",
+//! #                     "actor": { "id": 5784597, "login": "carllhw", "avatar_url": "https://avatars.githubusercontent.com/u/5784597?" },
+//! #                     "repo": { "id": 71928026, "name": "nabijaczleweli/cargo-update" },
+//! #                     "payload": { "action": "started" },
+//! #                     "created_at": "2016-11-08T03:10:26Z"
+//! #                   }]"#;
+//! # let event = &Event::parse(response).unwrap()[0];
+//! assert_eq!(ActorRenderer.render(event, &RenderOptions::default()), Message::Text("carllhw".to_string()));
+//! # }
+//! ```
+
+
+use self::super::super::util::GITHUB_WEB_URL;
+use self::super::{EventPayload, Commit, Event};
+use self::super::locale::Catalogue;
+use std::collections::BTreeMap;
+
+mod html;
+mod time;
+mod plain;
+mod embed;
+mod link;
+mod limit;
+mod escape;
+mod markdown;
+mod template;
+
+pub use self::embed::{COLOUR_OPENED, COLOUR_MERGED, COLOUR_CLOSED, EmbedRenderer, EmbedAuthor, EmbedField, Embed};
+pub use self::template::{TemplateRenderer, Template};
+pub use self::markdown::MarkdownRenderer;
+pub use self::escape::escape_markdown;
+pub use self::link::{linkify_at, linkify};
+pub use self::limit::{EMBED_DESCRIPTION_LIMIT, EMBED_FIELD_VALUE_LIMIT, MESSAGE_LIMIT, split_message, excerpt};
+pub use self::time::{TimeFormat, TimeStyle, Timezone};
+pub use self::plain::PlainRenderer;
+pub use self::html::HtmlRenderer;
+
+
+/// Something that can turn an event into a message.
+pub trait Renderer {
+    /// Render the specified event into a message to post, respecting the options where applicable.
+    fn render(&self, ev: &Event, options: &RenderOptions) -> Message;
+}
+
+/// Per-feed settings for the renderers.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct RenderOptions {
+    /// How to display event times.
+    pub time: TimeFormat,
+    /// Repositories whose events are posted without escaping, as `"owner/repo"` or `"owner/*"` for all of the owner's repositories.
+    pub trusted: Vec<String>,
+    /// How many characters of event bodies (see `Event::body()`) to include, if any.
+    pub excerpt: Option<usize>,
+    /// How many of a push's commits to list, none if `0`.
+    pub max_commits: usize,
+    /// Whether to only list commits pushed for the first time, and not ones already in another branch.
+    pub distinct_commits_only: bool,
+    /// The messages to describe events with.
+    pub catalogue: Catalogue,
+    /// Which URLs Discord shows previews for, by payload type name (see `EventPayload::type_name()`),
+    /// or `"default"` for types not listed, none if neither is.
+    pub previews: BTreeMap<String, Preview>,
+    /// The base URL of the web interface of the GitHub instance the events are from, to link them on.
+    pub web_url: String,
+}
+
+impl RenderOptions {
+    /// Check whether the specified repository is trusted, ignoring case, like GitHub does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dishub::ops::render::RenderOptions;
+    /// let options = RenderOptions {
+    ///     trusted: vec!["nabijaczleweli/dishub".to_string(), "rust-lang/*".to_string()],
+    ///     ..RenderOptions::default()
+    /// };
+    /// assert!(options.trusts("nabijaczleweli/dishub"));
+    /// assert!(options.trusts("Rust-Lang/cargo"));
+    /// assert!(!options.trusts("nabijaczleweli/cargo-update"));
+    /// ```
+    pub fn trusts(&self, repo: &str) -> bool {
+        self.trusted.iter().any(|t| {
+            if t.ends_with("/*") {
+                let owner = &t.as_bytes()[..t.len() - 1];
+                repo.len() > owner.len() && repo.as_bytes()[..owner.len()].eq_ignore_ascii_case(owner)
+            } else {
+                repo.eq_ignore_ascii_case(t)
+            }
+        })
+    }
+
+    /// Get the commits of the specified push event to list as per `max_commits` and `distinct_commits_only`,
+    /// and how many more there are, ignoring non-distinct ones if `distinct_commits_only` is set.
+    ///
+    /// Other events have no commits to list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dishub::ops::render::RenderOptions;
+    /// # use dishub::ops::Event;
+    /// let response = r#"[{
+    ///                     "id": "4848106862",
+    ///                     "type": "PushEvent",
+    ///                     "actor": { "id": 6709544, "login": "nabijaczleweli", "avatar_url": "https://avatars.githubusercontent.com/u/6709544?" },
+    ///                     "repo": { "id": 73089286, "name": "nabijaczleweli/dishub" },
+    ///                     "payload": {
+    ///                       "ref": "refs/heads/master", "head": "8eab689", "before": "4d28f4b", "size": 3, "distinct_size": 2,
+    ///                       "commits": [
+    ///                         { "sha": "4d28f4b", "author": { "email": "a@example.com", "name": "A" }, "message": "One", "distinct": false },
+    ///                         { "sha": "4665079", "author": { "email": "a@example.com", "name": "A" }, "message": "Two", "distinct": true },
+    ///                         { "sha": "8eab689", "author": { "email": "a@example.com", "name": "A" }, "message": "Three", "distinct": true }
+    ///                       ]
+    ///                     },
+    ///                     "created_at": "2016-11-10T21:40:06Z"
+    ///                   }]"#;
+    /// let event = &Event::parse(response).unwrap()[0];
+    ///
+    /// let (commits, more) = RenderOptions::default().listed_commits(event);
+    /// assert_eq!(commits.iter().map(|c| c.title()).collect::<Vec<_>>(), vec!["One", "Two", "Three"]);
+    /// assert_eq!(more, 0);
+    ///
+    /// let options = RenderOptions {
+    ///     max_commits: 1,
+    ///     distinct_commits_only: true,
+    ///     ..RenderOptions::default()
+    /// };
+    /// let (commits, more) = options.listed_commits(event);
+    /// assert_eq!(commits.iter().map(|c| c.title()).collect::<Vec<_>>(), vec!["Two"]);
+    /// assert_eq!(more, 1);
+    /// ```
+    pub fn listed_commits<'e>(&self, ev: &'e Event) -> (Vec<&'e Commit>, usize) {
+        match ev.payload {
+            EventPayload::Push { ref commits, .. } if self.max_commits != 0 => {
+                let commits: Vec<_> = commits.iter().filter(|c| c.distinct || !self.distinct_commits_only).collect();
+                let more = commits.len().saturating_sub(self.max_commits);
+                (commits.into_iter().take(self.max_commits).collect(), more)
+            }
+            _ => (vec![], 0),
+        }
+    }
+
+    /// Escape the specified user-controlled text from the specified event with `escape_markdown()`,
+    /// unless the event's repository is trusted.
+    pub fn escape(&self, ev: &Event, text: &str) -> String {
+        if self.trusts(&ev.repo.name) {
+            text.to_string()
+        } else {
+            escape_markdown(text)
+        }
+    }
+
+    /// Get the specified event's URLs (see `Event::urls()`) to put in text messages,
+    /// those Discord isn't to show previews for, as per `previews`, in angle brackets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dishub::ops::render::{RenderOptions, Preview};
+    /// # use dishub::ops::Event;
+    /// # use std::collections::BTreeMap;
+    /// let response = r#"[{
+    ///                     "id": "4831774905",
+    ///                     "type": "WatchEvent",
+    ///                     "actor": { "id": 5784597, "login": "carllhw", "avatar_url": "https://avatars.githubusercontent.com/u/5784597?" },
+    ///                     "repo": { "id": 71928026, "name": "nabijaczleweli/cargo-update" },
+    ///                     "payload": { "action": "started" },
+    ///                     "created_at": "2016-11-08T03:10:26Z"
+    ///                   }]"#;
+    /// let event = &Event::parse(response).unwrap()[0];
+    /// assert_eq!(RenderOptions::default().urls(event), vec!["<https://github.com/nabijaczleweli/cargo-update/stargazers>".to_string()]);
+    ///
+    /// let mut previews = BTreeMap::new();
+    /// previews.insert("default".to_string(), Preview::All);
+    /// let options = RenderOptions { previews: previews, ..RenderOptions::default() };
+    /// assert_eq!(options.urls(event), vec!["https://github.com/nabijaczleweli/cargo-update/stargazers".to_string()]);
+    /// ```
+    pub fn urls(&self, ev: &Event) -> Vec<String> {
+        let preview = self.previews.get(ev.payload.type_name()).or_else(|| self.previews.get("default")).cloned().unwrap_or(Preview::None);
+        let urls = ev.urls_at(&self.web_url);
+        let count = urls.len();
+        urls.into_iter()
+            .enumerate()
+            .map(|(i, url)| if preview.previews(i, count) {
+                url
+            } else {
+                format!("<{}>", url)
+            })
+            .collect()
+    }
+
+    /// Link the references in the specified user-controlled text from the specified event with `linkify_at()`,
+    /// escaping the rest unless the event's repository is trusted.
+    pub fn linkify(&self, ev: &Event, text: &str) -> String {
+        linkify_at(text, &ev.repo, &self.web_url, !self.trusts(&ev.repo.name))
+    }
+}
+
+impl Default for RenderOptions {
+    fn default() -> RenderOptions {
+        RenderOptions {
+            time: TimeFormat::default(),
+            trusted: vec![],
+            excerpt: None,
+            max_commits: 5,
+            distinct_commits_only: false,
+            catalogue: Catalogue::default(),
+            previews: BTreeMap::new(),
+            web_url: GITHUB_WEB_URL.to_string(),
+        }
+    }
+}
+
+/// A rendered event, ready to post.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Message {
+    /// A text message.
+    Text(String),
+    /// A Discord embed.
+    Embed(Embed),
+}
+
+/// Describe the commits not listed, per `RenderOptions::listed_commits()`, with the `"more_commits"` message from the catalogue.
+fn more_commits(more: usize, catalogue: &Catalogue) -> String {
+    catalogue.format("more_commits", None, more as u64, &[("count", &more)])
+}
+
+
+/// Which of an event's URLs Discord shows a preview (an "unfurl") for.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum Preview {
+    /// None of them, the default.
+    None,
+    /// All of them.
+    All,
+    /// The one with the specified index, if there's that many.
+    Nth(usize),
+    /// The last one.
+    Last,
+}
+
+impl Preview {
+    /// Get the preview setting's name, as used in `feeds.toml`, with 1-based indices.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dishub::ops::render::Preview;
+    /// assert_eq!(Preview::Nth(0).name(), "first");
+    /// assert_eq!(Preview::Nth(2).name(), "3");
+    /// ```
+    pub fn name(&self) -> String {
+        match *self {
+            Preview::None => "none".to_string(),
+            Preview::All => "all".to_string(),
+            Preview::Nth(0) => "first".to_string(),
+            Preview::Nth(i) => (i + 1).to_string(),
+            Preview::Last => "last".to_string(),
+        }
+    }
+
+    /// Get the preview setting with the specified name, as used in `feeds.toml`, with 1-based indices.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dishub::ops::render::Preview;
+    /// assert_eq!(Preview::from_name("all"), Some(Preview::All));
+    /// assert_eq!(Preview::from_name("2"), Some(Preview::Nth(1)));
+    /// assert_eq!(Preview::from_name("0"), None);
+    /// ```
+    pub fn from_name(name: &str) -> Option<Preview> {
+        match name {
+            "none" => Some(Preview::None),
+            "all" => Some(Preview::All),
+            "first" => Some(Preview::Nth(0)),
+            "last" => Some(Preview::Last),
+            n => n.parse::<usize>().ok().and_then(|n| n.checked_sub(1)).map(Preview::Nth),
+        }
+    }
+
+    /// Check whether to show a preview for the URL with the specified index out of the specified number of them.
+    pub fn previews(&self, idx: usize, count: usize) -> bool {
+        match *self {
+            Preview::None => false,
+            Preview::All => true,
+            Preview::Nth(i) => i == idx,
+            Preview::Last => idx + 1 == count,
+        }
+    }
+}
+
+/// The built-in renderers.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum Format {
+    /// `PlainRenderer`, the default.
+    Plain,
+    /// `MarkdownRenderer`.
+    Markdown,
+    /// `HtmlRenderer`.
+    Html,
+    /// `EmbedRenderer`.
+    Embed,
+}
+
+impl Format {
+    /// Get the format's name, as used in `feeds.toml`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dishub::ops::render::Format;
+    /// assert_eq!(Format::Markdown.name(), "markdown");
+    /// ```
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Format::Plain => "plain",
+            Format::Markdown => "markdown",
+            Format::Html => "html",
+            Format::Embed => "embed",
+        }
+    }
+
+    /// Get the format with the specified name, as used in `feeds.toml`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dishub::ops::render::Format;
+    /// assert_eq!(Format::from_name("embed"), Some(Format::Embed));
+    /// assert_eq!(Format::from_name("rtf"), None);
+    /// ```
+    pub fn from_name(name: &str) -> Option<Format> {
+        match name {
+            "plain" => Some(Format::Plain),
+            "markdown" => Some(Format::Markdown),
+            "html" => Some(Format::Html),
+            "embed" => Some(Format::Embed),
+            _ => None,
+        }
+    }
+
+    /// Get the renderer for this format.
+    pub fn renderer(&self) -> Box<Renderer> {
+        match *self {
+            Format::Plain => Box::new(PlainRenderer),
+            Format::Markdown => Box::new(MarkdownRenderer),
+            Format::Html => Box::new(HtmlRenderer),
+            Format::Embed => Box::new(EmbedRenderer),
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use self::super::{RenderOptions, Renderer, Message, more_commits, excerpt};
+use self::super::super::Event;
+
+
+/// Renders events as their time and summary, like their `Display` output, followed by their URLs, each on its own line
+/// and in angle brackets, so Discord doesn't show previews for them, save for those `RenderOptions::previews` specifies.
+///
+/// If `RenderOptions::excerpt` is set, an excerpt of the event's body follows the summary,
+/// as do the pushed commits, with their URLs, per `RenderOptions::listed_commits()`.
+///
+/// # Examples
+///
+/// ```
+/// # use dishub::ops::render::{PlainRenderer, RenderOptions, Renderer, Message};
+/// # use dishub::ops::Event;
+/// let response = r#"[{
+///                     "id": "4831774905",
+///                     "type": "WatchEvent",
+///                     "actor": { "id": 5784597, "login": "carllhw", "avatar_url": "https://avatars.githubusercontent.com/u/5784597?" },
+///                     "repo": { "id": 71928026, "name": "nabijaczleweli/cargo-update" },
+///                     "payload": { "action": "started" },
+///                     "created_at": "2016-11-08T03:10:26Z"
+///                   }]"#;
+/// assert_eq!(PlainRenderer.render(&Event::parse(response).unwrap()[0], &RenderOptions::default()),
+///            Message::Text("08.11.2016 03:10:26 AM: carllhw starred nabijaczleweli/cargo-update\n\
+///                           <https://github.com/nabijaczleweli/cargo-update/stargazers>".to_string()));
+/// ```
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct PlainRenderer;
+
+impl PlainRenderer {
+    /// Render the event into text.
+    pub fn text(&self, ev: &Event, options: &RenderOptions) -> String {
+        let mut text = format!("{}: {}", options.time.format(&ev.created_at), options.escape(ev, &ev.summary_in(&options.catalogue)));
+        if let Some(body) = options.excerpt.and_then(|max| ev.body().map(|b| excerpt(b, max))) {
+            text.push('\n');
+            text.push_str(&options.escape(ev, &body));
+        }
+
+        let (commits, more) = options.listed_commits(ev);
+        for commit in commits {
+            text.push_str(&format!("\n{} {} - {} <{}>",
+                                   commit.short_sha(),
+                                   options.escape(ev, commit.title()),
+                                   options.escape(ev, &commit.author_name),
+                                   commit.url_at(&ev.repo, &options.web_url)));
+        }
+        if more != 0 {
+            text.push('\n');
+            text.push_str(&more_commits(more, &options.catalogue));
+        }
+        options.urls(ev).into_iter().fold(text, |t, u| t + "\n" + &u)
+    }
+}
+
+impl Renderer for PlainRenderer {
+    fn render(&self, ev: &Event, options: &RenderOptions) -> Message {
+        Message::Text(self.text(ev, options))
+    }
+}
@@ -0,0 +1,474 @@
+use self::super::super::{EventPayload, Event};
+use self::super::{RenderOptions, Renderer, Message, escape_markdown, excerpt};
+use std::collections::BTreeMap;
+use json::JsonValue;
+
+
+/// A user-defined message template.
+///
+/// Templates are text with tags in double braces:
+///
+///   * `{{path}}` is replaced with the value at the dot-separated `path`,
+///   * `{{#if path}}...{{else}}...{{/if}}` includes the first part if the value at `path` is truthy, the `{{else}}` part otherwise,
+///     `{{#if path == "text"}}` and `{{#if path != "text"}}` compare the value at `path` with `text` instead,
+///   * `{{#each path}}...{{/each}}` includes its contents once for each element of the array at `path`.
+///
+/// The values are those of the event's dishub JSON representation (see `Event::to_json()`),
+/// so, for example, `{{actor.login}}`, `{{repo.name}}`, `{{payload.title}}`, `{{#each payload.commits}}` and `{{#each payload.pages}}`,
+/// except that the commits' `message`s are without their `Co-authored-by:` and `Signed-off-by:` trailers (see `Commit::message_without_trailers()`),
+/// plus the `Display` output of the event without the time under `summary`, the time formatted as per the feed's options under `time`,
+/// the UNIX timestamp under `timestamp`, its URLs under `urls`, the first of them under `url`, the branch pushed to under `branch`,
+/// the pushed commits to list (see `RenderOptions::listed_commits()`) under `commits`, each with its `sha`, `short_sha`, `url`,
+/// `title` (the first line of its message), `message`, `author`, and `distinct`, and how many more there are under `more_commits`,
+/// and, if the feed's options call for one, an excerpt of its body under `excerpt`.
+///
+/// Within `{{#each}}` paths are looked up in the current element first (which itself is `this`), then outside of it,
+/// and `@index`, `@first`, and `@last` are the element's index, whether it's the first one, and whether it's the last one.
+///
+/// Strings and numbers are inserted verbatim, except that, when rendering an event from a repository that isn't trusted,
+/// strings are escaped with `escape_markdown()`, other than the URLs under `url` and `urls` and the commits' `url`, and `time`,
+/// including within `{{#each urls}}`;
+/// `null`s are empty, arrays are their elements separated by `", "`,
+/// and objects are their `display_login`, `login`, or `name`, whichever they have, so `{{actor}}` and `{{repo}}` work as expected.
+///
+/// Falsy values are `null`, `false`, `0`, and empty strings, arrays and objects.
+///
+/// # Examples
+///
+/// ```
+/// # use dishub::ops::render::{RenderOptions, Template};
+/// # use dishub::ops::Event;
+/// let response = r#"[{
+///                     "id": "4831774905",
+///                     "type": "WatchEvent",
+///                     "actor": { "id": 5784597, "login": "carllhw", "avatar_url": "https://avatars.githubusercontent.com/u/5784597?" },
+///                     "repo": { "id": 71928026, "name": "nabijaczleweli/cargo-update" },
+///                     "payload": { "action": "started" },
+///                     "created_at": "2016-11-08T03:10:26Z"
+///                   }]"#;
+/// let event = &Event::parse(response).unwrap()[0];
+///
+/// let template = Template::parse("⭐ {{actor}} {{#if payload.action == \"started\"}}starred{{else}}unstarred{{/if}} {{repo}}").unwrap();
+/// assert_eq!(template.render(event, &RenderOptions::default()), "⭐ carllhw starred nabijaczleweli/cargo-update");
+///
+/// assert_eq!(Template::parse("{{#if actor}}").unwrap_err(), "1:1: unclosed {{#if}}");
+/// ```
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Template {
+    source: String,
+    nodes: Vec<Node>,
+}
+
+/// Renders events with a template for their payload type (see `EventPayload::type_name()`) if there is one,
+/// and with the fallback renderer otherwise.
+///
+/// # Examples
+///
+/// ```
+/// # use dishub::ops::render::{TemplateRenderer, RenderOptions, PlainRenderer, Renderer, Template, Message};
+/// # use dishub::ops::Event;
+/// # use std::collections::BTreeMap;
+/// let response = r#"[{
+///                     "id": "4831774905",
+///                     "type": "WatchEvent",
+///                     "actor": { "id": 5784597, "login": "carllhw", "avatar_url": "https://avatars.githubusercontent.com/u/5784597?" },
+///                     "repo": { "id": 71928026, "name": "nabijaczleweli/cargo-update" },
+///                     "payload": { "action": "started" },
+///                     "created_at": "2016-11-08T03:10:26Z"
+///                   }]"#;
+/// let event = &Event::parse(response).unwrap()[0];
+///
+/// let mut templates = BTreeMap::new();
+/// templates.insert("Watch".to_string(), Template::parse("{{repo}} has a new fan: {{actor}}").unwrap());
+/// let renderer = TemplateRenderer {
+///     templates: templates,
+///     fallback: Box::new(PlainRenderer),
+/// };
+/// assert_eq!(renderer.render(event, &RenderOptions::default()), Message::Text("nabijaczleweli/cargo-update has a new fan: carllhw".to_string()));
+///
+/// let renderer = TemplateRenderer {
+///     templates: BTreeMap::new(),
+///     ..renderer
+/// };
+/// assert_eq!(renderer.render(event, &RenderOptions::default()), PlainRenderer.render(event, &RenderOptions::default()));
+/// ```
+pub struct TemplateRenderer {
+    /// Templates to use, keyed by payload type name.
+    pub templates: BTreeMap<String, Template>,
+    /// What to use for events without a template.
+    pub fallback: Box<Renderer>,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+enum Node {
+    Text(String),
+    Value(Vec<String>),
+    If {
+        condition: Condition,
+        then: Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+    Each {
+        path: Vec<String>,
+        body: Vec<Node>,
+    },
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+enum Condition {
+    Truthy(Vec<String>),
+    Equal(Vec<String>, String),
+    NotEqual(Vec<String>, String),
+}
+
+/// Where a `{{#each}}` is at.
+struct Iteration {
+    element: JsonValue,
+    /// Where the element is in the context, with `"*"` for array indices.
+    path: Vec<String>,
+    index: usize,
+    last: bool,
+}
+
+impl Template {
+    /// Parse a template from the specified source.
+    ///
+    /// The error describes what's wrong and where, in the form `"line:column: what"`.
+    pub fn parse(source: &str) -> Result<Template, String> {
+        let mut parser = Parser {
+            source: source,
+            position: 0,
+        };
+
+        let (nodes, end) = try!(parser.parse_nodes());
+        match end {
+            None => {
+                Ok(Template {
+                    source: source.to_string(),
+                    nodes: nodes,
+                })
+            }
+            Some((tag, pos)) => Err(parser.error(pos, &format!("unexpected {{{{{}}}}}", tag))),
+        }
+    }
+
+    /// Get the source the template was parsed from.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Render the template for the specified event.
+    pub fn render(&self, ev: &Event, options: &RenderOptions) -> String {
+        self.render_context(&Template::context(ev, options), !options.trusts(&ev.repo.name))
+    }
+
+    /// Render the template with the specified values instead of an event's, which aren't escaped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use]
+    /// # extern crate json;
+    /// # extern crate dishub;
+    /// # use dishub::ops::render::Template;
+    /// # fn main() {
+    /// let template = Template::parse("{{#each commits}}{{#if @first}}{{else}}, {{/if}}{{this}}{{/each}}").unwrap();
+    /// assert_eq!(template.render_json(&object!{ "commits" => vec!["46650797", "1ce17f66"] }), "46650797, 1ce17f66");
+    /// # }
+    /// ```
+    pub fn render_json(&self, context: &JsonValue) -> String {
+        self.render_context(context, false)
+    }
+
+    fn render_context(&self, context: &JsonValue, escape: bool) -> String {
+        let mut out = String::new();
+        render_nodes(&self.nodes,
+                     &mut vec![Iteration {
+                                   element: context.clone(),
+                                   path: vec![],
+                                   index: 0,
+                                   last: true,
+                               }],
+                     escape,
+                     &mut out);
+        out
+    }
+
+    /// Get the values templates are rendered with for the specified event.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dishub::ops::render::{RenderOptions, Template};
+    /// # use dishub::ops::Event;
+    /// let response = r#"[{
+    ///                     "id": "4831774905",
+    ///                     "type": "WatchEvent",
+    ///                     "actor": { "id": 5784597, "login": "carllhw", "avatar_url": "https://avatars.githubusercontent.com/u/5784597?" },
+    ///                     "repo": { "id": 71928026, "name": "nabijaczleweli/cargo-update" },
+    ///                     "payload": { "action": "started" },
+    ///                     "created_at": "2016-11-08T03:10:26Z"
+    ///                   }]"#;
+    /// let context = Template::context(&Event::parse(response).unwrap()[0], &RenderOptions::default());
+    ///
+    /// assert_eq!(context["actor"]["login"], "carllhw");
+    /// assert_eq!(context["payload"]["type"], "Watch");
+    /// assert_eq!(context["summary"], "carllhw starred nabijaczleweli/cargo-update");
+    /// assert_eq!(context["time"], "08.11.2016 03:10:26 AM");
+    /// assert_eq!(context["timestamp"], 1478574626);
+    /// assert_eq!(context["url"], "https://github.com/nabijaczleweli/cargo-update/stargazers");
+    /// ```
+    pub fn context(ev: &Event, options: &RenderOptions) -> JsonValue {
+        let urls = ev.urls_at(&options.web_url);
+
+        let mut context = ev.to_json();
+        context["summary"] = ev.summary_in(&options.catalogue).into();
+        context["time"] = options.time.format(&ev.created_at).into();
+        context["timestamp"] = ev.created_at.timestamp().into();
+        context["url"] = urls.first().map(String::as_str).into();
+        context["urls"] = urls.into();
+        if let EventPayload::Push { ref pushed_ref, ref commits, .. } = ev.payload {
+            context["branch"] = pushed_ref.split('/').last().unwrap().into();
+            for (i, c) in commits.iter().enumerate() {
+                context["payload"]["commits"][i]["message"] = c.message_without_trailers().into();
+            }
+        }
+        let (commits, more) = options.listed_commits(ev);
+        context["commits"] = commits.into_iter()
+            .map(|c| {
+                object!{
+                    "sha" => c.sha.as_str(),
+                    "short_sha" => c.short_sha(),
+                    "url" => c.url_at(&ev.repo, &options.web_url),
+                    "title" => c.title(),
+                    "message" => c.message_without_trailers(),
+                    "author" => c.author_name.as_str(),
+                    "distinct" => c.distinct,
+                }
+            })
+            .collect::<Vec<_>>()
+            .into();
+        context["more_commits"] = more.into();
+        if let Some(body) = options.excerpt.and_then(|max| ev.body().map(|b| excerpt(b, max))) {
+            context["excerpt"] = body.into();
+        }
+        context
+    }
+}
+
+impl Renderer for TemplateRenderer {
+    fn render(&self, ev: &Event, options: &RenderOptions) -> Message {
+        match self.templates.get(ev.payload.type_name()) {
+            Some(template) => Message::Text(template.render(ev, options)),
+            None => self.fallback.render(ev, options),
+        }
+    }
+}
+
+
+struct Parser<'s> {
+    source: &'s str,
+    position: usize,
+}
+
+impl<'s> Parser<'s> {
+    /// Parse nodes up to the end of the source or a closing tag, which is returned alongside its position.
+    fn parse_nodes(&mut self) -> Result<(Vec<Node>, Option<(&'s str, usize)>), String> {
+        let mut nodes = vec![];
+
+        loop {
+            let rest = &self.source[self.position..];
+            let start = match rest.find("{{") {
+                Some(start) => start,
+                None => {
+                    if !rest.is_empty() {
+                        nodes.push(Node::Text(rest.to_string()));
+                    }
+                    self.position = self.source.len();
+                    return Ok((nodes, None));
+                }
+            };
+            if start != 0 {
+                nodes.push(Node::Text(rest[..start].to_string()));
+            }
+
+            let tag_pos = self.position + start;
+            let tag_len = try!(rest[start + 2..].find("}}").ok_or_else(|| self.error(tag_pos, "unclosed tag")));
+            let tag = rest[start + 2..start + 2 + tag_len].trim();
+            self.position = tag_pos + 2 + tag_len + 2;
+
+            if tag.starts_with("#if ") {
+                let condition = try!(self.parse_condition(tag["#if ".len()..].trim(), tag_pos));
+                let (then, end) = try!(self.parse_nodes());
+                let otherwise = match end {
+                    Some(("/if", _)) => vec![],
+                    Some(("else", _)) => {
+                        match try!(self.parse_nodes()) {
+                            (otherwise, Some(("/if", _))) => otherwise,
+                            (_, Some((tag, pos))) => return Err(self.error(pos, &format!("unexpected {{{{{}}}}}", tag))),
+                            (_, None) => return Err(self.error(tag_pos, "unclosed {{#if}}")),
+                        }
+                    }
+                    Some((tag, pos)) => return Err(self.error(pos, &format!("unexpected {{{{{}}}}}", tag))),
+                    None => return Err(self.error(tag_pos, "unclosed {{#if}}")),
+                };
+
+                nodes.push(Node::If {
+                    condition: condition,
+                    then: then,
+                    otherwise: otherwise,
+                });
+            } else if tag.starts_with("#each ") {
+                let path = try!(self.parse_path(tag["#each ".len()..].trim(), tag_pos));
+                let body = match try!(self.parse_nodes()) {
+                    (body, Some(("/each", _))) => body,
+                    (_, Some((tag, pos))) => return Err(self.error(pos, &format!("unexpected {{{{{}}}}}", tag))),
+                    (_, None) => return Err(self.error(tag_pos, "unclosed {{#each}}")),
+                };
+
+                nodes.push(Node::Each {
+                    path: path,
+                    body: body,
+                });
+            } else if tag == "else" || tag.starts_with('/') {
+                return Ok((nodes, Some((tag, tag_pos))));
+            } else if tag.starts_with('#') {
+                return Err(self.error(tag_pos, &format!("unknown block {{{{{}}}}}", tag)));
+            } else {
+                nodes.push(Node::Value(try!(self.parse_path(tag, tag_pos))));
+            }
+        }
+    }
+
+    fn parse_condition(&self, cond: &str, pos: usize) -> Result<Condition, String> {
+        for &(op, equal) in &[("==", true), ("!=", false)] {
+            if let Some(idx) = cond.find(op) {
+                let path = try!(self.parse_path(cond[..idx].trim(), pos));
+                let literal = cond[idx + op.len()..].trim();
+                if literal.len() < 2 || !literal.starts_with('"') || !literal.ends_with('"') {
+                    return Err(self.error(pos, &format!("expected a quoted string after {}, got {}", op, literal)));
+                }
+                let literal = literal[1..literal.len() - 1].to_string();
+
+                return Ok(if equal {
+                    Condition::Equal(path, literal)
+                } else {
+                    Condition::NotEqual(path, literal)
+                });
+            }
+        }
+
+        Ok(Condition::Truthy(try!(self.parse_path(cond, pos))))
+    }
+
+    fn parse_path(&self, path: &str, pos: usize) -> Result<Vec<String>, String> {
+        if path.is_empty() {
+            return Err(self.error(pos, "empty tag"));
+        }
+
+        let segments: Vec<_> = path.split('.').map(str::to_string).collect();
+        if let Some(seg) = segments.iter().find(|s| s.is_empty() || s.contains(char::is_whitespace)) {
+            Err(self.error(pos, &format!("invalid name \"{}\" in {}", seg, path)))
+        } else {
+            Ok(segments)
+        }
+    }
+
+    fn error(&self, pos: usize, what: &str) -> String {
+        let before = &self.source[..pos];
+        let line = before.matches('\n').count() + 1;
+        let column = before.rfind('\n').map(|nl| &before[nl + 1..]).unwrap_or(before).chars().count() + 1;
+        format!("{}:{}: {}", line, column, what)
+    }
+}
+
+
+fn render_nodes(nodes: &[Node], scopes: &mut Vec<Iteration>, escape: bool, out: &mut String) {
+    for node in nodes {
+        match *node {
+            Node::Text(ref text) => out.push_str(text),
+            Node::Value(ref path) => {
+                let (val, val_path) = lookup(path, scopes);
+                out.push_str(&stringify(&val, escape && !raw(&val_path)))
+            }
+            Node::If { ref condition, ref then, ref otherwise } => {
+                let holds = match *condition {
+                    Condition::Truthy(ref path) => truthy(&lookup(path, scopes).0),
+                    Condition::Equal(ref path, ref literal) => stringify(&lookup(path, scopes).0, false) == *literal,
+                    Condition::NotEqual(ref path, ref literal) => stringify(&lookup(path, scopes).0, false) != *literal,
+                };
+                render_nodes(if holds { then } else { otherwise }, scopes, escape, out);
+            }
+            Node::Each { ref path, ref body } => {
+                let (array, mut element_path) = lookup(path, scopes);
+                element_path.push("*".to_string());
+                let len = array.len();
+                for (index, element) in array.members().enumerate() {
+                    scopes.push(Iteration {
+                        element: element.clone(),
+                        path: element_path.clone(),
+                        index: index,
+                        last: index + 1 == len,
+                    });
+                    render_nodes(body, scopes, escape, out);
+                    scopes.pop();
+                }
+            }
+        }
+    }
+}
+
+/// Look the path up in the innermost scope that has its first segment,
+/// returning the value and where it is in the context, see `Iteration::path`.
+fn lookup(path: &[String], scopes: &[Iteration]) -> (JsonValue, Vec<String>) {
+    let innermost = &scopes[scopes.len() - 1];
+    let (first, scope) = match &path[0][..] {
+        "this" => (innermost.element.clone(), innermost),
+        "@index" => return (innermost.index.into(), path.to_vec()),
+        "@first" => return ((innermost.index == 0).into(), path.to_vec()),
+        "@last" => return (innermost.last.into(), path.to_vec()),
+        name => {
+            match scopes.iter().rev().find(|s| !s.element[name].is_null()) {
+                Some(scope) => (scope.element[name].clone(), scope),
+                None => return (JsonValue::Null, path.to_vec()),
+            }
+        }
+    };
+
+    let start = if path[0] == "this" { 1 } else { 0 };
+    let mut val_path = scope.path.clone();
+    val_path.extend(path[start..].iter().cloned());
+    (path[1..].iter().fold(first, |val, seg| val[&seg[..]].clone()), val_path)
+}
+
+/// Check whether the value at the specified path in the context is one of those `Template::context()` builds that are
+/// inserted unescaped, i.e. `time`, `url`, `urls` or any of them, or any of the `commits`' `url`.
+fn raw(path: &[String]) -> bool {
+    static RAW: &'static [&'static [&'static str]] = &[&["time"], &["url"], &["urls"], &["urls", "*"], &["commits", "*", "url"]];
+    RAW.iter().any(|raw| raw.len() == path.len() && raw.iter().zip(path).all(|(r, p)| **r == p[..]))
+}
+
+/// Stringify the value, escaping strings if `escape` is set.
+fn stringify(val: &JsonValue, escape: bool) -> String {
+    match *val {
+        JsonValue::Null => String::new(),
+        JsonValue::Short(_) |
+        JsonValue::String(_) => {
+            let s = val.as_str().unwrap();
+            if escape {
+                escape_markdown(s)
+            } else {
+                s.to_string()
+            }
+        }
+        JsonValue::Array(ref vals) => vals.iter().map(|v| stringify(v, escape)).collect::<Vec<_>>().join(", "),
+        JsonValue::Object(_) => {
+            ["display_login", "login", "name"].iter().map(|k| &val[*k]).find(|v| !v.is_null()).map(|v| stringify(v, escape)).unwrap_or_default()
+        }
+        ref other => other.dump(),
+    }
+}
+
+fn truthy(val: &JsonValue) -> bool {
+    !val.is_empty()
+}
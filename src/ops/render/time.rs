@@ -0,0 +1,233 @@
+use chrono::{FixedOffset, DateTime, Offset, Local};
+use chrono::format::{StrftimeItems, Item};
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+
+/// How to display event times.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate chrono;
+/// # extern crate dishub;
+/// # use dishub::ops::render::{TimeFormat, TimeStyle, Timezone};
+/// # use chrono::DateTime;
+/// # fn main() {
+/// let created_at = DateTime::parse_from_rfc3339("2016-11-08T03:10:26Z").unwrap();
+/// assert_eq!(TimeFormat::default().format(&created_at), "08.11.2016 03:10:26 AM");
+///
+/// let time = TimeFormat {
+///     timezone: Timezone::from_name("+01:00").unwrap(),
+///     style: TimeStyle::from_name("%Y-%m-%d %H:%M").unwrap(),
+/// };
+/// assert_eq!(time.format(&created_at), "2016-11-08 04:10");
+///
+/// let time = TimeFormat {
+///     timezone: Timezone::Original,
+///     style: TimeStyle::Discord('R'),
+/// };
+/// assert_eq!(time.format(&created_at), "<t:1478574626:R>");
+/// # }
+/// ```
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct TimeFormat {
+    /// The timezone to display times in.
+    ///
+    /// Corresponds to `timezone` in `feeds.toml`.
+    pub timezone: Timezone,
+    /// How to format times.
+    ///
+    /// Corresponds to `time_format` in `feeds.toml`.
+    pub style: TimeStyle,
+}
+
+/// The timezone to display times in.
+///
+/// Only fixed offsets are supported, so they need to be updated for daylight saving time,
+/// use `TimeStyle::Discord` to display times in each reader's own timezone instead.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Timezone {
+    /// The one the time was received in, UTC for GitHub, the default.
+    Original,
+    /// The one the daemon is running in.
+    Local,
+    /// A fixed offset from UTC.
+    Fixed(FixedOffset),
+}
+
+/// How to format times.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub enum TimeStyle {
+    /// With a [`strftime`](https://docs.rs/chrono/0.2/chrono/format/strftime/index.html) format string,
+    /// `"%d.%m.%Y %r"` by default.
+    Strftime(String),
+    /// As a [Discord timestamp](https://discord.com/developers/docs/reference#message-formatting-timestamp-styles)
+    /// with the specified style, e.g. `'R'` for `<t:UNIX:R>` (relative) or `'f'` for `<t:UNIX:f>` (date and time),
+    /// which Discord displays in each reader's timezone and locale.
+    ///
+    /// The timezone is ignored.
+    Discord(char),
+}
+
+impl TimeFormat {
+    /// Format the specified time.
+    pub fn format(&self, dt: &DateTime<FixedOffset>) -> String {
+        match self.style {
+            TimeStyle::Strftime(ref fmt) => self.timezone.convert(dt).format(fmt).to_string(),
+            TimeStyle::Discord(style) => format!("<t:{}:{}>", dt.timestamp(), style),
+        }
+    }
+}
+
+impl Default for TimeFormat {
+    fn default() -> TimeFormat {
+        TimeFormat {
+            timezone: Timezone::Original,
+            style: TimeStyle::Strftime("%d.%m.%Y %r".to_string()),
+        }
+    }
+}
+
+impl Timezone {
+    /// Get the timezone's name, as used in `feeds.toml`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate chrono;
+    /// # extern crate dishub;
+    /// # use dishub::ops::render::Timezone;
+    /// # use chrono::FixedOffset;
+    /// # fn main() {
+    /// assert_eq!(Timezone::Local.name(), "local");
+    /// assert_eq!(Timezone::Fixed(FixedOffset::west(5 * 60 * 60)).name(), "-05:00");
+    /// # }
+    /// ```
+    pub fn name(&self) -> String {
+        match *self {
+            Timezone::Original => "original".to_string(),
+            Timezone::Local => "local".to_string(),
+            Timezone::Fixed(offset) => offset.to_string(),
+        }
+    }
+
+    /// Get the timezone with the specified name, as used in `feeds.toml`.
+    ///
+    /// That's `"original"`, `"local"`, `"UTC"`, or an offset from UTC in the form `"+HH:MM"`, `"-HH:MM"`, `"+HH"`, or `"-HH"`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate chrono;
+    /// # extern crate dishub;
+    /// # use dishub::ops::render::Timezone;
+    /// # use chrono::FixedOffset;
+    /// # fn main() {
+    /// assert_eq!(Timezone::from_name("UTC"), Some(Timezone::Fixed(FixedOffset::east(0))));
+    /// assert_eq!(Timezone::from_name("+05:30"), Some(Timezone::Fixed(FixedOffset::east(5 * 60 * 60 + 30 * 60))));
+    /// assert_eq!(Timezone::from_name("-08"), Some(Timezone::Fixed(FixedOffset::west(8 * 60 * 60))));
+    /// assert_eq!(Timezone::from_name("Europe/Warsaw"), None);
+    /// # }
+    /// ```
+    pub fn from_name(name: &str) -> Option<Timezone> {
+        match name {
+            "original" => Some(Timezone::Original),
+            "local" => Some(Timezone::Local),
+            "UTC" => Some(Timezone::Fixed(FixedOffset::east(0))),
+            _ => {
+                let sign = match name.chars().next() {
+                    Some('+') => 1,
+                    Some('-') => -1,
+                    _ => return None,
+                };
+
+                let mut parts = name[1..].splitn(2, ':');
+                let hours = parts.next().and_then(|h| if h.len() == 2 { i32::from_str(h).ok() } else { None });
+                let minutes = match parts.next() {
+                    Some(m) if m.len() == 2 => i32::from_str(m).ok(),
+                    Some(_) => None,
+                    None => Some(0),
+                };
+
+                match (hours, minutes) {
+                    (Some(h), Some(m)) if h < 24 && m < 60 => Some(Timezone::Fixed(FixedOffset::east(sign * (h * 60 * 60 + m * 60)))),
+                    _ => None,
+                }
+            }
+        }
+    }
+
+    /// Convert the specified time to this timezone.
+    pub fn convert(&self, dt: &DateTime<FixedOffset>) -> DateTime<FixedOffset> {
+        match *self {
+            Timezone::Original => *dt,
+            Timezone::Local => {
+                let local = dt.with_timezone(&Local);
+                local.with_timezone(local.offset())
+            }
+            Timezone::Fixed(ref offset) => dt.with_timezone(offset),
+        }
+    }
+}
+
+impl Hash for Timezone {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match *self {
+            Timezone::Original => 0u8.hash(state),
+            Timezone::Local => 1u8.hash(state),
+            Timezone::Fixed(ref offset) => {
+                2u8.hash(state);
+                offset.local_minus_utc().num_seconds().hash(state);
+            }
+        }
+    }
+}
+
+impl TimeStyle {
+    /// Get the style's name, as used in `feeds.toml`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dishub::ops::render::TimeStyle;
+    /// assert_eq!(TimeStyle::Discord('f').name(), "discord:f");
+    /// assert_eq!(TimeStyle::Strftime("%H:%M".to_string()).name(), "%H:%M");
+    /// ```
+    pub fn name(&self) -> String {
+        match *self {
+            TimeStyle::Strftime(ref fmt) => fmt.clone(),
+            TimeStyle::Discord(style) => format!("discord:{}", style),
+        }
+    }
+
+    /// Get the style with the specified name, as used in `feeds.toml`.
+    ///
+    /// That's `"discord:X"` for `Discord('X')`, where `X` is one of Discord's timestamp styles, `t`, `T`, `d`, `D`, `f`, `F`, or `R`,
+    /// or a valid `strftime` format string for `Strftime`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dishub::ops::render::TimeStyle;
+    /// assert_eq!(TimeStyle::from_name("discord:R"), Some(TimeStyle::Discord('R')));
+    /// assert_eq!(TimeStyle::from_name("discord:X"), None);
+    /// assert_eq!(TimeStyle::from_name("%d %B %Y"), Some(TimeStyle::Strftime("%d %B %Y".to_string())));
+    /// assert_eq!(TimeStyle::from_name("%d %Q %Y"), None);
+    /// ```
+    pub fn from_name(name: &str) -> Option<TimeStyle> {
+        if name.starts_with("discord:") {
+            match &name["discord:".len()..] {
+                s @ "t" | s @ "T" | s @ "d" | s @ "D" | s @ "f" | s @ "F" | s @ "R" => Some(TimeStyle::Discord(s.chars().next().unwrap())),
+                _ => None,
+            }
+        } else if StrftimeItems::new(name).any(|i| match i {
+            Item::Error => true,
+            _ => false,
+        }) {
+            None
+        } else {
+            Some(TimeStyle::Strftime(name.to_string()))
+        }
+    }
+}
@@ -0,0 +1,129 @@
+//! This module contains the retrying of requests to GitHub and Discord failing transiently, see `Error::is_transient()`.
+//!
+//! Failed requests are retried after exponentially longer delays, with jitter, so as not to retry in lockstep.
+
+
+use std::time::{SystemTime, Duration, UNIX_EPOCH};
+use self::super::super::Error;
+use std::io::Write;
+use std::thread;
+
+
+/// How to retry requests failing transiently.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// How many times to retry a failed request. Default: 5
+    pub retries: u32,
+    /// How long to wait before the first retry, doubled for each further one. Default: 1 second
+    pub initial_delay: Duration,
+    /// The longest to wait before a retry. Default: 5 minutes
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> RetryPolicy {
+        RetryPolicy {
+            retries: 5,
+            initial_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(5 * 60),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Get how long to wait before the specified (0-based) retry, without jitter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dishub::ops::retry::RetryPolicy;
+    /// # use std::time::Duration;
+    /// let policy = RetryPolicy::default();
+    /// assert_eq!(policy.delay(0), Duration::from_secs(1));
+    /// assert_eq!(policy.delay(3), Duration::from_secs(8));
+    /// assert_eq!(policy.delay(20), Duration::from_secs(5 * 60));
+    /// ```
+    pub fn delay(&self, retry: u32) -> Duration {
+        let delay = if retry < 32 {
+            self.initial_delay.checked_mul(1 << retry)
+        } else {
+            None
+        };
+
+        match delay {
+            Some(delay) if delay < self.max_delay => delay,
+            _ => self.max_delay,
+        }
+    }
+
+    /// Get how long to wait before the specified (0-based) retry, with the specified jitter in `[0, 1)`,
+    /// which is between half and all of `delay()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dishub::ops::retry::RetryPolicy;
+    /// # use std::time::Duration;
+    /// let policy = RetryPolicy::default();
+    /// assert_eq!(policy.jittered_delay(2, 0.0), Duration::from_secs(2));
+    /// assert_eq!(policy.jittered_delay(2, 0.5), Duration::from_secs(3));
+    /// ```
+    pub fn jittered_delay(&self, retry: u32, jitter: f64) -> Duration {
+        let delay = self.delay(retry);
+        let nanos = (delay.as_secs() as f64 * 1e9 + delay.subsec_nanos() as f64) * (0.5 + jitter / 2.0);
+        Duration::new((nanos / 1e9) as u64, (nanos % 1e9) as u32)
+    }
+
+    /// Run the specified operation, retrying it while it fails transiently (see `Error::is_transient()`),
+    /// up to `retries` times, writing each retry to the specified output.
+    ///
+    /// Permanent errors, and the last transient one, are returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dishub::ops::retry::RetryPolicy;
+    /// # use std::time::Duration;
+    /// # use dishub::Error;
+    /// let policy = RetryPolicy {
+    ///     retries: 2,
+    ///     initial_delay: Duration::from_millis(1),
+    ///     ..RetryPolicy::default()
+    /// };
+    ///
+    /// let mut out = Vec::new();
+    /// let mut attempts = 0;
+    /// assert_eq!(policy.run(&mut out, || {
+    ///                attempts += 1;
+    ///                if attempts == 1 {
+    ///                    Err(Error::DiscordServerError { desc: "event message", status: 502 })
+    ///                } else {
+    ///                    Ok(attempts)
+    ///                }
+    ///            }),
+    ///            Ok(2));
+    /// assert!(String::from_utf8(out).unwrap().starts_with("Retry 1/2 in 0."));
+    /// ```
+    pub fn run<T, W: Write, F: FnMut() -> Result<T, Error>>(&self, log: &mut W, mut op: F) -> Result<T, Error> {
+        let mut retry = 0;
+        loop {
+            match op() {
+                Err(ref err) if err.is_transient() && retry < self.retries => {
+                    let delay = self.jittered_delay(retry, jitter());
+                    retry += 1;
+
+                    write!(log, "Retry {}/{} in {}.{:03}s: ", retry, self.retries, delay.as_secs(), delay.subsec_nanos() / 1000000).unwrap();
+                    err.print_error(log);
+                    thread::sleep(delay);
+                }
+                res => return res,
+            }
+        }
+    }
+}
+
+
+/// Not random, but the sub-second part of the current time is different enough between retries.
+fn jitter() -> f64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.subsec_nanos() as f64 / 1e9).unwrap_or(0.5)
+}
@@ -14,20 +14,25 @@
 //! ops::Feed::read()
 //! |> ops::start_daemon::feeds_filter()
 //! |> ops::Feed::poll()
-//! |> ops::start_daemon::post_text()
+//! |> ops::start_daemon::render_messages()
 //! |> ops::start_daemon::send_messages()
 //! |> ops::Feed::write()
+//! |> ops::start_daemon::sleep_time()
 //! ```
 
 
-use self::super::{AppTokens, Event, Feed, verify_file};
-use discord::{Discord, Error as DisErr};
-use discord::model::ChannelId;
+use self::super::render::{MESSAGE_LIMIT, RenderOptions, PlainRenderer, Renderer, Message, escape_markdown, split_message};
+use hyper::header::{Authorization, ContentType, Headers};
+use self::super::{AppTokens, Event, Feed, verify_file, github, http};
+use self::super::retry::RetryPolicy;
+use hyper::status::StatusCode;
 use self::super::super::Error;
-use chrono::{Duration, Local};
+use chrono::Local;
+use std::time::Duration as StdDuration;
 use std::path::PathBuf;
 use std::io::Write;
 use std::thread;
+use json;
 
 
 /// Verify if, given the current configuration, it's permitted to continue with the subsequent steps of the `start_daemon`
@@ -102,7 +107,10 @@ pub fn verify(config_dir: &(String, PathBuf)) -> Result<(PathBuf, PathBuf), Erro
 /// # extern crate chrono;
 /// # extern crate dishub;
 /// # use chrono::{Duration, Local};
+/// # use dishub::ops::render::{TimeFormat, Format};
+/// # use dishub::ops::locale::Catalogue;
 /// # use dishub::ops::{start_daemon, Feed};
+/// # use std::collections::BTreeMap;
 /// # fn main() {
 /// let mut out = Vec::new();
 /// let now = Local::now();
@@ -117,6 +125,18 @@ pub fn verify(config_dir: &(String, PathBuf)) -> Result<(PathBuf, PathBuf), Erro
 ///                      latest: Some(now),
 ///                      next_min: Some(now + Duration::minutes(1)),
 ///                      latest_event: Some(4831774905),
+///                      format: Format::Plain,
+///                      templates: BTreeMap::new(),
+///                      time: TimeFormat::default(),
+///                      trusted: vec![],
+///                      excerpt: None,
+///                      max_commits: 5,
+///                      distinct_commits_only: false,
+///                      previews: BTreeMap::new(),
+///                      api_url: None,
+///                      web_url: None,
+///                      locale: None,
+///                      catalogue: Catalogue::default(),
 ///                  },
 ///                  Feed {
 ///                      subject: "sehe".to_string(),
@@ -126,6 +146,18 @@ pub fn verify(config_dir: &(String, PathBuf)) -> Result<(PathBuf, PathBuf), Erro
 ///                      latest: Some(now - Duration::minutes(2)),
 ///                      next_min: Some(now - Duration::minutes(1)),
 ///                      latest_event: Some(4856265369),
+///                      format: Format::Markdown,
+///                      templates: BTreeMap::new(),
+///                      time: TimeFormat::default(),
+///                      trusted: vec![],
+///                      excerpt: None,
+///                      max_commits: 5,
+///                      distinct_commits_only: false,
+///                      previews: BTreeMap::new(),
+///                      api_url: None,
+///                      web_url: None,
+///                      locale: None,
+///                      catalogue: Catalogue::default(),
 ///                  }];
 ///
 /// let new_feeds: Vec<_> = feeds.iter().filter(|f| start_daemon::feeds_filter(&mut out, f)).collect();
@@ -146,35 +178,126 @@ pub fn feeds_filter<W: Write>(output: &mut W, f: &Feed) -> bool {
     }
 }
 
-/// Create a Discord message body from an event.
+/// Get how long to wait before polling the specified feeds again, which is the specified time,
+/// or, if requests to all the GitHub instances they're on are paused by their rate limits for longer than that,
+/// until the first can resume, see `github::paused_until()`.
+///
+/// # Examples
+///
+/// ```
+/// # use dishub::ops::start_daemon;
+/// # use dishub::ops::Feed;
+/// # use std::time::Duration;
+/// let feeds = vec![Feed::new("nabijaczleweli/dishub".to_string(), 105, 137)];
+/// assert_eq!(start_daemon::sleep_time(Duration::from_secs(60), &feeds), Duration::from_secs(60));
+/// assert_eq!(start_daemon::sleep_time(Duration::from_secs(60), &[]), Duration::from_secs(60));
+/// ```
+pub fn sleep_time(sleep: StdDuration, feeds: &[Feed]) -> StdDuration {
+    // None, and so the minimum, if any instance isn't paused
+    let pause = feeds.iter()
+        .map(|f| github::paused_until(&f.github_urls().api))
+        .min()
+        .and_then(|until| until)
+        .and_then(|until| (until - Local::now().with_timezone(until.offset())).to_std().ok());
+    match pause {
+        Some(pause) if pause > sleep => pause,
+        _ => sleep,
+    }
+}
+
+/// Create a plain-text Discord message body from an event.
+///
+/// This is the same as what `PlainRenderer` produces.
 ///
 /// # Examples
 ///
 /// ```
 /// # extern crate chrono;
 /// # extern crate dishub;
+/// # extern crate json;
 /// # use chrono::DateTime;
-/// # use dishub::ops::{start_daemon, EventPayload, Event};
+/// # use json::JsonValue;
+/// # use dishub::ops::{start_daemon, EventPayload, WatchAction, Event, Actor, Repo};
 /// # fn main() {
 /// assert_eq!(&start_daemon::post_text(&Event {
 ///     created_at: DateTime::parse_from_rfc2822("Tue, 8 Nov 2016 03:10:26 +0000").unwrap(),
-///     actor: "carllhw".to_string(),
-///     repo: "nabijaczleweli/cargo-update".to_string(),
+///     actor: Actor {
+///         id: 5784597,
+///         login: "carllhw".to_string(),
+///         display_login: "carllhw".to_string(),
+///         avatar_url: "https://avatars.githubusercontent.com/u/5784597?".to_string(),
+///     },
+///     repo: Repo {
+///         id: 71928026,
+///         name: "nabijaczleweli/cargo-update".to_string(),
+///     },
+///     org: None,
 ///     id: 4831774905,
 ///     payload: EventPayload::Watch {
-///         action: "started".to_string(),
+///         action: WatchAction::Started,
 ///     },
+///     raw: JsonValue::Null,
 /// }), "08.11.2016 03:10:26 AM: carllhw starred nabijaczleweli/cargo-update\n\
 ///      <https://github.com/nabijaczleweli/cargo-update/stargazers>");
 /// # }
 /// ```
 pub fn post_text(ev: &Event) -> String {
-    ev.urls().into_iter().fold(ev.to_string(), |t, u| t + "\n<" + &u + ">")
+    PlainRenderer.text(ev, &RenderOptions::default())
+}
+
+/// Create the message noting that some events in the feed with the specified subject were missed (see `Feed::poll()`),
+/// with the `"events_missed"` message from the options' catalogue.
+///
+/// # Examples
+///
+/// ```
+/// # use dishub::ops::render::{RenderOptions, Message};
+/// # use dishub::ops::start_daemon;
+/// assert_eq!(start_daemon::missed_events_message("nabijaczleweli/cargo_update", &RenderOptions::default()),
+///            Message::Text("Some events in nabijaczleweli/cargo\\_update were missed".to_string()));
+/// ```
+pub fn missed_events_message(subject: &str, options: &RenderOptions) -> Message {
+    Message::Text(options.catalogue.format("events_missed", None, 0, &[("subject", &escape_markdown(subject))]))
+}
+
+/// Render the specified events into messages to post with the specified renderer and options.
+///
+/// Usually the renderer and options are the feed's, i.e. `feed.renderer()` and `feed.render_options()`.
+///
+/// Text messages longer than Discord allows are split into several, see `render::split_message()`.
+///
+/// # Examples
+///
+/// ```
+/// # use dishub::ops::render::{MarkdownRenderer, RenderOptions, Message};
+/// # use dishub::ops::{start_daemon, Event};
+/// let response = r#"[{
+///                     "id": "4831774905",
+///                     "type": "WatchEvent",
+///                     "actor": { "id": 5784597, "login": "carllhw", "avatar_url": "https://avatars.githubusercontent.com/u/5784597?" },
+///                     "repo": { "id": 71928026, "name": "nabijaczleweli/cargo-update" },
+///                     "payload": { "action": "started" },
+///                     "created_at": "2016-11-08T03:10:26Z"
+///                   }]"#;
+/// assert_eq!(start_daemon::render_messages(&MarkdownRenderer, &RenderOptions::default(), &Event::parse(response).unwrap()),
+///            vec![Message::Text("*08.11.2016 03:10:26 AM*\n\
+///                                **carllhw starred nabijaczleweli/cargo-update**\n\
+///                                <https://github.com/nabijaczleweli/cargo-update/stargazers>".to_string())]);
+/// ```
+pub fn render_messages<R: Renderer + ?Sized>(renderer: &R, options: &RenderOptions, events: &[Event]) -> Vec<Message> {
+    events.iter()
+        .flat_map(|ev| match renderer.render(ev, options) {
+            Message::Text(text) => split_message(&text, MESSAGE_LIMIT).into_iter().map(Message::Text).collect(),
+            msg => vec![msg],
+        })
+        .collect()
 }
 
 /// Post the specified messages to a Discord channel.
 ///
-/// Will automatially wait on rate-limits.
+/// Will automatially wait on rate-limits, and retry messages failing transiently per `retry`, writing the retries to `log`.
+///
+/// The messages are posted with no allowed mentions, so nothing in them can ping anyone, regardless of escaping.
 ///
 /// # Examples
 ///
@@ -182,7 +305,10 @@ pub fn post_text(ev: &Event) -> String {
 /// # extern crate chrono;
 /// # extern crate dishub;
 /// # use dishub::Error;
+/// # use dishub::ops::render::Message;
+/// # use dishub::ops::retry::RetryPolicy;
 /// # use dishub::ops::{start_daemon, AppTokens};
+/// # use std::io::stderr;
 /// # struct Feed {
 /// #     channel: u64,
 /// # }
@@ -190,35 +316,78 @@ pub fn post_text(ev: &Event) -> String {
 /// # let tokens = AppTokens {
 /// #     github: "".to_string(),
 /// #     discord: "".to_string(),
+/// #     github_instances: None,
 /// # };
 /// # let feed = Feed {
 /// #     channel: 10,
 /// # };
 /// start_daemon::send_messages(&tokens,
-///     vec!["08.11.2016 03:10:26 AM: carllhw starred nabijaczleweli/cargo-update\n\
-///           <https://github.com/nabijaczleweli/cargo_update/stargazers>".to_string(),
-///          "09.11.2016 06:14:26 PM: sehe pushed 1 commit to sehe/opus\n\
-///           <https://github.com/sehe/opus/compare/95659cd...eb282d9>".to_string()],
-///     feed.channel).unwrap();
+///     vec![Message::Text("08.11.2016 03:10:26 AM: carllhw starred nabijaczleweli/cargo-update\n\
+///                         <https://github.com/nabijaczleweli/cargo_update/stargazers>".to_string()),
+///          Message::Text("09.11.2016 06:14:26 PM: sehe pushed 1 commit to sehe/opus\n\
+///                         <https://github.com/sehe/opus/compare/95659cd...eb282d9>".to_string())],
+///     feed.channel,
+///     &RetryPolicy::default(),
+///     &mut stderr()).unwrap();
 /// # }
 /// ```
-pub fn send_messages(tokens: &AppTokens, txts: Vec<String>, channel: u64) -> Result<(), Error> {
-    let discord = try!(Discord::from_bot_token(&tokens.discord).map_err(|_| Error::LoginFailed("Discord")));
-
-    for txt in txts {
-        loop {
-            match discord.send_message(&ChannelId(channel), &txt, "", false) {
-                Err(DisErr::RateLimited(ms)) => thread::sleep(Duration::milliseconds(ms as i64).to_std().unwrap()),
-                Err(_) => {
-                    return Err(Error::Io {
-                        desc: "event message",
-                        op: "post",
-                    })
-                }
-                Ok(_) => break,
-            }
-        }
+pub fn send_messages<W: Write>(tokens: &AppTokens, msgs: Vec<Message>, channel: u64, retry: &RetryPolicy, log: &mut W) -> Result<(), Error> {
+    for msg in msgs {
+        let (mut body, desc) = match msg {
+            Message::Text(txt) => (object!{ "content" => txt }, "event message"),
+            Message::Embed(embed) => (object!{ "embeds" => vec![embed.to_json()] }, "event embed"),
+        };
+        body["allowed_mentions"] = object!{ "parse" => json::JsonValue::new_array() };
+        let body = body.dump();
+
+        try!(retry.run(log, || post_message(tokens, &body, channel, desc)));
     }
 
     Ok(())
 }
+
+
+/// How many times a message is posted before giving up on Discord's rate limit.
+const POST_ATTEMPTS: u32 = 5;
+
+/// The longest a rate-limited post waits before it's retried.
+const MAX_RETRY_AFTER: f64 = 60f64;
+
+
+/// The `discord` crate predates embeds and can't time out, so post messages directly.
+///
+/// Rate-limited posts are retried after the `retry_after` Discord asks for, up to `POST_ATTEMPTS` times in total,
+/// other unsuccessful responses are returned as errors with Discord's message.
+fn post_message(tokens: &AppTokens, body: &str, channel: u64, desc: &'static str) -> Result<(), Error> {
+    let url = format!("https://discord.com/api/v10/channels/{}/messages", channel);
+    let mut headers = Headers::new();
+    headers.set(Authorization(format!("Bot {}", tokens.discord)));
+    headers.set(ContentType::json());
+
+    let mut attempt = 1;
+    loop {
+        let resp = try!(http::post(&url, headers.clone(), body.to_string(), desc, "post"));
+
+        if resp.status.is_success() {
+            return Ok(());
+        } else if resp.status.is_server_error() {
+            return Err(Error::DiscordServerError {
+                desc: desc,
+                status: resp.status.to_u16(),
+            });
+        }
+
+        let body = json::parse(&resp.body).ok();
+        if resp.status == StatusCode::TooManyRequests && attempt < POST_ATTEMPTS {
+            let retry_after = body.as_ref().and_then(|b| b["retry_after"].as_f64()).filter(|r| *r >= 0f64).unwrap_or(1f64);
+            thread::sleep(StdDuration::from_millis((retry_after.min(MAX_RETRY_AFTER) * 1000f64) as u64));
+            attempt += 1;
+        } else {
+            return Err(Error::DiscordRequestFailed {
+                desc: desc,
+                status: resp.status.to_u16(),
+                message: body.as_ref().and_then(|b| b["message"].as_str().map(str::to_string)),
+            });
+        }
+    }
+}
@@ -12,6 +12,8 @@
 
 
 use clap::{self, App, SubCommand, Arg, AppSettings};
+use self::super::ops::retry::RetryPolicy;
+use self::super::ops::http::HttpConfig;
 use std::time::Duration;
 use std::path::PathBuf;
 use std::env::home_dir;
@@ -41,6 +43,8 @@ pub enum Subsystem {
     StartDaemon {
         /// How long to sleep between each iteration. Default: 1 minute
         sleep: Duration,
+        /// How to retry requests failing transiently. Default: `RetryPolicy::default()`
+        retry: RetryPolicy,
     },
 }
 
@@ -50,6 +54,8 @@ pub enum Subsystem {
 pub struct Options {
     /// Directory containing configuration. Default: `"$HOME/.dishub"`
     pub config_dir: (String, PathBuf),
+    /// How to make HTTP requests, i.e. their timeouts. Default: `HttpConfig::default()`
+    pub http: HttpConfig,
     /// The specified subsystem.
     pub subsystem: Subsystem,
 }
@@ -66,6 +72,15 @@ impl Options {
             .about("Rust app for posting GitHub activity on Discord")
             .arg(Arg::from_usage("-c --config-dir=[CONFIG_DIR] 'Directory containing configuration. Default: $HOME/.dishub'")
                 .validator(Options::config_dir_validator))
+            .arg(Arg::from_usage("--connect-timeout=[CONNECT_TIMEOUT] 'Time to wait for connections to GitHub and Discord'")
+                .default_value("10s")
+                .validator(|s| Options::timeout_validator("--connect-timeout", s)))
+            .arg(Arg::from_usage("--read-timeout=[READ_TIMEOUT] 'Time to wait for each read from GitHub and Discord'")
+                .default_value("30s")
+                .validator(|s| Options::timeout_validator("--read-timeout", s)))
+            .arg(Arg::from_usage("--timeout=[TIMEOUT] 'Time to wait for whole requests to GitHub and Discord'")
+                .default_value("60s")
+                .validator(|s| Options::timeout_validator("--timeout", s)))
             .subcommand(SubCommand::with_name("init")
                 .about("Initialise global app data")
                 .arg(Arg::from_usage("-f --force 'Override current app configuration'")))
@@ -75,6 +90,15 @@ impl Options {
                 .about("Run the activity-posting daemon")
                 .arg(Arg::from_usage("-s --sleep=[SLEEP_TIME] 'Time to sleep between each iteration'")
                     .default_value("60s")
+                    .validator(Options::sleep_validator))
+                .arg(Arg::from_usage("-r --retries=[RETRIES] 'How many times to retry failed requests'")
+                    .default_value("5")
+                    .validator(Options::retries_validator))
+                .arg(Arg::from_usage("--retry-delay=[RETRY_DELAY] 'Time to wait before the first retry, doubled for each further one'")
+                    .default_value("1s")
+                    .validator(Options::sleep_validator))
+                .arg(Arg::from_usage("--max-retry-delay=[MAX_RETRY_DELAY] 'Longest time to wait before a retry'")
+                    .default_value("300s")
                     .validator(Options::sleep_validator)))
             .get_matches();
 
@@ -101,12 +125,26 @@ impl Options {
                     }
                 }
             },
+            http: HttpConfig {
+                connect_timeout: Duration::from_secs(Options::parse_sleep(matches.value_of("connect-timeout").unwrap()).unwrap()),
+                read_timeout: Duration::from_secs(Options::parse_sleep(matches.value_of("read-timeout").unwrap()).unwrap()),
+                timeout: Duration::from_secs(Options::parse_sleep(matches.value_of("timeout").unwrap()).unwrap()),
+                ..HttpConfig::default()
+            },
             subsystem: match matches.subcommand() {
                 ("init", Some(init_matches)) => Subsystem::Init { force: init_matches.is_present("force") },
                 ("add-feeds", _) => Subsystem::AddFeeds,
                 ("unfollow-feeds", _) => Subsystem::UnfollowFeeds,
                 ("start-daemon", Some(start_daemon_matches)) => {
-                    Subsystem::StartDaemon { sleep: Duration::from_secs(Options::parse_sleep(start_daemon_matches.value_of("sleep").unwrap()).unwrap()) }
+                    let duration = |arg| Duration::from_secs(Options::parse_sleep(start_daemon_matches.value_of(arg).unwrap()).unwrap());
+                    Subsystem::StartDaemon {
+                        sleep: duration("sleep"),
+                        retry: RetryPolicy {
+                            retries: u32::from_str(start_daemon_matches.value_of("retries").unwrap()).unwrap(),
+                            initial_delay: duration("retry-delay"),
+                            max_delay: duration("max-retry-delay"),
+                        },
+                    }
                 }
                 _ => panic!("No subcommand passed"),
             },
@@ -127,4 +165,15 @@ impl Options {
             Some(_) => Ok(()),
         }
     }
+
+    fn timeout_validator(option: &str, s: String) -> Result<(), String> {
+        match Options::parse_sleep(&s) {
+            None | Some(0) => Err(format!("\"{}\" is not a valid {} (in format \"NNNs\", at least 1s)", s, option)),
+            Some(_) => Ok(()),
+        }
+    }
+
+    fn retries_validator(s: String) -> Result<(), String> {
+        u32::from_str(&s).map(|_| ()).map_err(|_| format!("\"{}\" is not a valid number of retries", s))
+    }
 }
@@ -8,6 +8,12 @@ use std::iter;
 /// The User-Agent to use when authenticating to the GitHub API.
 pub static GITHUB_USER_AGENT: &'static str = concat!("dishub/", env!("CARGO_PKG_VERSION"));
 
+/// The base URL of github.com's API, used unless another instance is configured, see `ops::github::GitHubUrls`.
+pub static GITHUB_API_URL: &'static str = "https://api.github.com";
+
+/// The base URL of github.com's web interface, used unless another instance is configured, see `ops::github::GitHubUrls`.
+pub static GITHUB_WEB_URL: &'static str = "https://github.com";
+
 
 /// Uppercase the first character of the supplied string.
 ///
@@ -45,3 +45,134 @@ fn watched_does_not_exist() {
 fn login_failed() {
     assert_eq!(Error::LoginFailed("").exit_value(), 6);
 }
+
+#[test]
+fn event_parsing_failed() {
+    assert_eq!(Error::EventParsingFailed {
+                       id: "".to_string(),
+                       tp: "".to_string(),
+                       path: "".to_string(),
+                   }
+                   .exit_value(),
+               7);
+}
+
+#[test]
+fn github_unauthorised() {
+    assert_eq!(Error::GitHubUnauthorised {
+                       status: 401,
+                       url: "".to_string(),
+                       message: None,
+                   }
+                   .exit_value(),
+               8);
+}
+
+#[test]
+fn github_forbidden() {
+    assert_eq!(Error::GitHubForbidden {
+                       status: 403,
+                       url: "".to_string(),
+                       message: None,
+                   }
+                   .exit_value(),
+               9);
+}
+
+#[test]
+fn github_not_found() {
+    assert_eq!(Error::GitHubNotFound {
+                       status: 404,
+                       url: "".to_string(),
+                       message: None,
+                   }
+                   .exit_value(),
+               10);
+}
+
+#[test]
+fn github_server_error() {
+    assert_eq!(Error::GitHubServerError {
+                       status: 502,
+                       url: "".to_string(),
+                       message: None,
+                   }
+                   .exit_value(),
+               11);
+}
+
+#[test]
+fn github_unexpected_status() {
+    assert_eq!(Error::GitHubUnexpectedStatus {
+                       status: 422,
+                       url: "".to_string(),
+                       message: None,
+                   }
+                   .exit_value(),
+               12);
+}
+
+#[test]
+fn github_rate_limited() {
+    assert_eq!(Error::GitHubRateLimited {
+                       url: "".to_string(),
+                       message: None,
+                       until: None,
+                   }
+                   .exit_value(),
+               13);
+}
+
+#[test]
+fn network() {
+    assert_eq!(Error::Network {
+                       desc: "",
+                       op: "",
+                       url: "".to_string(),
+                   }
+                   .exit_value(),
+               14);
+}
+
+#[test]
+fn discord_server_error() {
+    assert_eq!(Error::DiscordServerError {
+                       desc: "",
+                       status: 502,
+                   }
+                   .exit_value(),
+               15);
+}
+
+#[test]
+fn timeout() {
+    assert_eq!(Error::Timeout {
+                       desc: "",
+                       op: "",
+                       url: "".to_string(),
+                   }
+                   .exit_value(),
+               16);
+}
+
+#[test]
+fn proxy_refused() {
+    assert_eq!(Error::ProxyRefused {
+                       desc: "",
+                       url: "".to_string(),
+                       reason: "".to_string(),
+                   }
+                   .exit_value(),
+               17);
+}
+
+#[test]
+fn discord_request_failed() {
+    assert_eq!(Error::DiscordRequestFailed {
+                       desc: "",
+                       status: 403,
+                       message: None,
+                   }
+                   .exit_value(),
+               18);
+}
@@ -0,0 +1,63 @@
+use dishub::Error;
+
+
+#[test]
+fn transient() {
+    for err in &[Error::Network {
+                     desc: "GitHub repo events",
+                     op: "poll",
+                     url: "https://api.github.com/repos/nabijaczleweli/dishub/events".to_string(),
+                 },
+                 Error::GitHubServerError {
+                     status: 502,
+                     url: "https://api.github.com/repos/nabijaczleweli/dishub/events".to_string(),
+                     message: None,
+                 },
+                 Error::Timeout {
+                     desc: "GitHub repo events",
+                     op: "poll",
+                     url: "https://api.github.com/repos/nabijaczleweli/dishub/events".to_string(),
+                 },
+                 Error::DiscordServerError {
+                     desc: "event message",
+                     status: 503,
+                 }] {
+        assert!(err.is_transient(), "{:?}", err);
+    }
+}
+
+#[test]
+fn permanent() {
+    for err in &[Error::Io {
+                     desc: "event message",
+                     op: "post",
+                 },
+                 Error::GitHubNotFound {
+                     status: 404,
+                     url: "https://api.github.com/repos/nabijaczleweli/dishub/events".to_string(),
+                     message: None,
+                 },
+                 Error::GitHubRateLimited {
+                     url: "https://api.github.com/repos/nabijaczleweli/dishub/events".to_string(),
+                     message: None,
+                     until: None,
+                 },
+                 Error::Timeout {
+                     desc: "event message",
+                     op: "post",
+                     url: "https://discord.com/api/v10/channels/105/messages".to_string(),
+                 },
+                 Error::ProxyRefused {
+                     desc: "event message",
+                     url: "https://discord.com/api/v10/channels/105/messages".to_string(),
+                     reason: "SOCKS5 proxy rejected the user name and password".to_string(),
+                 },
+                 Error::DiscordRequestFailed {
+                     desc: "event message",
+                     status: 429,
+                     message: Some("You are being rate limited.".to_string()),
+                 },
+                 Error::LoginFailed("Discord")] {
+        assert!(!err.is_transient(), "{:?}", err);
+    }
+}
@@ -1,2 +1,3 @@
 mod exit_value;
 mod print_error;
+mod is_transient;
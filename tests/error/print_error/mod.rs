@@ -1,5 +1,6 @@
-use dishub::Error;
 use std::iter::FromIterator;
+use chrono::DateTime;
+use dishub::Error;
 
 mod file_parsing_failed;
 
@@ -55,3 +56,186 @@ fn login_failed() {
     Error::LoginFailed("GitHub").print_error(&mut out);
     assert_eq!(String::from_iter(out.iter().map(|&i| i as char)), "Failed to log in to GitHub.\n".to_string());
 }
+
+#[test]
+fn event_parsing_failed() {
+    let mut out = Vec::new();
+    Error::EventParsingFailed {
+            id: "4844096927".to_string(),
+            tp: "IssuesEvent".to_string(),
+            path: "payload.issue.number".to_string(),
+        }
+        .print_error(&mut out);
+    assert_eq!(String::from_iter(out.iter().map(|&i| i as char)),
+               "Failed to parse IssuesEvent 4844096927: \"payload.issue.number\" is malformed.\n".to_string());
+}
+
+#[test]
+fn github_unauthorised() {
+    let mut out = Vec::new();
+    Error::GitHubUnauthorised {
+            status: 401,
+            url: "https://api.github.com/users/nabijaczleweli/events".to_string(),
+            message: Some("Bad credentials".to_string()),
+        }
+        .print_error(&mut out);
+    assert_eq!(String::from_iter(out.iter().map(|&i| i as char)),
+               "GitHub rejected the token for https://api.github.com/users/nabijaczleweli/events (401: \"Bad credentials\"), \
+                check it with dishub init --force.\n"
+                   .to_string());
+}
+
+#[test]
+fn github_forbidden() {
+    let mut out = Vec::new();
+    Error::GitHubForbidden {
+            status: 403,
+            url: "https://api.github.com/repos/nabijaczleweli/dishub/events".to_string(),
+            message: Some("Repository access blocked".to_string()),
+        }
+        .print_error(&mut out);
+    assert_eq!(String::from_iter(out.iter().map(|&i| i as char)),
+               "GitHub refused access to https://api.github.com/repos/nabijaczleweli/dishub/events (403: \"Repository access blocked\").\n".to_string());
+}
+
+#[test]
+fn github_not_found() {
+    let mut out = Vec::new();
+    Error::GitHubNotFound {
+            status: 404,
+            url: "https://api.github.com/repos/nabijaczleweli/dishub/events".to_string(),
+            message: Some("Not Found".to_string()),
+        }
+        .print_error(&mut out);
+    assert_eq!(String::from_iter(out.iter().map(|&i| i as char)),
+               "https://api.github.com/repos/nabijaczleweli/dishub/events wasn't found on GitHub (404: \"Not Found\").\n".to_string());
+}
+
+#[test]
+fn github_server_error() {
+    let mut out = Vec::new();
+    Error::GitHubServerError {
+            status: 502,
+            url: "https://api.github.com/repos/nabijaczleweli/dishub/events".to_string(),
+            message: None,
+        }
+        .print_error(&mut out);
+    assert_eq!(String::from_iter(out.iter().map(|&i| i as char)),
+               "GitHub failed to handle https://api.github.com/repos/nabijaczleweli/dishub/events (502).\n".to_string());
+}
+
+#[test]
+fn github_unexpected_status() {
+    let mut out = Vec::new();
+    Error::GitHubUnexpectedStatus {
+            status: 422,
+            url: "https://api.github.com/repos/nabijaczleweli/dishub/events".to_string(),
+            message: Some("Validation Failed".to_string()),
+        }
+        .print_error(&mut out);
+    assert_eq!(String::from_iter(out.iter().map(|&i| i as char)),
+               "Unexpected response from GitHub for https://api.github.com/repos/nabijaczleweli/dishub/events (422: \"Validation Failed\").\n"
+                   .to_string());
+}
+
+#[test]
+fn github_rate_limited() {
+    let mut out = Vec::new();
+    Error::GitHubRateLimited {
+            url: "https://api.github.com/repos/nabijaczleweli/dishub/events".to_string(),
+            message: Some("API rate limit exceeded for 127.0.0.1.".to_string()),
+            until: Some(DateTime::parse_from_rfc3339("2016-11-10T09:08:58+01:00").unwrap()),
+        }
+        .print_error(&mut out);
+    assert_eq!(String::from_iter(out.iter().map(|&i| i as char)),
+               "GitHub's rate limit was hit for https://api.github.com/repos/nabijaczleweli/dishub/events (\"API rate limit exceeded for 127.0.0.1.\"), \
+                pausing until 09:08:58.\n"
+                   .to_string());
+
+    let mut out = Vec::new();
+    Error::GitHubRateLimited {
+            url: "https://api.github.com/users/nabijaczleweli/events".to_string(),
+            message: None,
+            until: None,
+        }
+        .print_error(&mut out);
+    assert_eq!(String::from_iter(out.iter().map(|&i| i as char)),
+               "GitHub's rate limit was hit for https://api.github.com/users/nabijaczleweli/events.\n".to_string());
+}
+
+#[test]
+fn network() {
+    let mut out = Vec::new();
+    Error::Network {
+            desc: "GitHub repo events",
+            op: "poll",
+            url: "https://api.github.com/repos/nabijaczleweli/dishub/events".to_string(),
+        }
+        .print_error(&mut out);
+    assert_eq!(String::from_iter(out.iter().map(|&i| i as char)),
+               "Polling GitHub repo events failed: no response from https://api.github.com/repos/nabijaczleweli/dishub/events.\n".to_string());
+}
+
+#[test]
+fn discord_server_error() {
+    let mut out = Vec::new();
+    Error::DiscordServerError {
+            desc: "event embed",
+            status: 502,
+        }
+        .print_error(&mut out);
+    assert_eq!(String::from_iter(out.iter().map(|&i| i as char)), "Discord failed to handle the event embed (502).\n".to_string());
+}
+
+#[test]
+fn discord_request_failed() {
+    let mut out = Vec::new();
+    Error::DiscordRequestFailed {
+            desc: "event embed",
+            status: 403,
+            message: Some("Missing Access".to_string()),
+        }
+        .print_error(&mut out);
+    assert_eq!(String::from_iter(out.iter().map(|&i| i as char)),
+               "Discord rejected the event embed (403: \"Missing Access\").\n".to_string());
+}
+
+#[test]
+fn discord_request_failed_no_message() {
+    let mut out = Vec::new();
+    Error::DiscordRequestFailed {
+            desc: "event message",
+            status: 429,
+            message: None,
+        }
+        .print_error(&mut out);
+    assert_eq!(String::from_iter(out.iter().map(|&i| i as char)), "Discord rejected the event message (429).\n".to_string());
+}
+
+#[test]
+fn timeout() {
+    let mut out = Vec::new();
+    Error::Timeout {
+            desc: "event message",
+            op: "post",
+            url: "https://discord.com/api/v10/channels/105/messages".to_string(),
+        }
+        .print_error(&mut out);
+    assert_eq!(String::from_iter(out.iter().map(|&i| i as char)),
+               "Posting event message timed out waiting for https://discord.com/api/v10/channels/105/messages.\n".to_string());
+}
+
+#[test]
+fn proxy_refused() {
+    let mut out = Vec::new();
+    Error::ProxyRefused {
+            desc: "event message",
+            url: "https://discord.com/api/v10/channels/105/messages".to_string(),
+            reason: "proxy responded with \"HTTP/1.1 407 Proxy Authentication Required\"".to_string(),
+        }
+        .print_error(&mut out);
+    assert_eq!(String::from_iter(out.iter().map(|&i| i as char)),
+               "The proxy refused to connect to https://discord.com/api/v10/channels/105/messages for the event message: proxy responded with \"HTTP/1.1 \
+                407 Proxy Authentication Required\".\n"
+                   .to_string());
+}
@@ -1,5 +1,6 @@
 extern crate chrono;
 extern crate dishub;
+#[macro_use]
 extern crate json;
 
 mod error;
@@ -0,0 +1,41 @@
+use dishub::ops::add_feeds::get_github_urls;
+use dishub::ops::github::GitHubUrls;
+use std::io::Cursor;
+
+
+#[test]
+fn default() {
+    let mut out = Vec::new();
+    let result = get_github_urls(&mut Cursor::new(b"\n"), &mut out);
+
+    assert_eq!(result, None);
+    assert_eq!(out[..], b"GitHub instance API URL (or empty for the default): "[..]);
+}
+
+#[test]
+fn enterprise() {
+    let mut out = Vec::new();
+    let result = get_github_urls(&mut Cursor::new(b"ghe.example.com\nhttps://ghe.example.com/api/v3\n"), &mut out);
+
+    assert_eq!(result,
+               Some(GitHubUrls {
+                   api: "https://ghe.example.com/api/v3".to_string(),
+                   web: "https://ghe.example.com".to_string(),
+               }));
+    assert_eq!(out[..],
+               b"GitHub instance API URL (or empty for the default): Invalid URL ghe.example.com\n\
+                 GitHub instance API URL (or empty for the default): "[..]);
+}
+
+#[test]
+fn underivable_web_url() {
+    let mut out = Vec::new();
+    let result = get_github_urls(&mut Cursor::new(b"https://git.example.com/github-api\nhttps://git.example.com/\n"), &mut out);
+
+    assert_eq!(result,
+               Some(GitHubUrls {
+                   api: "https://git.example.com/github-api".to_string(),
+                   web: "https://git.example.com".to_string(),
+               }));
+    assert_eq!(out[..], b"GitHub instance API URL (or empty for the default): GitHub instance web URL: "[..]);
+}
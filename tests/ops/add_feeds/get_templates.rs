@@ -0,0 +1,60 @@
+use dishub::ops::add_feeds::get_templates;
+use dishub::ops::EventPayload;
+use std::io::Cursor;
+use std::str;
+
+
+#[test]
+fn none() {
+    let mut out = Vec::new();
+    let result = get_templates(&mut Cursor::new(b"\n"), &mut out);
+
+    assert!(result.is_empty());
+    assert_eq!(str::from_utf8(&out[..]).unwrap(),
+               format!("Event types: {}\n\
+                        \n\
+                        Event type to template (or empty to end): ",
+                       EventPayload::type_names().join(", ")));
+}
+
+#[test]
+fn correct() {
+    let mut out = Vec::new();
+    let result = get_templates(&mut Cursor::new(&b"Push\n\
+                                                   {{actor}} pushed:\\\n\
+                                                   {{#each payload.commits}}{{sha}}\\\n\
+                                                   {{/each}}\n\
+                                                   Watch\n\
+                                                   {{actor}} starred {{repo}}\n\
+                                                   \n"[..]),
+                               &mut out);
+
+    assert_eq!(result.iter().map(|(tp, t)| (&tp[..], t.source())).collect::<Vec<_>>(),
+               vec![("Push", "{{actor}} pushed:\n{{#each payload.commits}}{{sha}}\n{{/each}}"), ("Watch", "{{actor}} starred {{repo}}")]);
+}
+
+#[test]
+fn unknown_type() {
+    let mut out = Vec::new();
+    let result = get_templates(&mut Cursor::new(&b"PushEvent\n\
+                                                   Push\n\
+                                                   {{actor}} pushed\n\
+                                                   \n"[..]),
+                               &mut out);
+
+    assert_eq!(result.keys().collect::<Vec<_>>(), vec!["Push"]);
+    assert!(str::from_utf8(&out[..]).unwrap().contains("Unknown event type PushEvent\n"));
+}
+
+#[test]
+fn malformed_template() {
+    let mut out = Vec::new();
+    let result = get_templates(&mut Cursor::new(&b"Push\n\
+                                                   {{#if payload.commits}}\n\
+                                                   {{actor}} pushed\n\
+                                                   \n"[..]),
+                               &mut out);
+
+    assert_eq!(result["Push"].source(), "{{actor}} pushed");
+    assert!(str::from_utf8(&out[..]).unwrap().contains("Template: Malformed template: 1:1: unclosed {{#if}}\nTemplate: "));
+}
@@ -1,4 +1,6 @@
 mod get_watch_subject;
+mod get_github_urls;
 mod get_valid_channel;
 mod get_valid_server;
+mod get_templates;
 mod verify;
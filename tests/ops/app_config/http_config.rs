@@ -0,0 +1,62 @@
+use dishub::ops::proxy::{ProxyKind, Proxy};
+use dishub::ops::http::HttpConfig;
+use dishub::ops::AppConfig;
+use std::time::Duration;
+use std::path::PathBuf;
+
+
+fn env(var: &str) -> Option<String> {
+    match var {
+        "https_proxy" => Some("http://env-proxy:3128".to_string()),
+        "no_proxy" => Some("github.com,,discord.com ".to_string()),
+        _ => None,
+    }
+}
+
+
+#[test]
+fn from_env() {
+    let config = AppConfig::default().http_config(HttpConfig::default(), env).unwrap();
+    assert_eq!(config.proxy,
+               Some(Proxy {
+                   kind: ProxyKind::Http,
+                   host: "env-proxy".to_string(),
+                   port: 3128,
+                   username: None,
+                   password: None,
+               }));
+    assert_eq!(config.no_proxy, vec!["github.com".to_string(), "discord.com".to_string()]);
+}
+
+#[test]
+fn overrides_env() {
+    let config = AppConfig {
+            proxy: Some("socks5h://config-proxy".to_string()),
+            no_proxy: Some(vec!["*".to_string()]),
+            ..AppConfig::default()
+        }
+        .http_config(HttpConfig::default(), env)
+        .unwrap();
+    assert_eq!(config.proxy.map(|p| (p.kind, p.host, p.port)), Some((ProxyKind::Socks5, "config-proxy".to_string(), 1080)));
+    assert_eq!(config.no_proxy, vec!["*".to_string()]);
+}
+
+#[test]
+fn none() {
+    let config = AppConfig::default().http_config(HttpConfig::default(), |_| None).unwrap();
+    assert_eq!(config, HttpConfig::default());
+}
+
+#[test]
+fn keeps_timeouts() {
+    let http = HttpConfig { connect_timeout: Duration::from_secs(1), ..HttpConfig::default() };
+    assert_eq!(AppConfig::default().http_config(http.clone(), env).unwrap().connect_timeout, http.connect_timeout);
+}
+
+#[test]
+fn ca_bundle() {
+    let config = AppConfig { ca_bundle: Some("/etc/dishub/ca.pem".to_string()), ..AppConfig::default() }
+        .http_config(HttpConfig::default(), |_| None)
+        .unwrap();
+    assert_eq!(config.ca_bundle, Some(PathBuf::from("/etc/dishub/ca.pem")));
+}
@@ -0,0 +1,2 @@
+mod read;
+mod http_config;
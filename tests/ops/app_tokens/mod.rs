@@ -14,6 +14,7 @@ fn write() {
     AppTokens {
             discord: "EaCdWByA0BTPnlt6OYpnuAc0.cfPzzG.7UuaJIiPatD507FYjgBcHw3Ecob".to_string(),
             github: "YO4QXSAMh72MivCCOYpRWxNx0ZpEwocF8DM1D130".to_string(),
+            github_instances: None,
         }
         .write(&path);
 
@@ -26,3 +27,13 @@ fn write() {
                "discord = \"EaCdWByA0BTPnlt6OYpnuAc0.cfPzzG.7UuaJIiPatD507FYjgBcHw3Ecob\"\n\
                 github = \"YO4QXSAMh72MivCCOYpRWxNx0ZpEwocF8DM1D130\"\n");
 }
+
+#[test]
+fn github_token_empty() {
+    let tokens = AppTokens {
+        discord: "EaCdWByA0BTPnlt6OYpnuAc0.cfPzzG.7UuaJIiPatD507FYjgBcHw3Ecob".to_string(),
+        github: "".to_string(),
+        github_instances: None,
+    };
+    assert_eq!(tokens.github_token("https://api.github.com"), None);
+}
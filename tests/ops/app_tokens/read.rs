@@ -1,5 +1,6 @@
 use self::super::super::make_dir;
 use dishub::ops::AppTokens;
+use std::collections::BTreeMap;
 use std::io::Write;
 use std::fs::File;
 use dishub::Error;
@@ -19,6 +20,30 @@ fn correct() {
                Ok(AppTokens {
                    discord: "EaCdWByA0BTPnlt6OYpnuAc0.cfPzzG.7UuaJIiPatD507FYjgBcHw3Ecob".to_string(),
                    github: "YO4QXSAMh72MivCCOYpRWxNx0ZpEwocF8DM1D130".to_string(),
+                   github_instances: None,
+               }));
+}
+
+#[test]
+fn github_instances() {
+    let mut path = make_dir("app-tokens-read", "github_instances");
+    path.push("tokens.toml");
+
+    let _ = File::create(&path).unwrap().write_all(br#"
+        discord = "EaCdWByA0BTPnlt6OYpnuAc0.cfPzzG.7UuaJIiPatD507FYjgBcHw3Ecob"
+        github = "YO4QXSAMh72MivCCOYpRWxNx0ZpEwocF8DM1D130"
+
+        [github_instances]
+        "https://ghe.example.com/api/v3" = "8ffd5a8e6b4ec5e42e27c4cbd8c9c0b3a1e3d3f4"
+    "#);
+
+    let mut instances = BTreeMap::new();
+    instances.insert("https://ghe.example.com/api/v3".to_string(), "8ffd5a8e6b4ec5e42e27c4cbd8c9c0b3a1e3d3f4".to_string());
+    assert_eq!(AppTokens::read(&path),
+               Ok(AppTokens {
+                   discord: "EaCdWByA0BTPnlt6OYpnuAc0.cfPzzG.7UuaJIiPatD507FYjgBcHw3Ecob".to_string(),
+                   github: "YO4QXSAMh72MivCCOYpRWxNx0ZpEwocF8DM1D130".to_string(),
+                   github_instances: Some(instances),
                }));
 }
 
@@ -1,57 +1,86 @@
-use json;
+use json::{self, JsonValue};
 use chrono::DateTime;
-use dishub::ops::{EventPayload, Event};
+use dishub::ops::{IssuesAction, EventPayload, Event, Actor, Repo};
 
 
 static COMMIT_COMMENT: &'static str = include_str!("../../../../test-data/commit_comment_payload.json");
 static CREATE: &'static str = include_str!("../../../../test-data/create_payload.json");
 static DELETE: &'static str = include_str!("../../../../test-data/delete_payload.json");
+static DISCUSSION: &'static str = include_str!("../../../../test-data/discussion_payload.json");
+static DISCUSSION_COMMENT: &'static str = include_str!("../../../../test-data/discussion_comment_payload.json");
 static FORK: &'static str = include_str!("../../../../test-data/fork_payload.json");
+static FORK_APPLY: &'static str = include_str!("../../../../test-data/fork_apply_payload.json");
 static GOLLUM: &'static str = include_str!("../../../../test-data/gollum_payload.json");
 static ISSUE_COMMENT: &'static str = include_str!("../../../../test-data/issue_comment_payload.json");
+static ISSUE_COMMENT_PULL_REQUEST: &'static str = include_str!("../../../../test-data/issue_comment_pull_request_payload.json");
 static ISSUES: &'static str = include_str!("../../../../test-data/issues_payload.json");
 static MEMBER: &'static str = include_str!("../../../../test-data/member_payload.json");
 static PUBLIC: &'static str = include_str!("../../../../test-data/public_payload.json");
 static PULL_REQUEST: &'static str = include_str!("../../../../test-data/pull_request_payload.json");
+static PULL_REQUEST_DRAFT: &'static str = include_str!("../../../../test-data/pull_request_draft_payload.json");
 static PULL_REQUEST_REVIEW: &'static str = include_str!("../../../../test-data/pull_request_review_payload.json");
 static PULL_REQUEST_REVIEW_COMMENT: &'static str = include_str!("../../../../test-data/pull_request_review_comment_payload.json");
+static PULL_REQUEST_REVIEW_THREAD: &'static str = include_str!("../../../../test-data/pull_request_review_thread_payload.json");
 static PUSH: &'static str = include_str!("../../../../test-data/push_payload.json");
+static PUSH_CO_AUTHORED: &'static str = include_str!("../../../../test-data/push_co_authored_payload.json");
 static RELEASE: &'static str = include_str!("../../../../test-data/release_payload.json");
+static SPONSORSHIP: &'static str = include_str!("../../../../test-data/sponsorship_payload.json");
 static WATCH: &'static str = include_str!("../../../../test-data/watch_payload.json");
-static OTHER_FORK_APPLY: &'static str = include_str!("../../../../test-data/other_fork_apply_payload.json");
+static OTHER_DOWNLOAD: &'static str = include_str!("../../../../test-data/other_download_payload.json");
 
 
 #[test]
 fn commit_comment() {
     assert_eq!(&format!("{}",
-                        Event { payload: EventPayload::from("CommitCommentEvent", &json::parse(COMMIT_COMMENT).unwrap()), ..base() }),
+                        Event { payload: EventPayload::from("CommitCommentEvent", &json::parse(COMMIT_COMMENT).unwrap()).unwrap(), ..base() }),
                "10.11.2016 08:42:18 AM: liigo commented on 1ce17f668485fd6741895c3caba1f2ea77ab0e6c in nabijaczleweli/cargo-update");
 }
 
 #[test]
 fn create() {
     assert_eq!(&format!("{}",
-                        Event { payload: EventPayload::from("CreateEvent", &json::parse(CREATE).unwrap()), ..base() }),
+                        Event { payload: EventPayload::from("CreateEvent", &json::parse(CREATE).unwrap()).unwrap(), ..base() }),
                "10.11.2016 08:42:18 AM: liigo created tag v0.5.0");
 }
 
 #[test]
 fn delete() {
     assert_eq!(&format!("{}",
-                        Event { payload: EventPayload::from("DeleteEvent", &json::parse(DELETE).unwrap()), ..base() }),
+                        Event { payload: EventPayload::from("DeleteEvent", &json::parse(DELETE).unwrap()).unwrap(), ..base() }),
                "10.11.2016 08:42:18 AM: liigo deleted tag v0.2.0");
 }
 
+#[test]
+fn discussion() {
+    assert_eq!(&format!("{}",
+                        Event { payload: EventPayload::from("DiscussionEvent", &json::parse(DISCUSSION).unwrap()).unwrap(), ..base() }),
+               "10.11.2016 08:42:18 AM: liigo created discussion #213 on nabijaczleweli/cargo-update: \"Updating packages installed from git\"");
+}
+
+#[test]
+fn discussion_comment() {
+    assert_eq!(&format!("{}",
+                        Event { payload: EventPayload::from("DiscussionCommentEvent", &json::parse(DISCUSSION_COMMENT).unwrap()).unwrap(), ..base() }),
+               "10.11.2016 08:42:18 AM: liigo created comment to discussion #213 on nabijaczleweli/cargo-update");
+}
+
 #[test]
 fn fork() {
-    assert_eq!(&format!("{}", Event { payload: EventPayload::from("ForkEvent", &json::parse(FORK).unwrap()), ..base() }),
+    assert_eq!(&format!("{}", Event { payload: EventPayload::from("ForkEvent", &json::parse(FORK).unwrap()).unwrap(), ..base() }),
                "10.11.2016 08:42:18 AM: liigo forked nabijaczleweli/cargo-update to nabijaczleweli/clap-rs");
 }
 
+#[test]
+fn fork_apply() {
+    assert_eq!(&format!("{}",
+                        Event { payload: EventPayload::from("ForkApplyEvent", &json::parse(FORK_APPLY).unwrap()).unwrap(), ..base() }),
+               "10.11.2016 08:42:18 AM: liigo applied a patch from the fork queue to master in nabijaczleweli/cargo-update");
+}
+
 #[test]
 fn gollum() {
     assert_eq!(&format!("{}",
-                        Event { payload: EventPayload::from("GollumEvent", &json::parse(GOLLUM).unwrap()), ..base() }),
+                        Event { payload: EventPayload::from("GollumEvent", &json::parse(GOLLUM).unwrap()).unwrap(), ..base() }),
                "10.11.2016 08:42:18 AM: liigo changed wiki on nabijaczleweli/cargo-update:\n\
                 \x20\x20edited \"Packages\"\n\
                 \x20\x20edited \"Packages\"");
@@ -60,86 +89,181 @@ fn gollum() {
 #[test]
 fn issue_comment() {
     assert_eq!(&format!("{}",
-                        Event { payload: EventPayload::from("IssueCommentEvent", &json::parse(ISSUE_COMMENT).unwrap()), ..base() }),
+                        Event { payload: EventPayload::from("IssueCommentEvent", &json::parse(ISSUE_COMMENT).unwrap()).unwrap(), ..base() }),
                "10.11.2016 08:42:18 AM: liigo created comment to #12 on nabijaczleweli/cargo-update");
 }
 
+#[test]
+fn issue_comment_pull_request() {
+    assert_eq!(&format!("{}",
+                        Event {
+                            payload: EventPayload::from("IssueCommentEvent", &json::parse(ISSUE_COMMENT_PULL_REQUEST).unwrap()).unwrap(),
+                            ..base()
+                        }),
+               "10.11.2016 08:42:18 AM: liigo created comment to #214 on nabijaczleweli/cargo-update");
+}
+
 #[test]
 fn issues() {
     assert_eq!(&format!("{}",
-                        Event { payload: EventPayload::from("IssuesEvent", &json::parse(ISSUES).unwrap()), ..base() }),
+                        Event { payload: EventPayload::from("IssuesEvent", &json::parse(ISSUES).unwrap()).unwrap(), ..base() }),
                "10.11.2016 08:42:18 AM: liigo opened #11 on nabijaczleweli/cargo-update: \"'unknown error occurred': It\"");
 }
 
+#[test]
+fn issues_unknown_action() {
+    assert_eq!(&format!("{}",
+                        Event {
+                            payload: EventPayload::Issues {
+                                action: IssuesAction::Unknown("transferred".to_string()),
+                                number: 11,
+                                title: "'unknown error occurred': It".to_string(),
+                                body: "".to_string(),
+                                labels: vec![],
+                                assignees: vec![],
+                                milestone: None,
+                            },
+                            ..base()
+                        }),
+               "10.11.2016 08:42:18 AM: liigo transferred #11 on nabijaczleweli/cargo-update: \"'unknown error occurred': It\"");
+}
+
 #[test]
 fn member() {
     assert_eq!(&format!("{}",
-                        Event { payload: EventPayload::from("MemberEvent", &json::parse(MEMBER).unwrap()), ..base() }),
+                        Event { payload: EventPayload::from("MemberEvent", &json::parse(MEMBER).unwrap()).unwrap(), ..base() }),
                "10.11.2016 08:42:18 AM: liigo added Enet4 to nabijaczleweli/cargo-update");
 }
 
 #[test]
 fn public() {
     assert_eq!(&format!("{}",
-                        Event { payload: EventPayload::from("PublicEvent", &json::parse(PUBLIC).unwrap()), ..base() }),
+                        Event { payload: EventPayload::from("PublicEvent", &json::parse(PUBLIC).unwrap()).unwrap(), ..base() }),
                "10.11.2016 08:42:18 AM: liigo made nabijaczleweli/cargo-update public");
 }
 
 #[test]
 fn pull_request() {
     assert_eq!(&format!("{}",
-                        Event { payload: EventPayload::from("PullRequestEvent", &json::parse(PULL_REQUEST).unwrap()), ..base() }),
+                        Event { payload: EventPayload::from("PullRequestEvent", &json::parse(PULL_REQUEST).unwrap()).unwrap(), ..base() }),
                "10.11.2016 08:42:18 AM: liigo merged #138 on nabijaczleweli/cargo-update: \"bumping version.h to 1.7.0\"");
 }
 
+#[test]
+fn pull_request_draft() {
+    assert_eq!(&format!("{}",
+                        Event { payload: EventPayload::from("PullRequestEvent", &json::parse(PULL_REQUEST_DRAFT).unwrap()).unwrap(), ..base() }),
+               "10.11.2016 08:42:18 AM: liigo opened draft #214 from git-packages into master on nabijaczleweli/cargo-update: \"Install git packages with -g\" \
+                (+120/−4)");
+}
+
 #[test]
 fn pull_request_review() {
     assert_eq!(&format!("{}",
-                        Event { payload: EventPayload::from("PullRequestReviewEvent", &json::parse(PULL_REQUEST_REVIEW).unwrap()), ..base() }),
+                        Event { payload: EventPayload::from("PullRequestReviewEvent", &json::parse(PULL_REQUEST_REVIEW).unwrap()).unwrap(), ..base() }),
                "10.11.2016 08:42:18 AM: liigo submitted as approved #210 on nabijaczleweli/cargo-update");
 }
 
 #[test]
 fn pull_request_review_comment() {
     assert_eq!(&format!("{}",
-                        Event { payload: EventPayload::from("PullRequestReviewCommentEvent", &json::parse(PULL_REQUEST_REVIEW_COMMENT).unwrap()), ..base() }),
+                        Event {
+                            payload: EventPayload::from("PullRequestReviewCommentEvent", &json::parse(PULL_REQUEST_REVIEW_COMMENT).unwrap()).unwrap(),
+                            ..base()
+                        }),
                "10.11.2016 08:42:18 AM: liigo created comment to #210 on nabijaczleweli/cargo-update");
 }
 
+#[test]
+fn pull_request_review_thread() {
+    assert_eq!(&format!("{}",
+                        Event {
+                            payload: EventPayload::from("PullRequestReviewThreadEvent", &json::parse(PULL_REQUEST_REVIEW_THREAD).unwrap()).unwrap(),
+                            ..base()
+                        }),
+               "10.11.2016 08:42:18 AM: liigo resolved thread in #210 on nabijaczleweli/cargo-update");
+}
+
 #[test]
 fn push() {
-    assert_eq!(&format!("{}", Event { payload: EventPayload::from("PushEvent", &json::parse(PUSH).unwrap()), ..base() }),
-               "10.11.2016 08:42:18 AM: liigo pushed 1 commit to doc in nabijaczleweli/cargo-update");
+    assert_eq!(&format!("{}", Event { payload: EventPayload::from("PushEvent", &json::parse(PUSH).unwrap()).unwrap(), ..base() }),
+               "10.11.2016 08:42:18 AM: liigo pushed 1 commit by Nabijaczleweli Autouploader Bot to doc in nabijaczleweli/cargo-update");
+}
+
+#[test]
+fn push_co_authored() {
+    assert_eq!(&format!("{}",
+                        Event { payload: EventPayload::from("PushEvent", &json::parse(PUSH_CO_AUTHORED).unwrap()).unwrap(), ..base() }),
+               "10.11.2016 08:42:18 AM: liigo pushed 2 commits by nabijaczleweli, liigo and Stefan Sehe to master in nabijaczleweli/cargo-update");
 }
 
 #[test]
 fn release() {
     assert_eq!(&format!("{}",
-                        Event { payload: EventPayload::from("ReleaseEvent", &json::parse(RELEASE).unwrap()), ..base() }),
+                        Event { payload: EventPayload::from("ReleaseEvent", &json::parse(RELEASE).unwrap()).unwrap(), ..base() }),
                "10.11.2016 08:42:18 AM: liigo published v0.5.0 from master");
 }
 
+#[test]
+fn sponsorship() {
+    assert_eq!(&format!("{}",
+                        Event { payload: EventPayload::from("SponsorshipEvent", &json::parse(SPONSORSHIP).unwrap()).unwrap(), ..base() }),
+               "10.11.2016 08:42:18 AM: liigo created sponsorship of nabijaczleweli by sehe at $5 a month");
+}
+
 #[test]
 fn watch() {
     assert_eq!(&format!("{}",
-                        Event { payload: EventPayload::from("WatchEvent", &json::parse(WATCH).unwrap()), ..base() }),
+                        Event { payload: EventPayload::from("WatchEvent", &json::parse(WATCH).unwrap()).unwrap(), ..base() }),
                "10.11.2016 08:42:18 AM: liigo starred nabijaczleweli/cargo-update");
 }
 
 #[test]
 fn other() {
     assert_eq!(&format!("{}",
-                        Event { payload: EventPayload::from("ForkApplyEvent", &json::parse(OTHER_FORK_APPLY).unwrap()), ..base() }),
-               "10.11.2016 08:42:18 AM: liigo invoked an unsupported event on nabijaczleweli/cargo-update: ForkApplyEvent");
+                        Event {
+                            payload: EventPayload::from("DownloadEvent", &json::parse(OTHER_DOWNLOAD).unwrap()).unwrap(),
+                            raw: raw_with_payload(OTHER_DOWNLOAD),
+                            ..base()
+                        }),
+               "10.11.2016 08:42:18 AM: liigo invoked download on nabijaczleweli/cargo-update: \"dishub-v0.1.0.zip\"");
+}
+
+#[test]
+fn other_action() {
+    let payload = r#"{"action": "checks_requested", "merge_group": {"head_ref": "refs/heads/gh-readonly-queue/master/pr-210"}}"#;
+    assert_eq!(&format!("{}",
+                        Event {
+                            payload: EventPayload::from("MergeGroupEvent", &json::parse(payload).unwrap()).unwrap(),
+                            raw: raw_with_payload(payload),
+                            ..base()
+                        }),
+               "10.11.2016 08:42:18 AM: liigo checks requested merge group on nabijaczleweli/cargo-update");
 }
 
 
-fn base() -> Event {
+pub fn base() -> Event {
     Event {
         created_at: DateTime::parse_from_rfc2822("Thu, 10 Nov 2016 08:42:18 +0000").unwrap(),
-        actor: "liigo".to_string(),
-        repo: "nabijaczleweli/cargo-update".to_string(),
+        actor: Actor {
+            id: 346530,
+            login: "liigo".to_string(),
+            display_login: "liigo".to_string(),
+            avatar_url: "https://avatars.githubusercontent.com/u/346530?".to_string(),
+        },
+        repo: Repo {
+            id: 71928026,
+            name: "nabijaczleweli/cargo-update".to_string(),
+        },
+        org: None,
         id: 4844096927,
         payload: EventPayload::Public,
+        raw: JsonValue::Null,
     }
 }
+
+pub fn raw_with_payload(payload: &str) -> JsonValue {
+    let mut raw = JsonValue::new_object();
+    raw["payload"] = json::parse(payload).unwrap();
+    raw
+}
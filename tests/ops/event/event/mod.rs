@@ -1,39 +1,142 @@
 mod display;
+mod summary_in;
 mod urls;
+mod to_json;
 
+use json;
 use chrono::DateTime;
-use dishub::ops::{EventPayload, Event};
+use dishub::ops::{IssueCommentAction, IssuesAction, EventPayload, WatchAction, Event, Actor, Repo, Org};
+use dishub::Error;
 
 
 static EVENTS: &'static str = include_str!("../../../../test-data/two_events.json");
+static MALFORMED: &'static str = include_str!("../../../../test-data/malformed_events.json");
 
 
 #[test]
 fn parse() {
-    assert_eq!(Event::parse(EVENTS),
+    assert_eq!(Event::parse(EVENTS).unwrap(),
                vec![Event {
                         created_at: DateTime::parse_from_rfc2822("Thu, 10 Nov 2016 00:42:18 +0000").unwrap(),
-                        actor: "liigo".to_string(),
-                        repo: "nabijaczleweli/cargo-update".to_string(),
+                        actor: Actor {
+                            id: 346530,
+                            login: "liigo".to_string(),
+                            display_login: "liigo".to_string(),
+                            avatar_url: "https://avatars.githubusercontent.com/u/346530?".to_string(),
+                        },
+                        repo: Repo {
+                            id: 71928026,
+                            name: "nabijaczleweli/cargo-update".to_string(),
+                        },
+                        org: None,
                         id: 4844096927,
                         payload: EventPayload::Issues {
-                            action: "opened".to_string(),
+                            action: IssuesAction::Opened,
                             number: 11,
                             title: "'unknown error occurred': It".to_string(),
                             body: r"```C:\Users\liigo>cargo install-update```".to_string(),
                             labels: vec![],
+                            assignees: vec![],
+                            milestone: None,
                         },
+                        raw: json::parse(EVENTS).unwrap()[0].clone(),
                     },
                     Event {
                         created_at: DateTime::parse_from_rfc2822("Thu, 10 Nov 2016 10:48:04 +0000").unwrap(),
-                        actor: "nabijaczleweli".to_string(),
-                        repo: "nabijaczleweli/cargo-update".to_string(),
+                        actor: Actor {
+                            id: 6709544,
+                            login: "nabijaczleweli".to_string(),
+                            display_login: "nabijaczleweli".to_string(),
+                            avatar_url: "https://avatars.githubusercontent.com/u/6709544?".to_string(),
+                        },
+                        repo: Repo {
+                            id: 71928026,
+                            name: "nabijaczleweli/cargo-update".to_string(),
+                        },
+                        org: None,
                         id: 4846163121,
                         payload: EventPayload::IssueComment {
-                            action: "created".to_string(),
+                            action: IssueCommentAction::Created,
                             issue: 12,
+                            pull_request: false,
                             body: "Your `.cargo.toml`?".to_string(),
                             id: 259659314,
                         },
+                        raw: json::parse(EVENTS).unwrap()[1].clone(),
+                    }]);
+}
+
+#[test]
+fn parse_malformed() {
+    assert_eq!(Event::parse(MALFORMED),
+               Err(Error::EventParsingFailed {
+                   id: "4844096927".to_string(),
+                   tp: "IssuesEvent".to_string(),
+                   path: "payload.issue.number".to_string(),
+               }));
+}
+
+#[test]
+fn parse_not_json() {
+    assert_eq!(Event::parse("{}"),
+               Err(Error::FileParsingFailed {
+                   desc: "GitHub events",
+                   errors: vec!["the response is not an array".to_string()],
+               }));
+}
+
+#[test]
+fn parse_lenient() {
+    let (events, errors) = Event::parse_lenient(MALFORMED).unwrap();
+
+    assert_eq!(events,
+               vec![Event {
+                        created_at: DateTime::parse_from_rfc2822("Thu, 10 Nov 2016 00:42:18 +0000").unwrap(),
+                        actor: Actor {
+                            id: 346530,
+                            login: "liigo".to_string(),
+                            display_login: "liigo".to_string(),
+                            avatar_url: "https://avatars.githubusercontent.com/u/346530?".to_string(),
+                        },
+                        repo: Repo {
+                            id: 71928026,
+                            name: "nabijaczleweli/cargo-update".to_string(),
+                        },
+                        org: None,
+                        id: 4844096927,
+                        payload: EventPayload::Other { event_type: "IssuesEvent".to_string() },
+                        raw: json::parse(MALFORMED).unwrap()[0].clone(),
+                    },
+                    Event {
+                        created_at: DateTime::parse_from_rfc2822("Thu, 10 Nov 2016 10:49:04 +0000").unwrap(),
+                        actor: Actor {
+                            id: 85255,
+                            login: "sehe".to_string(),
+                            display_login: "sehe".to_string(),
+                            avatar_url: "https://avatars.githubusercontent.com/u/85255?".to_string(),
+                        },
+                        repo: Repo {
+                            id: 71928026,
+                            name: "nabijaczleweli/cargo-update".to_string(),
+                        },
+                        org: Some(Org {
+                            id: 24362521,
+                            login: "rust-cargo-tools".to_string(),
+                            avatar_url: "https://avatars.githubusercontent.com/u/24362521?".to_string(),
+                        }),
+                        id: 4846163209,
+                        payload: EventPayload::Watch { action: WatchAction::Started },
+                        raw: json::parse(MALFORMED).unwrap()[2].clone(),
+                    }]);
+    assert_eq!(errors,
+               vec![Error::EventParsingFailed {
+                        id: "4844096927".to_string(),
+                        tp: "IssuesEvent".to_string(),
+                        path: "payload.issue.number".to_string(),
+                    },
+                    Error::EventParsingFailed {
+                        id: "4846163121".to_string(),
+                        tp: "IssueCommentEvent".to_string(),
+                        path: "created_at".to_string(),
                     }]);
 }
@@ -0,0 +1,127 @@
+use dishub::ops::{IssuesAction, EventPayload, Event};
+use dishub::ops::locale::Catalogue;
+use self::super::display::{base, raw_with_payload};
+use json;
+
+
+static CREATE: &'static str = include_str!("../../../../test-data/create_payload.json");
+static GOLLUM: &'static str = include_str!("../../../../test-data/gollum_payload.json");
+static ISSUES: &'static str = include_str!("../../../../test-data/issues_payload.json");
+static MEMBER: &'static str = include_str!("../../../../test-data/member_payload.json");
+static PULL_REQUEST: &'static str = include_str!("../../../../test-data/pull_request_payload.json");
+static PULL_REQUEST_DRAFT: &'static str = include_str!("../../../../test-data/pull_request_draft_payload.json");
+static PUSH: &'static str = include_str!("../../../../test-data/push_payload.json");
+static PUSH_CO_AUTHORED: &'static str = include_str!("../../../../test-data/push_co_authored_payload.json");
+static RELEASE: &'static str = include_str!("../../../../test-data/release_payload.json");
+
+
+#[test]
+fn english() {
+    for &(tp, payload) in &[("CreateEvent", CREATE), ("GollumEvent", GOLLUM), ("PushEvent", PUSH_CO_AUTHORED), ("ReleaseEvent", RELEASE)] {
+        let ev = Event { payload: EventPayload::from(tp, &json::parse(payload).unwrap()).unwrap(), ..base() };
+        assert_eq!(ev.summary_in(Catalogue::english()), ev.summary());
+    }
+}
+
+#[test]
+fn create() {
+    assert_eq!(Event { payload: EventPayload::from("CreateEvent", &json::parse(CREATE).unwrap()).unwrap(), ..base() }.summary_in(&polish()),
+               "liigo utworzył(a) tag v0.5.0");
+}
+
+#[test]
+fn gollum() {
+    assert_eq!(Event { payload: EventPayload::from("GollumEvent", &json::parse(GOLLUM).unwrap()).unwrap(), ..base() }.summary_in(&polish()),
+               "liigo zmienił(a) wiki w nabijaczleweli/cargo-update:\n\
+                \x20\x20zmieniono „Packages”\n\
+                \x20\x20zmieniono „Packages”");
+}
+
+#[test]
+fn issues() {
+    assert_eq!(Event { payload: EventPayload::from("IssuesEvent", &json::parse(ISSUES).unwrap()).unwrap(), ..base() }.summary_in(&polish()),
+               "liigo otworzył(a) #11 w nabijaczleweli/cargo-update: „'unknown error occurred': It”");
+}
+
+#[test]
+fn issues_unknown_action() {
+    assert_eq!(Event {
+                       payload: EventPayload::Issues {
+                           action: IssuesAction::Unknown("frobnicated".to_string()),
+                           number: 11,
+                           title: "'unknown error occurred': It".to_string(),
+                           body: "".to_string(),
+                           labels: vec![],
+                           assignees: vec![],
+                           milestone: None,
+                       },
+                       ..base()
+                   }
+                   .summary_in(&polish()),
+               "liigo frobnicated #11 w nabijaczleweli/cargo-update: „'unknown error occurred': It”");
+}
+
+#[test]
+fn member() {
+    assert_eq!(Event { payload: EventPayload::from("MemberEvent", &json::parse(MEMBER).unwrap()).unwrap(), ..base() }.summary_in(&polish()),
+               "liigo dodał(a) Enet4 do nabijaczleweli/cargo-update");
+}
+
+#[test]
+fn pull_request() {
+    assert_eq!(Event { payload: EventPayload::from("PullRequestEvent", &json::parse(PULL_REQUEST).unwrap()).unwrap(), ..base() }.summary_in(&polish()),
+               "liigo scalił(a) #138 w nabijaczleweli/cargo-update: „bumping version.h to 1.7.0”");
+}
+
+#[test]
+fn pull_request_draft() {
+    assert_eq!(Event { payload: EventPayload::from("PullRequestEvent", &json::parse(PULL_REQUEST_DRAFT).unwrap()).unwrap(), ..base() }
+                   .summary_in(&polish()),
+               "liigo otworzył(a) szkic #214 z git-packages do master w nabijaczleweli/cargo-update: „Install git packages with -g” (+120/−4)");
+}
+
+#[test]
+fn pull_request_unknown_stats() {
+    let mut ev = Event { payload: EventPayload::from("PullRequestEvent", &json::parse(PULL_REQUEST_DRAFT).unwrap()).unwrap(), ..base() };
+    if let EventPayload::PullRequest { ref mut additions, ref mut deletions, .. } = ev.payload {
+        *additions = None;
+        *deletions = None;
+    }
+    assert_eq!(ev.summary_in(&polish()),
+               "liigo otworzył(a) szkic #214 z git-packages do master w nabijaczleweli/cargo-update: „Install git packages with -g”");
+}
+
+#[test]
+fn push() {
+    assert_eq!(Event { payload: EventPayload::from("PushEvent", &json::parse(PUSH).unwrap()).unwrap(), ..base() }.summary_in(&polish()),
+               "liigo wypchnął(-ęła) 1 commit autorstwa Nabijaczleweli Autouploader Bot do doc w nabijaczleweli/cargo-update");
+}
+
+#[test]
+fn push_co_authored() {
+    assert_eq!(Event { payload: EventPayload::from("PushEvent", &json::parse(PUSH_CO_AUTHORED).unwrap()).unwrap(), ..base() }.summary_in(&polish()),
+               "liigo wypchnął(-ęła) 2 commity autorstwa nabijaczleweli, liigo i Stefan Sehe do master w nabijaczleweli/cargo-update");
+}
+
+#[test]
+fn release() {
+    assert_eq!(Event { payload: EventPayload::from("ReleaseEvent", &json::parse(RELEASE).unwrap()).unwrap(), ..base() }.summary_in(&polish()),
+               "liigo opublikował(a) v0.5.0 z master");
+}
+
+#[test]
+fn other_action() {
+    let payload = r#"{"action": "checks_requested", "merge_group": {"head_ref": "refs/heads/gh-readonly-queue/master/pr-210"}}"#;
+    assert_eq!(Event {
+                       payload: EventPayload::from("MergeGroupEvent", &json::parse(payload).unwrap()).unwrap(),
+                       raw: raw_with_payload(payload),
+                       ..base()
+                   }
+                   .summary_in(&polish()),
+               "liigo checks requested merge group w nabijaczleweli/cargo-update");
+}
+
+
+fn polish() -> Catalogue {
+    Catalogue::builtin("pl").unwrap()
+}
@@ -0,0 +1,111 @@
+use json::{self, JsonValue};
+use chrono::DateTime;
+use dishub::ops::{EventPayload, WatchAction, Event, Actor, Repo, Org};
+use dishub::Error;
+
+
+static EVENTS: &'static str = include_str!("../../../../test-data/two_events.json");
+static MALFORMED: &'static str = include_str!("../../../../test-data/malformed_events.json");
+
+
+#[test]
+fn two_events() {
+    for event in Event::parse(EVENTS).unwrap() {
+        round_trip(event);
+    }
+}
+
+#[test]
+fn malformed_events() {
+    for event in Event::parse_lenient(MALFORMED).unwrap().0 {
+        round_trip(event);
+    }
+}
+
+#[test]
+fn not_from_github() {
+    round_trip(Event {
+        created_at: DateTime::parse_from_rfc2822("Thu, 10 Nov 2016 08:42:18 +0100").unwrap(),
+        actor: Actor {
+            id: 85255,
+            login: "sehe".to_string(),
+            display_login: "sehe".to_string(),
+            avatar_url: "https://avatars.githubusercontent.com/u/85255?".to_string(),
+        },
+        repo: Repo {
+            id: 71928026,
+            name: "nabijaczleweli/cargo-update".to_string(),
+        },
+        org: Some(Org {
+            id: 24362521,
+            login: "rust-cargo-tools".to_string(),
+            avatar_url: "https://avatars.githubusercontent.com/u/24362521?".to_string(),
+        }),
+        id: 4846163209,
+        payload: EventPayload::Watch { action: WatchAction::Started },
+        raw: JsonValue::Null,
+    });
+}
+
+#[test]
+fn schema() {
+    let event = Event::parse(EVENTS).unwrap().remove(1).to_json();
+
+    assert_eq!(event["id"], 4846163121u64);
+    assert_eq!(event["created_at"], "2016-11-10T10:48:04+00:00");
+    assert_eq!(event["actor"],
+               object!{
+                   "id" => 6709544,
+                   "login" => "nabijaczleweli",
+                   "display_login" => "nabijaczleweli",
+                   "avatar_url" => "https://avatars.githubusercontent.com/u/6709544?",
+               });
+    assert_eq!(event["repo"],
+               object!{
+                   "id" => 71928026,
+                   "name" => "nabijaczleweli/cargo-update",
+               });
+    assert!(event["org"].is_null());
+    assert_eq!(event["payload"],
+               object!{
+                   "type" => "IssueComment",
+                   "action" => "created",
+                   "issue" => 12,
+                   "pull_request" => false,
+                   "body" => "Your `.cargo.toml`?",
+                   "id" => 259659314,
+               });
+    assert_eq!(event["raw"], json::parse(EVENTS).unwrap()[1]);
+}
+
+#[test]
+fn malformed() {
+    let mut event = Event::parse(EVENTS).unwrap()[0].to_json();
+    event["actor"]["login"] = JsonValue::Null;
+
+    assert_eq!(Event::from_json(&event),
+               Err(Error::EventParsingFailed {
+                   id: "4844096927".to_string(),
+                   tp: "Issues".to_string(),
+                   path: "actor.login".to_string(),
+               }));
+}
+
+#[test]
+fn malformed_payload() {
+    let mut event = Event::parse(EVENTS).unwrap()[0].to_json();
+    event["payload"]["number"] = "11".into();
+
+    assert_eq!(Event::from_json(&event),
+               Err(Error::EventParsingFailed {
+                   id: "4844096927".to_string(),
+                   tp: "Issues".to_string(),
+                   path: "payload.number".to_string(),
+               }));
+}
+
+
+fn round_trip(event: Event) {
+    assert_eq!(Event::from_json(&event.to_json()).as_ref(), Ok(&event));
+    assert_eq!(Event::from_json(&json::parse(&event.to_json().dump()).unwrap()), Ok(event));
+}
@@ -1,5 +1,7 @@
+use json::{self, JsonValue};
 use chrono::DateTime;
-use dishub::ops::{GollumPayload, EventPayload, Event};
+use dishub::ops::{IssueCommentAction, PullRequestAction, GollumPayload, ReleaseAction, IssuesAction, EventPayload, MemberAction, WatchAction, Event, Actor,
+                  Repo};
 
 
 #[test]
@@ -59,12 +61,58 @@ fn delete() {
         .is_empty());
 }
 
+#[test]
+fn discussion() {
+    assert_eq!(Event {
+                       payload: EventPayload::Discussion {
+                           action: "created".to_string(),
+                           number: 213,
+                           title: "Updating packages installed from git".to_string(),
+                           body: "".to_string(),
+                           category: "Q&A".to_string(),
+                       },
+                       ..base()
+                   }
+                   .urls(),
+               vec!["https://github.com/nabijaczleweli/cargo-update/discussions/213".to_string()]);
+}
+
+#[test]
+fn discussion_comment() {
+    assert_eq!(Event {
+                       payload: EventPayload::DiscussionComment {
+                           action: "created".to_string(),
+                           discussion: 213,
+                           body: "Pass `-g`.".to_string(),
+                           id: 5117036,
+                       },
+                       ..base()
+                   }
+                   .urls(),
+               vec!["https://github.com/nabijaczleweli/cargo-update/discussions/213#discussioncomment-5117036".to_string()]);
+}
+
 #[test]
 fn fork() {
     assert_eq!(Event { payload: EventPayload::Fork { new_slug: "liigo/cargo-update".to_string() }, ..base() }.urls(),
                vec!["https://github.com/liigo/cargo-update".to_string()]);
 }
 
+#[test]
+fn fork_apply() {
+    assert_eq!(Event {
+                       payload: EventPayload::ForkApply {
+                           head: "master".to_string(),
+                           before: "62476f13306db1cfade222d41bcdcb51".to_string(),
+                           after: "ce64d74910128530fad48dbd2bb4f836a".to_string(),
+                       },
+                       ..base()
+                   }
+                   .urls(),
+               vec!["https://github.com/nabijaczleweli/cargo-update/compare/62476f13306db1cfade222d41bcdcb51...ce64d74910128530fad48dbd2bb4f836a"
+                        .to_string()]);
+}
+
 #[test]
 fn gollum() {
     assert_eq!(Event {
@@ -94,8 +142,9 @@ fn gollum() {
 fn issue_comment() {
     assert_eq!(Event {
                        payload: EventPayload::IssueComment {
-                           action: "created".to_string(),
+                           action: IssueCommentAction::Created,
                            issue: 1,
+                           pull_request: false,
                            body: "plz gib code".to_string(),
                            id: 4321,
                        },
@@ -105,15 +154,33 @@ fn issue_comment() {
                vec!["https://github.com/nabijaczleweli/cargo-update/issues/1#issuecomment-4321".to_string()]);
 }
 
+#[test]
+fn issue_comment_pull_request() {
+    assert_eq!(Event {
+                       payload: EventPayload::IssueComment {
+                           action: IssueCommentAction::Created,
+                           issue: 3,
+                           pull_request: true,
+                           body: "plz merge".to_string(),
+                           id: 4322,
+                       },
+                       ..base()
+                   }
+                   .urls(),
+               vec!["https://github.com/nabijaczleweli/cargo-update/pull/3#issuecomment-4322".to_string()]);
+}
+
 #[test]
 fn issues() {
     assert_eq!(Event {
                        payload: EventPayload::Issues {
-                           action: "assigned".to_string(),
+                           action: IssuesAction::Assigned,
                            number: 2,
                            title: "This is code".to_string(),
                            body: "Closes #1".to_string(),
                            labels: vec!["invalid".to_string(), "question".to_string()],
+                           assignees: vec!["nabijaczleweli".to_string()],
+                           milestone: None,
                        },
                        ..base()
                    }
@@ -125,7 +192,7 @@ fn issues() {
 fn member() {
     assert!(Event {
             payload: EventPayload::Member {
-                action: "added".to_string(),
+                action: MemberAction::Added,
                 user: "sehe".to_string(),
             },
             ..base()
@@ -144,11 +211,21 @@ fn public() {
 fn pull_request() {
     assert_eq!(Event {
                        payload: EventPayload::PullRequest {
-                           action: "assigned".to_string(),
+                           action: PullRequestAction::Assigned,
                            number: 3,
                            title: "Remove bad numbar".to_string(),
                            body: "Closes #2".to_string(),
                            merged: false,
+                           merged_by: None,
+                           draft: false,
+                           head: "numbar".to_string(),
+                           base: "master".to_string(),
+                           additions: Some(1),
+                           deletions: Some(2),
+                           changed_files: Some(1),
+                           labels: vec![],
+                           assignees: vec![],
+                           milestone: None,
                        },
                        ..base()
                    }
@@ -187,6 +264,34 @@ fn pull_request_review_comment() {
                vec!["https://github.com/nabijaczleweli/cargo-update/pull/3#discussion_r4312".to_string()]);
 }
 
+#[test]
+fn pull_request_review_thread() {
+    assert_eq!(Event {
+                       payload: EventPayload::PullRequestReviewThread {
+                           action: "resolved".to_string(),
+                           pr: 210,
+                           first_comment: Some(63127893),
+                       },
+                       ..base()
+                   }
+                   .urls(),
+               vec!["https://github.com/nabijaczleweli/cargo-update/pull/210#discussion_r63127893".to_string()]);
+}
+
+#[test]
+fn pull_request_review_thread_empty() {
+    assert_eq!(Event {
+                       payload: EventPayload::PullRequestReviewThread {
+                           action: "resolved".to_string(),
+                           pr: 210,
+                           first_comment: None,
+                       },
+                       ..base()
+                   }
+                   .urls(),
+               vec!["https://github.com/nabijaczleweli/cargo-update/pull/210".to_string()]);
+}
+
 #[test]
 fn push() {
     assert_eq!(Event {
@@ -208,7 +313,7 @@ fn push() {
 fn release() {
     assert_eq!(Event {
                        payload: EventPayload::Release {
-                           action: "published".to_string(),
+                           action: ReleaseAction::Published,
                            tag_name: "v0.1.0".to_string(),
                            target: "master".to_string(),
                            draft: false,
@@ -222,24 +327,68 @@ fn release() {
                vec!["https://github.com/nabijaczleweli/cargo-update/releases/tag/v0.1.0".to_string()]);
 }
 
+#[test]
+fn sponsorship() {
+    assert_eq!(Event {
+                       payload: EventPayload::Sponsorship {
+                           action: "created".to_string(),
+                           sponsor: "sehe".to_string(),
+                           sponsorable: "nabijaczleweli".to_string(),
+                           tier: None,
+                       },
+                       ..base()
+                   }
+                   .urls(),
+               vec!["https://github.com/sponsors/nabijaczleweli".to_string()]);
+}
+
 #[test]
 fn watch() {
-    assert_eq!(Event { payload: EventPayload::Watch { action: "started".to_string() }, ..base() }.urls(),
+    assert_eq!(Event { payload: EventPayload::Watch { action: WatchAction::Started }, ..base() }.urls(),
                vec!["https://github.com/nabijaczleweli/cargo-update/stargazers".to_string()]);
 }
 
 #[test]
 fn other() {
-    assert!(Event { payload: EventPayload::Other { event_type: "ForkApplyEvent".to_string() }, ..base() }.urls().is_empty());
+    assert!(Event { payload: EventPayload::Other { event_type: "DownloadEvent".to_string() }, ..base() }.urls().is_empty());
+}
+
+#[test]
+fn other_html_url() {
+    assert_eq!(Event {
+                       payload: EventPayload::Other { event_type: "MergeQueueEntryEvent".to_string() },
+                       raw: json::parse(r#"{"payload": {"action": "created",
+                                                          "merge_group": {"html_url": "https://github.com/nabijaczleweli/cargo-update/queue/master"}}}"#)
+                           .unwrap(),
+                       ..base()
+                   }
+                   .urls(),
+               vec!["https://github.com/nabijaczleweli/cargo-update/queue/master".to_string()]);
+}
+
+#[test]
+fn enterprise() {
+    assert_eq!(Event { payload: EventPayload::Watch { action: WatchAction::Started }, ..base() }.urls_at("https://ghe.example.com"),
+               vec!["https://ghe.example.com/nabijaczleweli/cargo-update/stargazers".to_string()]);
 }
 
 
 fn base() -> Event {
     Event {
         created_at: DateTime::parse_from_rfc2822("Thu, 10 Nov 2016 00:42:18 +0000").unwrap(),
-        actor: "liigo".to_string(),
-        repo: "nabijaczleweli/cargo-update".to_string(),
+        actor: Actor {
+            id: 346530,
+            login: "liigo".to_string(),
+            display_login: "liigo".to_string(),
+            avatar_url: "https://avatars.githubusercontent.com/u/346530?".to_string(),
+        },
+        repo: Repo {
+            id: 71928026,
+            name: "nabijaczleweli/cargo-update".to_string(),
+        },
+        org: None,
         id: 4844096927,
         payload: EventPayload::Public,
+        raw: JsonValue::Null,
     }
 }
@@ -1 +1,2 @@
 mod parse;
+mod to_json;
@@ -1,28 +1,38 @@
 use json;
-use dishub::ops::{GollumPayload, EventPayload, Commit};
+use dishub::Error;
+use dishub::ops::{IssueCommentAction, PullRequestAction, ContributorKind, GollumPayload, ReleaseAction, IssuesAction, EventPayload, MemberAction,
+                  WatchAction, Contributor, Commit};
 
 
 static COMMIT_COMMENT: &'static str = include_str!("../../../../test-data/commit_comment_payload.json");
 static CREATE: &'static str = include_str!("../../../../test-data/create_payload.json");
 static DELETE: &'static str = include_str!("../../../../test-data/delete_payload.json");
+static DISCUSSION: &'static str = include_str!("../../../../test-data/discussion_payload.json");
+static DISCUSSION_COMMENT: &'static str = include_str!("../../../../test-data/discussion_comment_payload.json");
 static FORK: &'static str = include_str!("../../../../test-data/fork_payload.json");
+static FORK_APPLY: &'static str = include_str!("../../../../test-data/fork_apply_payload.json");
 static GOLLUM: &'static str = include_str!("../../../../test-data/gollum_payload.json");
 static ISSUE_COMMENT: &'static str = include_str!("../../../../test-data/issue_comment_payload.json");
+static ISSUE_COMMENT_PULL_REQUEST: &'static str = include_str!("../../../../test-data/issue_comment_pull_request_payload.json");
 static ISSUES: &'static str = include_str!("../../../../test-data/issues_payload.json");
 static MEMBER: &'static str = include_str!("../../../../test-data/member_payload.json");
 static PUBLIC: &'static str = include_str!("../../../../test-data/public_payload.json");
 static PULL_REQUEST: &'static str = include_str!("../../../../test-data/pull_request_payload.json");
+static PULL_REQUEST_DRAFT: &'static str = include_str!("../../../../test-data/pull_request_draft_payload.json");
 static PULL_REQUEST_REVIEW: &'static str = include_str!("../../../../test-data/pull_request_review_payload.json");
 static PULL_REQUEST_REVIEW_COMMENT: &'static str = include_str!("../../../../test-data/pull_request_review_comment_payload.json");
+static PULL_REQUEST_REVIEW_THREAD: &'static str = include_str!("../../../../test-data/pull_request_review_thread_payload.json");
 static PUSH: &'static str = include_str!("../../../../test-data/push_payload.json");
+static PUSH_CO_AUTHORED: &'static str = include_str!("../../../../test-data/push_co_authored_payload.json");
 static RELEASE: &'static str = include_str!("../../../../test-data/release_payload.json");
+static SPONSORSHIP: &'static str = include_str!("../../../../test-data/sponsorship_payload.json");
 static WATCH: &'static str = include_str!("../../../../test-data/watch_payload.json");
-static OTHER_FORK_APPLY: &'static str = include_str!("../../../../test-data/other_fork_apply_payload.json");
+static OTHER_DOWNLOAD: &'static str = include_str!("../../../../test-data/other_download_payload.json");
 
 
 #[test]
 fn commit_comment() {
-    assert_eq!(EventPayload::from("CommitCommentEvent", &json::parse(COMMIT_COMMENT).unwrap()),
+    assert_eq!(EventPayload::from("CommitCommentEvent", &json::parse(COMMIT_COMMENT).unwrap()).unwrap(),
                EventPayload::CommitComment {
                    content: "> maybe on your shit thing\r\n".to_string(),
                    commit_id: "1ce17f668485fd6741895c3caba1f2ea77ab0e6c".to_string(),
@@ -32,7 +42,7 @@ fn commit_comment() {
 
 #[test]
 fn create() {
-    assert_eq!(EventPayload::from("CreateEvent", &json::parse(CREATE).unwrap()),
+    assert_eq!(EventPayload::from("CreateEvent", &json::parse(CREATE).unwrap()).unwrap(),
                EventPayload::Create {
                    ref_type: "tag".to_string(),
                    ref_name: Some("v0.5.0".to_string()),
@@ -41,24 +51,70 @@ fn create() {
                });
 }
 
+#[test]
+fn create_null_description() {
+    assert_eq!(EventPayload::from("CreateEvent",
+                                  &json::parse(r#"{"ref": null, "ref_type": "repository", "master_branch": "master", "description": null}"#).unwrap())
+                   .unwrap(),
+               EventPayload::Create {
+                   ref_type: "repository".to_string(),
+                   ref_name: None,
+                   master_branch: "master".to_string(),
+                   repo_description: "".to_string(),
+               });
+}
+
 #[test]
 fn delete() {
-    assert_eq!(EventPayload::from("DeleteEvent", &json::parse(DELETE).unwrap()),
+    assert_eq!(EventPayload::from("DeleteEvent", &json::parse(DELETE).unwrap()).unwrap(),
                EventPayload::Delete {
                    ref_type: "tag".to_string(),
                    ref_name: "v0.2.0".to_string(),
                });
 }
 
+#[test]
+fn discussion() {
+    assert_eq!(EventPayload::from("DiscussionEvent", &json::parse(DISCUSSION).unwrap()).unwrap(),
+               EventPayload::Discussion {
+                   action: "created".to_string(),
+                   number: 213,
+                   title: "Updating packages installed from git".to_string(),
+                   body: "Is there a way to make `cargo install-update` pick up git packages?".to_string(),
+                   category: "Q&A".to_string(),
+               });
+}
+
+#[test]
+fn discussion_comment() {
+    assert_eq!(EventPayload::from("DiscussionCommentEvent", &json::parse(DISCUSSION_COMMENT).unwrap()).unwrap(),
+               EventPayload::DiscussionComment {
+                   action: "created".to_string(),
+                   discussion: 213,
+                   body: "Pass `-g`.".to_string(),
+                   id: 5117036,
+               });
+}
+
 #[test]
 fn fork() {
-    assert_eq!(EventPayload::from("ForkEvent", &json::parse(FORK).unwrap()),
+    assert_eq!(EventPayload::from("ForkEvent", &json::parse(FORK).unwrap()).unwrap(),
                EventPayload::Fork { new_slug: "nabijaczleweli/clap-rs".to_string() });
 }
 
+#[test]
+fn fork_apply() {
+    assert_eq!(EventPayload::from("ForkApplyEvent", &json::parse(FORK_APPLY).unwrap()).unwrap(),
+               EventPayload::ForkApply {
+                   head: "master".to_string(),
+                   before: "62476f13306db1cfade222d41bcdcb51".to_string(),
+                   after: "ce64d74910128530fad48dbd2bb4f836a".to_string(),
+               });
+}
+
 #[test]
 fn gollum() {
-    assert_eq!(EventPayload::from("GollumEvent", &json::parse(GOLLUM).unwrap()),
+    assert_eq!(EventPayload::from("GollumEvent", &json::parse(GOLLUM).unwrap()).unwrap(),
                EventPayload::Gollum {
                    pages: vec![GollumPayload {
                                    page_name: "Packages".to_string(),
@@ -79,56 +135,142 @@ fn gollum() {
 
 #[test]
 fn issue_comment() {
-    assert_eq!(EventPayload::from("IssueCommentEvent", &json::parse(ISSUE_COMMENT).unwrap()),
+    assert_eq!(EventPayload::from("IssueCommentEvent", &json::parse(ISSUE_COMMENT).unwrap()).unwrap(),
                EventPayload::IssueComment {
-                   action: "created".to_string(),
+                   action: IssueCommentAction::Created,
                    issue: 12,
+                   pull_request: false,
                    body: "It is possible to install the same package twice".to_string(),
                    id: 259662246,
                });
 }
 
+#[test]
+fn issue_comment_pull_request() {
+    assert_eq!(EventPayload::from("IssueCommentEvent", &json::parse(ISSUE_COMMENT_PULL_REQUEST).unwrap()).unwrap(),
+               EventPayload::IssueComment {
+                   action: IssueCommentAction::Created,
+                   issue: 214,
+                   pull_request: true,
+                   body: "Mark it ready when the tests pass.".to_string(),
+                   id: 1366856129,
+               });
+}
+
 #[test]
 fn issues() {
-    assert_eq!(EventPayload::from("IssuesEvent", &json::parse(ISSUES).unwrap()),
+    assert_eq!(EventPayload::from("IssuesEvent", &json::parse(ISSUES).unwrap()).unwrap(),
                EventPayload::Issues {
-                   action: "opened".to_string(),
+                   action: IssuesAction::Opened,
                    number: 11,
                    title: "'unknown error occurred': It".to_string(),
                    body: r"```C:\Users\liigo>cargo install-update```".to_string(),
                    labels: vec!["bug".to_string(), "question".to_string()],
+                   assignees: vec![],
+                   milestone: None,
+               });
+}
+
+#[test]
+fn issues_unknown_action() {
+    let payload = r#"{"action": "transferred", "issue": {"number": 11, "title": "Title", "body": null, "labels": [], "assignees": []}}"#;
+    assert_eq!(EventPayload::from("IssuesEvent", &json::parse(payload).unwrap()).unwrap(),
+               EventPayload::Issues {
+                   action: IssuesAction::Unknown("transferred".to_string()),
+                   number: 11,
+                   title: "Title".to_string(),
+                   body: "".to_string(),
+                   labels: vec![],
+                   assignees: vec![],
+                   milestone: None,
                });
 }
 
 #[test]
 fn member() {
-    assert_eq!(EventPayload::from("MemberEvent", &json::parse(MEMBER).unwrap()),
+    assert_eq!(EventPayload::from("MemberEvent", &json::parse(MEMBER).unwrap()).unwrap(),
                EventPayload::Member {
-                   action: "added".to_string(),
+                   action: MemberAction::Added,
                    user: "Enet4".to_string(),
                });
 }
 
 #[test]
 fn public() {
-    assert_eq!(EventPayload::from("PublicEvent", &json::parse(PUBLIC).unwrap()), EventPayload::Public);
+    assert_eq!(EventPayload::from("PublicEvent", &json::parse(PUBLIC).unwrap()).unwrap(), EventPayload::Public);
 }
 
 #[test]
 fn pull_request() {
-    assert_eq!(EventPayload::from("PullRequestEvent", &json::parse(PULL_REQUEST).unwrap()),
+    assert_eq!(EventPayload::from("PullRequestEvent", &json::parse(PULL_REQUEST).unwrap()).unwrap(),
                EventPayload::PullRequest {
-                   action: "closed".to_string(),
+                   action: PullRequestAction::Closed,
                    number: 138,
                    title: "bumping version.h to 1.7.0".to_string(),
                    body: "".to_string(),
                    merged: true,
+                   merged_by: Some("stephenmathieson".to_string()),
+                   draft: false,
+                   head: "master".to_string(),
+                   base: "master".to_string(),
+                   additions: Some(1),
+                   deletions: Some(1),
+                   changed_files: Some(1),
+                   labels: vec![],
+                   assignees: vec![],
+                   milestone: None,
+               });
+}
+
+#[test]
+fn pull_request_minimal() {
+    let payload = r#"{"action": "opened", "number": 12, "pull_request": {"title": "Title", "body": null, "merged": false,
+                                                                        "head": {"ref": "feature"}, "base": {"ref": "master"}}}"#;
+    assert_eq!(EventPayload::from("PullRequestEvent", &json::parse(payload).unwrap()).unwrap(),
+               EventPayload::PullRequest {
+                   action: PullRequestAction::Opened,
+                   number: 12,
+                   title: "Title".to_string(),
+                   body: "".to_string(),
+                   merged: false,
+                   merged_by: None,
+                   draft: false,
+                   head: "feature".to_string(),
+                   base: "master".to_string(),
+                   additions: None,
+                   deletions: None,
+                   changed_files: None,
+                   labels: vec![],
+                   assignees: vec![],
+                   milestone: None,
+               });
+}
+
+#[test]
+fn pull_request_draft() {
+    assert_eq!(EventPayload::from("PullRequestEvent", &json::parse(PULL_REQUEST_DRAFT).unwrap()).unwrap(),
+               EventPayload::PullRequest {
+                   action: PullRequestAction::Opened,
+                   number: 214,
+                   title: "Install git packages with -g".to_string(),
+                   body: "Closes #213.".to_string(),
+                   merged: false,
+                   merged_by: None,
+                   draft: true,
+                   head: "git-packages".to_string(),
+                   base: "master".to_string(),
+                   additions: Some(120),
+                   deletions: Some(4),
+                   changed_files: Some(5),
+                   labels: vec!["enhancement".to_string()],
+                   assignees: vec!["nabijaczleweli".to_string()],
+                   milestone: Some("v11.0.0".to_string()),
                });
 }
 
 #[test]
 fn pull_request_review() {
-    assert_eq!(EventPayload::from("PullRequestReviewEvent", &json::parse(PULL_REQUEST_REVIEW).unwrap()),
+    assert_eq!(EventPayload::from("PullRequestReviewEvent", &json::parse(PULL_REQUEST_REVIEW).unwrap()).unwrap(),
                EventPayload::PullRequestReview {
                    action: "submitted".to_string(),
                    pr: 210,
@@ -140,7 +282,7 @@ fn pull_request_review() {
 
 #[test]
 fn pull_request_review_comment() {
-    assert_eq!(EventPayload::from("PullRequestReviewCommentEvent", &json::parse(PULL_REQUEST_REVIEW_COMMENT).unwrap()),
+    assert_eq!(EventPayload::from("PullRequestReviewCommentEvent", &json::parse(PULL_REQUEST_REVIEW_COMMENT).unwrap()).unwrap(),
                EventPayload::PullRequestReviewComment {
                    action: "created".to_string(),
                    pr: 210,
@@ -149,9 +291,28 @@ fn pull_request_review_comment() {
                });
 }
 
+#[test]
+fn pull_request_review_thread() {
+    assert_eq!(EventPayload::from("PullRequestReviewThreadEvent", &json::parse(PULL_REQUEST_REVIEW_THREAD).unwrap()).unwrap(),
+               EventPayload::PullRequestReviewThread {
+                   action: "resolved".to_string(),
+                   pr: 210,
+                   first_comment: Some(63127893),
+               });
+
+    let mut payload = json::parse(PULL_REQUEST_REVIEW_THREAD).unwrap();
+    payload["thread"].remove("comments");
+    assert_eq!(EventPayload::from("PullRequestReviewThreadEvent", &payload).unwrap(),
+               EventPayload::PullRequestReviewThread {
+                   action: "resolved".to_string(),
+                   pr: 210,
+                   first_comment: None,
+               });
+}
+
 #[test]
 fn push() {
-    assert_eq!(EventPayload::from("PushEvent", &json::parse(PUSH).unwrap()),
+    assert_eq!(EventPayload::from("PushEvent", &json::parse(PUSH).unwrap()).unwrap(),
                EventPayload::Push {
                    pushed_ref: "refs/heads/doc".to_string(),
                    prev_head: "4d28f4b488f04c35135af7576ff5fd1f0ce53c7a".to_string(),
@@ -164,6 +325,7 @@ fn push() {
                                      author_name: "Nabijaczleweli Autouploader Bot".to_string(),
                                      author_email: "nabijaczleweli@gmail.com".to_string(),
                                      distinct: true,
+                                     contributors: vec![],
                                  },
                                  Commit {
                                      sha: "8eab6892203102e66a4b1eed26abc5a24cad8afe".to_string(),
@@ -171,15 +333,46 @@ fn push() {
                                      author_name: "Nabijaczleweli Autouploader Bot".to_string(),
                                      author_email: "nabijaczleweli@gmail.com".to_string(),
                                      distinct: false,
+                                     contributors: vec![],
                                  }],
                });
 }
 
+#[test]
+fn push_co_authored() {
+    match EventPayload::from("PushEvent", &json::parse(PUSH_CO_AUTHORED).unwrap()).unwrap() {
+        EventPayload::Push { commits, .. } => {
+            assert_eq!(commits.iter().map(|c| c.contributors.clone()).collect::<Vec<_>>(),
+                       vec![vec![Contributor {
+                                     kind: ContributorKind::CoAuthor,
+                                     name: "liigo".to_string(),
+                                     email: "liigo@qq.com".to_string(),
+                                 },
+                                 Contributor {
+                                     kind: ContributorKind::SignOff,
+                                     name: "nabijaczleweli".to_string(),
+                                     email: "nabijaczleweli@gmail.com".to_string(),
+                                 }],
+                            vec![Contributor {
+                                     kind: ContributorKind::CoAuthor,
+                                     name: "Stefan Sehe".to_string(),
+                                     email: "sehe@example.com".to_string(),
+                                 }],
+                            vec![]]);
+            assert_eq!(commits.iter().map(Commit::message_without_trailers).collect::<Vec<_>>(),
+                       vec!["Install git packages with -g\n\nCloses #213.".to_string(),
+                            "Document -g".to_string(),
+                            "Update manual for commits 1c8725dc2222...58998fadba13".to_string()]);
+        }
+        _ => panic!("not a push"),
+    }
+}
+
 #[test]
 fn release() {
-    assert_eq!(EventPayload::from("ReleaseEvent", &json::parse(RELEASE).unwrap()),
+    assert_eq!(EventPayload::from("ReleaseEvent", &json::parse(RELEASE).unwrap()).unwrap(),
                EventPayload::Release {
-                   action: "published".to_string(),
+                   action: ReleaseAction::Published,
                    tag_name: "v0.5.0".to_string(),
                    target: "master".to_string(),
                    draft: false,
@@ -189,14 +382,45 @@ fn release() {
                });
 }
 
+#[test]
+fn sponsorship() {
+    assert_eq!(EventPayload::from("SponsorshipEvent", &json::parse(SPONSORSHIP).unwrap()).unwrap(),
+               EventPayload::Sponsorship {
+                   action: "created".to_string(),
+                   sponsor: "sehe".to_string(),
+                   sponsorable: "nabijaczleweli".to_string(),
+                   tier: Some("$5 a month".to_string()),
+               });
+}
+
 #[test]
 fn watch() {
-    assert_eq!(EventPayload::from("WatchEvent", &json::parse(WATCH).unwrap()),
-               EventPayload::Watch { action: "started".to_string() });
+    assert_eq!(EventPayload::from("WatchEvent", &json::parse(WATCH).unwrap()).unwrap(),
+               EventPayload::Watch { action: WatchAction::Started });
 }
 
 #[test]
 fn other() {
-    assert_eq!(EventPayload::from("ForkApplyEvent", &json::parse(OTHER_FORK_APPLY).unwrap()),
-               EventPayload::Other { event_type: "ForkApplyEvent".to_string() });
+    assert_eq!(EventPayload::from("DownloadEvent", &json::parse(OTHER_DOWNLOAD).unwrap()).unwrap(),
+               EventPayload::Other { event_type: "DownloadEvent".to_string() });
+}
+
+#[test]
+fn malformed() {
+    assert_eq!(EventPayload::from("PushEvent", &json::parse(r#"{"ref": "refs/heads/master", "commits": [{"sha": 12}]}"#).unwrap()),
+               Err(Error::EventParsingFailed {
+                   id: "".to_string(),
+                   tp: "PushEvent".to_string(),
+                   path: "payload.before".to_string(),
+               }));
+}
+
+#[test]
+fn malformed_nested() {
+    assert_eq!(EventPayload::from("GollumEvent", &json::parse(r#"{"pages": [{"page_name": "Home", "title": 12}]}"#).unwrap()),
+               Err(Error::EventParsingFailed {
+                   id: "".to_string(),
+                   tp: "GollumEvent".to_string(),
+                   path: "payload.pages[0].title".to_string(),
+               }));
 }
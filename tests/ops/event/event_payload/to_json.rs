@@ -0,0 +1,215 @@
+use json;
+use dishub::Error;
+use dishub::ops::{GollumPayload, EventPayload, Commit};
+
+
+static COMMIT_COMMENT: &'static str = include_str!("../../../../test-data/commit_comment_payload.json");
+static CREATE: &'static str = include_str!("../../../../test-data/create_payload.json");
+static DELETE: &'static str = include_str!("../../../../test-data/delete_payload.json");
+static DISCUSSION: &'static str = include_str!("../../../../test-data/discussion_payload.json");
+static DISCUSSION_COMMENT: &'static str = include_str!("../../../../test-data/discussion_comment_payload.json");
+static FORK: &'static str = include_str!("../../../../test-data/fork_payload.json");
+static FORK_APPLY: &'static str = include_str!("../../../../test-data/fork_apply_payload.json");
+static GOLLUM: &'static str = include_str!("../../../../test-data/gollum_payload.json");
+static ISSUE_COMMENT: &'static str = include_str!("../../../../test-data/issue_comment_payload.json");
+static ISSUE_COMMENT_PULL_REQUEST: &'static str = include_str!("../../../../test-data/issue_comment_pull_request_payload.json");
+static ISSUES: &'static str = include_str!("../../../../test-data/issues_payload.json");
+static MEMBER: &'static str = include_str!("../../../../test-data/member_payload.json");
+static PUBLIC: &'static str = include_str!("../../../../test-data/public_payload.json");
+static PULL_REQUEST: &'static str = include_str!("../../../../test-data/pull_request_payload.json");
+static PULL_REQUEST_DRAFT: &'static str = include_str!("../../../../test-data/pull_request_draft_payload.json");
+static PULL_REQUEST_REVIEW: &'static str = include_str!("../../../../test-data/pull_request_review_payload.json");
+static PULL_REQUEST_REVIEW_COMMENT: &'static str = include_str!("../../../../test-data/pull_request_review_comment_payload.json");
+static PULL_REQUEST_REVIEW_THREAD: &'static str = include_str!("../../../../test-data/pull_request_review_thread_payload.json");
+static PUSH: &'static str = include_str!("../../../../test-data/push_payload.json");
+static PUSH_CO_AUTHORED: &'static str = include_str!("../../../../test-data/push_co_authored_payload.json");
+static RELEASE: &'static str = include_str!("../../../../test-data/release_payload.json");
+static SPONSORSHIP: &'static str = include_str!("../../../../test-data/sponsorship_payload.json");
+static WATCH: &'static str = include_str!("../../../../test-data/watch_payload.json");
+static OTHER_DOWNLOAD: &'static str = include_str!("../../../../test-data/other_download_payload.json");
+
+
+#[test]
+fn commit_comment() {
+    round_trip("CommitCommentEvent", COMMIT_COMMENT);
+}
+
+#[test]
+fn create() {
+    round_trip("CreateEvent", CREATE);
+}
+
+#[test]
+fn delete() {
+    round_trip("DeleteEvent", DELETE);
+}
+
+#[test]
+fn discussion() {
+    round_trip("DiscussionEvent", DISCUSSION);
+}
+
+#[test]
+fn discussion_comment() {
+    round_trip("DiscussionCommentEvent", DISCUSSION_COMMENT);
+}
+
+#[test]
+fn fork() {
+    round_trip("ForkEvent", FORK);
+}
+
+#[test]
+fn fork_apply() {
+    round_trip("ForkApplyEvent", FORK_APPLY);
+}
+
+#[test]
+fn gollum() {
+    round_trip("GollumEvent", GOLLUM);
+}
+
+#[test]
+fn issue_comment() {
+    round_trip("IssueCommentEvent", ISSUE_COMMENT);
+}
+
+#[test]
+fn issue_comment_pull_request() {
+    round_trip("IssueCommentEvent", ISSUE_COMMENT_PULL_REQUEST);
+}
+
+#[test]
+fn issues() {
+    round_trip("IssuesEvent", ISSUES);
+}
+
+#[test]
+fn member() {
+    round_trip("MemberEvent", MEMBER);
+}
+
+#[test]
+fn public() {
+    round_trip("PublicEvent", PUBLIC);
+}
+
+#[test]
+fn pull_request() {
+    round_trip("PullRequestEvent", PULL_REQUEST);
+}
+
+#[test]
+fn pull_request_draft() {
+    round_trip("PullRequestEvent", PULL_REQUEST_DRAFT);
+}
+
+#[test]
+fn pull_request_review() {
+    round_trip("PullRequestReviewEvent", PULL_REQUEST_REVIEW);
+}
+
+#[test]
+fn pull_request_review_comment() {
+    round_trip("PullRequestReviewCommentEvent", PULL_REQUEST_REVIEW_COMMENT);
+}
+
+#[test]
+fn pull_request_review_thread() {
+    round_trip("PullRequestReviewThreadEvent", PULL_REQUEST_REVIEW_THREAD);
+}
+
+#[test]
+fn push() {
+    round_trip("PushEvent", PUSH);
+}
+
+#[test]
+fn push_co_authored() {
+    round_trip("PushEvent", PUSH_CO_AUTHORED);
+}
+
+#[test]
+fn release() {
+    round_trip("ReleaseEvent", RELEASE);
+}
+
+#[test]
+fn sponsorship() {
+    round_trip("SponsorshipEvent", SPONSORSHIP);
+}
+
+#[test]
+fn watch() {
+    round_trip("WatchEvent", WATCH);
+}
+
+#[test]
+fn other() {
+    round_trip("DownloadEvent", OTHER_DOWNLOAD);
+}
+
+#[test]
+fn commits() {
+    match EventPayload::from("PushEvent", &json::parse(PUSH).unwrap()).unwrap() {
+        EventPayload::Push { commits, .. } => {
+            for commit in commits {
+                assert_eq!(Commit::from_json(&json::parse(&commit.to_json().dump()).unwrap()), Ok(commit));
+            }
+        }
+        _ => panic!("not a push"),
+    }
+}
+
+#[test]
+fn gollum_pages() {
+    match EventPayload::from("GollumEvent", &json::parse(GOLLUM).unwrap()).unwrap() {
+        EventPayload::Gollum { pages } => {
+            for page in pages {
+                assert_eq!(GollumPayload::from_json(&json::parse(&page.to_json().dump()).unwrap()), Ok(page));
+            }
+        }
+        _ => panic!("not gollum"),
+    }
+}
+
+#[test]
+fn unknown_type() {
+    assert_eq!(EventPayload::from_json(&json::parse(r#"{"type": "Download", "event_type": "DownloadEvent"}"#).unwrap()),
+               Err(Error::EventParsingFailed {
+                   id: "".to_string(),
+                   tp: "Download".to_string(),
+                   path: "payload.type".to_string(),
+               }));
+}
+
+#[test]
+fn malformed() {
+    assert_eq!(EventPayload::from_json(&json::parse(r#"{"type": "Issues", "action": "opened", "number": 11, "title": "Title", "body": "",
+                                                        "labels": ["bug", 12]}"#)
+                   .unwrap()),
+               Err(Error::EventParsingFailed {
+                   id: "".to_string(),
+                   tp: "Issues".to_string(),
+                   path: "payload.labels[1]".to_string(),
+               }));
+}
+
+#[test]
+fn malformed_nested() {
+    assert_eq!(EventPayload::from_json(&json::parse(r#"{"type": "Push", "pushed_ref": "refs/heads/master", "prev_head": "", "new_head": "", "size": 1,
+                                                        "distinct_size": 1, "commits": [{"sha": "46650797f4aec4d373c647062eb3df288ee7b8f2"}]}"#)
+                   .unwrap()),
+               Err(Error::EventParsingFailed {
+                   id: "".to_string(),
+                   tp: "Push".to_string(),
+                   path: "payload.commits[0].message".to_string(),
+               }));
+}
+
+
+fn round_trip(tpe: &str, payload: &str) {
+    let payload = EventPayload::from(tpe, &json::parse(payload).unwrap()).unwrap();
+    assert_eq!(EventPayload::from_json(&payload.to_json()).as_ref(), Ok(&payload));
+    assert_eq!(EventPayload::from_json(&json::parse(&payload.to_json().dump()).unwrap()), Ok(payload));
+}
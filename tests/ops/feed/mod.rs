@@ -1,4 +1,7 @@
+use dishub::ops::render::{TimeFormat, Format};
+use dishub::ops::locale::Catalogue;
 use dishub::ops::Feed;
+use std::collections::BTreeMap;
 
 mod write;
 mod read;
@@ -15,5 +18,17 @@ fn new() {
                    latest: None,
                    next_min: None,
                    latest_event: None,
+                   format: Format::Plain,
+                   templates: BTreeMap::new(),
+                   time: TimeFormat::default(),
+                   trusted: vec![],
+                   excerpt: None,
+                   max_commits: 5,
+                   distinct_commits_only: false,
+                   previews: BTreeMap::new(),
+                   api_url: None,
+                   web_url: None,
+                   locale: None,
+                   catalogue: Catalogue::default(),
                });
 }
@@ -1,8 +1,13 @@
 use self::super::super::make_dir;
+use dishub::ops::render::{TimeFormat, TimeStyle, Template, Timezone, Preview, Format};
+use std::collections::BTreeMap;
+use dishub::ops::locale::Catalogue;
+use dishub::ops::github::GitHubUrls;
 use dishub::ops::Feed;
+use dishub::Error;
 use chrono::DateTime;
 use std::io::Write;
-use std::fs::File;
+use std::fs::{File, create_dir};
 
 
 #[test]
@@ -57,5 +62,518 @@ fn polled() {
                            latest: Some(DateTime::parse_from_rfc2822("Sat, 1 Jul 2000 15:12:57 -0800").unwrap()),
                            next_min: Some(DateTime::parse_from_rfc2822("Sat, 1 Jul 2000 15:14:00 -0800").unwrap()),
                            latest_event: Some(512),
+                           format: Format::Plain,
+                           templates: BTreeMap::new(),
+                           time: TimeFormat::default(),
+                           trusted: vec![],
+                           excerpt: None,
+                           max_commits: 5,
+                           distinct_commits_only: false,
+                           previews: BTreeMap::new(),
+                           api_url: None,
+                           web_url: None,
+                           locale: None,
+                           catalogue: Catalogue::default(),
                        }]));
 }
+
+#[test]
+fn formatted() {
+    let mut path = make_dir("feed-read", "formatted");
+    path.push("feeds.toml");
+
+    File::create(&path)
+        .unwrap()
+        .write_all(b"[[feed]]\n\
+                     channel = 105\n\
+                     format = \"embed\"\n\
+                     server = 1056\n\
+                     subject = \"nabijaczleweli\"\n")
+        .unwrap();
+
+    let mut feed = Feed::new("nabijaczleweli".to_string(), 1056, 105);
+    feed.format = Format::Embed;
+    assert_eq!(Feed::read(&path), Ok(vec![feed]));
+}
+
+#[test]
+fn unknown_format() {
+    let mut path = make_dir("feed-read", "unknown_format");
+    path.push("feeds.toml");
+
+    File::create(&path)
+        .unwrap()
+        .write_all(b"[[feed]]\n\
+                     channel = 105\n\
+                     format = \"rtf\"\n\
+                     server = 1056\n\
+                     subject = \"nabijaczleweli\"\n")
+        .unwrap();
+
+    assert_eq!(Feed::read(&path),
+               Err(Error::FileParsingFailed {
+                   desc: "Followed feeds",
+                   errors: vec!["Unknown format \"rtf\" for nabijaczleweli".to_string()],
+               }));
+}
+
+#[test]
+fn templated() {
+    let mut path = make_dir("feed-read", "templated");
+    path.push("feeds.toml");
+
+    File::create(&path)
+        .unwrap()
+        .write_all(b"[[feed]]\n\
+                     channel = 105\n\
+                     server = 1056\n\
+                     subject = \"nabijaczleweli\"\n\
+                     \n\
+                     [feed.templates]\n\
+                     Push = \"{{actor}} pushed to {{branch}}\"\n\
+                     Watch = \"\\u2b50 {{repo}}\"\n")
+        .unwrap();
+
+    let mut feed = Feed::new("nabijaczleweli".to_string(), 1056, 105);
+    feed.templates.insert("Push".to_string(), Template::parse("{{actor}} pushed to {{branch}}").unwrap());
+    feed.templates.insert("Watch".to_string(), Template::parse("⭐ {{repo}}").unwrap());
+    assert_eq!(Feed::read(&path), Ok(vec![feed]));
+}
+
+#[test]
+fn bad_templates() {
+    let mut path = make_dir("feed-read", "bad_templates");
+    path.push("feeds.toml");
+
+    File::create(&path)
+        .unwrap()
+        .write_all(b"[[feed]]\n\
+                     channel = 105\n\
+                     server = 1056\n\
+                     subject = \"nabijaczleweli\"\n\
+                     \n\
+                     [feed.templates]\n\
+                     PushEvent = \"{{actor}} pushed\"\n\
+                     Watch = \"{{#each payload.commits}}\"\n")
+        .unwrap();
+
+    assert_eq!(Feed::read(&path),
+               Err(Error::FileParsingFailed {
+                   desc: "Followed feeds",
+                   errors: vec!["Unknown event type \"PushEvent\" for template for nabijaczleweli".to_string(),
+                                "Watch template for nabijaczleweli: 1:1: unclosed {{#each}}".to_string()],
+               }));
+}
+
+#[test]
+fn timed() {
+    let mut path = make_dir("feed-read", "timed");
+    path.push("feeds.toml");
+
+    File::create(&path)
+        .unwrap()
+        .write_all(b"[[feed]]\n\
+                     channel = 105\n\
+                     server = 1056\n\
+                     subject = \"nabijaczleweli\"\n\
+                     time_format = \"discord:R\"\n\
+                     timezone = \"+02:00\"\n")
+        .unwrap();
+
+    let mut feed = Feed::new("nabijaczleweli".to_string(), 1056, 105);
+    feed.time = TimeFormat {
+        timezone: Timezone::from_name("+02:00").unwrap(),
+        style: TimeStyle::Discord('R'),
+    };
+    assert_eq!(Feed::read(&path), Ok(vec![feed]));
+}
+
+#[test]
+fn bad_time() {
+    let mut path = make_dir("feed-read", "bad_time");
+    path.push("feeds.toml");
+
+    File::create(&path)
+        .unwrap()
+        .write_all(b"[[feed]]\n\
+                     channel = 105\n\
+                     server = 1056\n\
+                     subject = \"nabijaczleweli\"\n\
+                     time_format = \"%Q\"\n\
+                     timezone = \"Europe/Warsaw\"\n")
+        .unwrap();
+
+    assert_eq!(Feed::read(&path),
+               Err(Error::FileParsingFailed {
+                   desc: "Followed feeds",
+                   errors: vec!["Unknown timezone \"Europe/Warsaw\" for nabijaczleweli".to_string(),
+                                "Invalid time format \"%Q\" for nabijaczleweli".to_string()],
+               }));
+}
+
+#[test]
+fn trusted() {
+    let mut path = make_dir("feed-read", "trusted");
+    path.push("feeds.toml");
+
+    File::create(&path)
+        .unwrap()
+        .write_all(b"[[feed]]\n\
+                     channel = 105\n\
+                     server = 1056\n\
+                     subject = \"nabijaczleweli\"\n\
+                     trusted = [\"nabijaczleweli/dishub\", \"nabijaczleweli/*\"]\n")
+        .unwrap();
+
+    let mut feed = Feed::new("nabijaczleweli".to_string(), 1056, 105);
+    feed.trusted = vec!["nabijaczleweli/dishub".to_string(), "nabijaczleweli/*".to_string()];
+    assert_eq!(Feed::read(&path), Ok(vec![feed]));
+}
+
+#[test]
+fn bad_trusted() {
+    let mut path = make_dir("feed-read", "bad_trusted");
+    path.push("feeds.toml");
+
+    File::create(&path)
+        .unwrap()
+        .write_all(b"[[feed]]\n\
+                     channel = 105\n\
+                     server = 1056\n\
+                     subject = \"nabijaczleweli\"\n\
+                     trusted = [\"nabijaczleweli\", \"nabijaczleweli/dishub/issues\", \"/dishub\"]\n")
+        .unwrap();
+
+    assert_eq!(Feed::read(&path),
+               Err(Error::FileParsingFailed {
+                   desc: "Followed feeds",
+                   errors: vec!["Invalid trusted repository \"nabijaczleweli\" for nabijaczleweli".to_string(),
+                                "Invalid trusted repository \"nabijaczleweli/dishub/issues\" for nabijaczleweli".to_string(),
+                                "Invalid trusted repository \"/dishub\" for nabijaczleweli".to_string()],
+               }));
+}
+
+#[test]
+fn excerpted() {
+    let mut path = make_dir("feed-read", "excerpted");
+    path.push("feeds.toml");
+
+    File::create(&path)
+        .unwrap()
+        .write_all(b"[[feed]]\n\
+                     channel = 105\n\
+                     excerpt = 300\n\
+                     server = 1056\n\
+                     subject = \"nabijaczleweli\"\n")
+        .unwrap();
+
+    let mut feed = Feed::new("nabijaczleweli".to_string(), 1056, 105);
+    feed.excerpt = Some(300);
+    assert_eq!(Feed::read(&path), Ok(vec![feed]));
+}
+
+#[test]
+fn bad_excerpt() {
+    let mut path = make_dir("feed-read", "bad_excerpt");
+    path.push("feeds.toml");
+
+    File::create(&path)
+        .unwrap()
+        .write_all(b"[[feed]]\n\
+                     channel = 105\n\
+                     excerpt = 0\n\
+                     server = 1056\n\
+                     subject = \"nabijaczleweli\"\n")
+        .unwrap();
+
+    assert_eq!(Feed::read(&path),
+               Err(Error::FileParsingFailed {
+                   desc: "Followed feeds",
+                   errors: vec!["Excerpt length 0 for nabijaczleweli, remove it to disable excerpts".to_string()],
+               }));
+}
+
+#[test]
+fn commits() {
+    let mut path = make_dir("feed-read", "commits");
+    path.push("feeds.toml");
+
+    File::create(&path)
+        .unwrap()
+        .write_all(b"[[feed]]\n\
+                     channel = 105\n\
+                     distinct_commits_only = true\n\
+                     max_commits = 10\n\
+                     server = 1056\n\
+                     subject = \"nabijaczleweli\"\n")
+        .unwrap();
+
+    let mut feed = Feed::new("nabijaczleweli".to_string(), 1056, 105);
+    feed.max_commits = 10;
+    feed.distinct_commits_only = true;
+    assert_eq!(Feed::read(&path), Ok(vec![feed]));
+}
+
+#[test]
+fn previewed() {
+    let mut path = make_dir("feed-read", "previewed");
+    path.push("feeds.toml");
+
+    File::create(&path)
+        .unwrap()
+        .write_all(b"[[feed]]\n\
+                     channel = 105\n\
+                     server = 1056\n\
+                     subject = \"nabijaczleweli\"\n\
+                     \n\
+                     [feed.previews]\n\
+                     Gollum = \"2\"\n\
+                     Release = \"first\"\n\
+                     default = \"all\"\n")
+        .unwrap();
+
+    let mut feed = Feed::new("nabijaczleweli".to_string(), 1056, 105);
+    feed.previews.insert("Gollum".to_string(), Preview::Nth(1));
+    feed.previews.insert("Release".to_string(), Preview::Nth(0));
+    feed.previews.insert("default".to_string(), Preview::All);
+    assert_eq!(Feed::read(&path), Ok(vec![feed]));
+}
+
+#[test]
+fn bad_previews() {
+    let mut path = make_dir("feed-read", "bad_previews");
+    path.push("feeds.toml");
+
+    File::create(&path)
+        .unwrap()
+        .write_all(b"[[feed]]\n\
+                     channel = 105\n\
+                     server = 1056\n\
+                     subject = \"nabijaczleweli\"\n\
+                     \n\
+                     [feed.previews]\n\
+                     Push = \"0\"\n\
+                     Starred = \"all\"\n")
+        .unwrap();
+
+    assert_eq!(Feed::read(&path),
+               Err(Error::FileParsingFailed {
+                   desc: "Followed feeds",
+                   errors: vec!["Invalid preview \"0\" for Push for nabijaczleweli".to_string(),
+                                "Unknown event type \"Starred\" for previews for nabijaczleweli".to_string()],
+               }));
+}
+
+#[test]
+fn enterprise() {
+    let mut path = make_dir("feed-read", "enterprise");
+    path.push("feeds.toml");
+
+    File::create(&path)
+        .unwrap()
+        .write_all(b"[[feed]]\n\
+                     api_url = \"https://ghe.example.com/api/v3/\"\n\
+                     channel = 105\n\
+                     server = 1056\n\
+                     subject = \"nabijaczleweli\"\n\
+                     web_url = \"https://ghe.example.com\"\n")
+        .unwrap();
+
+    let mut feed = Feed::new("nabijaczleweli".to_string(), 1056, 105);
+    feed.api_url = Some("https://ghe.example.com/api/v3".to_string());
+    feed.web_url = Some("https://ghe.example.com".to_string());
+    assert_eq!(Feed::read(&path), Ok(vec![feed]));
+}
+
+#[test]
+fn enterprise_api_url_only() {
+    let mut path = make_dir("feed-read", "enterprise_api_url_only");
+    path.push("feeds.toml");
+
+    File::create(&path)
+        .unwrap()
+        .write_all(b"[[feed]]\n\
+                     api_url = \"https://ghe.example.com/api/v3\"\n\
+                     channel = 105\n\
+                     server = 1056\n\
+                     subject = \"nabijaczleweli\"\n")
+        .unwrap();
+
+    let feeds = Feed::read(&path).unwrap();
+    assert_eq!(feeds[0].github_urls(),
+               GitHubUrls {
+                   api: "https://ghe.example.com/api/v3".to_string(),
+                   web: "https://ghe.example.com".to_string(),
+               });
+}
+
+#[test]
+fn unpaired_urls() {
+    let mut path = make_dir("feed-read", "unpaired_urls");
+    path.push("feeds.toml");
+
+    File::create(&path)
+        .unwrap()
+        .write_all(b"[[feed]]\n\
+                     api_url = \"https://git.example.com/github-api\"\n\
+                     channel = 105\n\
+                     server = 1056\n\
+                     subject = \"nabijaczleweli\"\n\
+                     \n\
+                     [[feed]]\n\
+                     channel = 105\n\
+                     server = 1056\n\
+                     subject = \"nabijaczleweli/dishub\"\n\
+                     web_url = \"https://ghe.example.com\"\n")
+        .unwrap();
+
+    assert_eq!(Feed::read(&path),
+               Err(Error::FileParsingFailed {
+                   desc: "Followed feeds",
+                   errors: vec!["Can't tell the web URL for API URL \"https://git.example.com/github-api\" for nabijaczleweli, set web_url as well"
+                                    .to_string(),
+                                "Web URL \"https://ghe.example.com\" without api_url for nabijaczleweli/dishub".to_string()],
+               }));
+}
+
+#[test]
+fn bad_urls() {
+    let mut path = make_dir("feed-read", "bad_urls");
+    path.push("feeds.toml");
+
+    File::create(&path)
+        .unwrap()
+        .write_all(b"[[feed]]\n\
+                     api_url = \"ghe.example.com\"\n\
+                     channel = 105\n\
+                     server = 1056\n\
+                     subject = \"nabijaczleweli\"\n\
+                     web_url = \"ftp://ghe.example.com\"\n")
+        .unwrap();
+
+    assert_eq!(Feed::read(&path),
+               Err(Error::FileParsingFailed {
+                   desc: "Followed feeds",
+                   errors: vec!["Invalid API URL \"ghe.example.com\" for nabijaczleweli".to_string(),
+                                "Invalid web URL \"ftp://ghe.example.com\" for nabijaczleweli".to_string()],
+               }));
+}
+
+#[test]
+fn localised() {
+    let mut path = make_dir("feed-read", "localised");
+    path.push("feeds.toml");
+
+    File::create(&path)
+        .unwrap()
+        .write_all(b"[[feed]]\n\
+                     channel = 105\n\
+                     locale = \"pl\"\n\
+                     server = 1056\n\
+                     subject = \"nabijaczleweli\"\n")
+        .unwrap();
+
+    let mut feed = Feed::new("nabijaczleweli".to_string(), 1056, 105);
+    feed.locale = Some("pl".to_string());
+    feed.catalogue = Catalogue::builtin("pl").unwrap();
+    assert_eq!(Feed::read(&path), Ok(vec![feed]));
+}
+
+#[test]
+fn channel_locales() {
+    let mut path = make_dir("feed-read", "channel_locales");
+    path.push("feeds.toml");
+
+    File::create(&path)
+        .unwrap()
+        .write_all(b"[[feed]]\n\
+                     channel = 105\n\
+                     server = 1056\n\
+                     subject = \"nabijaczleweli\"\n\
+                     [[feed]]\n\
+                     channel = 105\n\
+                     locale = \"en\"\n\
+                     server = 1056\n\
+                     subject = \"nabijaczleweli/dishub\"\n\
+                     [[feed]]\n\
+                     channel = 106\n\
+                     server = 1056\n\
+                     subject = \"sehe\"\n\
+                     [channel_locales]\n\
+                     105 = \"pl\"\n")
+        .unwrap();
+
+    let mut polish = Feed::new("nabijaczleweli".to_string(), 1056, 105);
+    polish.catalogue = Catalogue::builtin("pl").unwrap();
+    let mut english = Feed::new("nabijaczleweli/dishub".to_string(), 1056, 105);
+    english.locale = Some("en".to_string());
+    english.catalogue = Catalogue::builtin("en").unwrap();
+    assert_eq!(Feed::read(&path), Ok(vec![polish, english, Feed::new("sehe".to_string(), 1056, 106)]));
+}
+
+#[test]
+fn custom_locale() {
+    let mut path = make_dir("feed-read", "custom_locale");
+    let _ = create_dir(path.join("locales"));
+    File::create(path.join("locales").join("pirate.toml"))
+        .unwrap()
+        .write_all(b"plural = \"en\"\n\
+                     [messages]\n\
+                     watch = \"{actor} be starrin' {repo}, arr\"\n")
+        .unwrap();
+    path.push("feeds.toml");
+
+    File::create(&path)
+        .unwrap()
+        .write_all(b"[[feed]]\n\
+                     channel = 105\n\
+                     locale = \"pirate\"\n\
+                     server = 1056\n\
+                     subject = \"nabijaczleweli\"\n")
+        .unwrap();
+
+    let feeds = Feed::read(&path).unwrap();
+    assert_eq!(feeds[0].locale, Some("pirate".to_string()));
+    assert_eq!(feeds[0].catalogue.name, "pirate");
+    assert_eq!(feeds[0].catalogue.get("watch", 1), Some("{actor} be starrin' {repo}, arr"));
+    assert_eq!(feeds[0].catalogue.get("fork", 1), Catalogue::english().get("fork", 1));
+}
+
+#[test]
+fn bad_locale() {
+    let mut path = make_dir("feed-read", "bad_locale");
+    let _ = create_dir(path.join("locales"));
+    File::create(path.join("locales").join("broken.toml"))
+        .unwrap()
+        .write_all(b"plural = \"pl\"\n\
+                     [messages]\n\
+                     push = [\"{count} commit\", \"{count} commits\"]\n\
+                     plunder = \"arr\"\n")
+        .unwrap();
+    path.push("feeds.toml");
+
+    File::create(&path)
+        .unwrap()
+        .write_all(b"[[feed]]\n\
+                     channel = 105\n\
+                     locale = \"tlh\"\n\
+                     server = 1056\n\
+                     subject = \"nabijaczleweli\"\n\
+                     [[feed]]\n\
+                     channel = 105\n\
+                     locale = \"broken\"\n\
+                     server = 1056\n\
+                     subject = \"nabijaczleweli/dishub\"\n\
+                     [channel_locales]\n\
+                     general = \"pl\"\n")
+        .unwrap();
+
+    assert_eq!(Feed::read(&path),
+               Err(Error::FileParsingFailed {
+                   desc: "Followed feeds",
+                   errors: vec!["Invalid channel ID \"general\" in channel_locales".to_string(),
+                                "Unknown locale \"tlh\" for nabijaczleweli".to_string(),
+                                "Locale \"broken\": Unknown message \"plunder\" for nabijaczleweli/dishub".to_string(),
+                                "Locale \"broken\": Message \"push\" needs 3 forms for plural rule \"pl\" for nabijaczleweli/dishub".to_string()],
+               }));
+}
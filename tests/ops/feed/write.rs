@@ -1,7 +1,10 @@
 use self::super::super::make_dir;
+use dishub::ops::render::{TimeFormat, TimeStyle, Template, Timezone, Preview, Format};
+use std::collections::BTreeMap;
+use dishub::ops::locale::Catalogue;
 use dishub::ops::Feed;
 use chrono::DateTime;
-use std::io::Read;
+use std::io::{Write, Read};
 use std::fs::File;
 
 
@@ -52,6 +55,18 @@ fn polled() {
                          latest: Some(DateTime::parse_from_rfc2822("Sat, 1 Jul 2000 15:12:57 -0800").unwrap()),
                          next_min: Some(DateTime::parse_from_rfc2822("Sat, 1 Jul 2000 15:14:00 -0800").unwrap()),
                          latest_event: Some(512),
+                         format: Format::Plain,
+                         templates: BTreeMap::new(),
+                         time: TimeFormat::default(),
+                         trusted: vec![],
+                         excerpt: None,
+                         max_commits: 5,
+                         distinct_commits_only: false,
+                         previews: BTreeMap::new(),
+                         api_url: None,
+                         web_url: None,
+                         locale: None,
+                         catalogue: Catalogue::default(),
                      }],
                 &path);
 
@@ -70,3 +85,253 @@ fn polled() {
                 server = 1056\n\
                 subject = \"nabijaczleweli\"\n");
 }
+
+#[test]
+fn formatted() {
+    let mut path = make_dir("feed-write", "formatted");
+    path.push("feeds.toml");
+
+    let mut feed = Feed::new("nabijaczleweli".to_string(), 1056, 105);
+    feed.format = Format::Html;
+    Feed::write(vec![feed], &path);
+
+    let mut buf = String::new();
+    let _ = File::open(&path)
+        .unwrap()
+        .read_to_string(&mut buf);
+
+    assert_eq!(&buf,
+               "[[feed]]\n\
+                channel = 105\n\
+                format = \"html\"\n\
+                server = 1056\n\
+                subject = \"nabijaczleweli\"\n");
+}
+
+#[test]
+fn templated() {
+    let mut path = make_dir("feed-write", "templated");
+    path.push("feeds.toml");
+
+    let mut feed = Feed::new("nabijaczleweli".to_string(), 1056, 105);
+    feed.templates.insert("Push".to_string(), Template::parse("{{actor}} pushed to {{branch}}").unwrap());
+    Feed::write(vec![feed.clone()], &path);
+
+    let mut buf = String::new();
+    let _ = File::open(&path)
+        .unwrap()
+        .read_to_string(&mut buf);
+
+    assert_eq!(&buf,
+               "[[feed]]\n\
+                channel = 105\n\
+                server = 1056\n\
+                subject = \"nabijaczleweli\"\n\
+                \n\
+                [feed.templates]\n\
+                Push = \"{{actor}} pushed to {{branch}}\"\n");
+    assert_eq!(Feed::read(&path), Ok(vec![feed]));
+}
+
+#[test]
+fn timed() {
+    let mut path = make_dir("feed-write", "timed");
+    path.push("feeds.toml");
+
+    let mut feed = Feed::new("nabijaczleweli".to_string(), 1056, 105);
+    feed.time = TimeFormat {
+        timezone: Timezone::Local,
+        style: TimeStyle::Strftime("%H:%M".to_string()),
+    };
+    Feed::write(vec![feed.clone()], &path);
+
+    let mut buf = String::new();
+    let _ = File::open(&path)
+        .unwrap()
+        .read_to_string(&mut buf);
+
+    assert_eq!(&buf,
+               "[[feed]]\n\
+                channel = 105\n\
+                server = 1056\n\
+                subject = \"nabijaczleweli\"\n\
+                time_format = \"%H:%M\"\n\
+                timezone = \"local\"\n");
+    assert_eq!(Feed::read(&path), Ok(vec![feed]));
+}
+
+#[test]
+fn trusted() {
+    let mut path = make_dir("feed-write", "trusted");
+    path.push("feeds.toml");
+
+    let mut feed = Feed::new("nabijaczleweli".to_string(), 1056, 105);
+    feed.trusted = vec!["nabijaczleweli/*".to_string()];
+    Feed::write(vec![feed.clone()], &path);
+
+    let mut buf = String::new();
+    let _ = File::open(&path)
+        .unwrap()
+        .read_to_string(&mut buf);
+
+    assert_eq!(&buf,
+               "[[feed]]\n\
+                channel = 105\n\
+                server = 1056\n\
+                subject = \"nabijaczleweli\"\n\
+                trusted = [\"nabijaczleweli/*\"]\n");
+    assert_eq!(Feed::read(&path), Ok(vec![feed]));
+}
+
+#[test]
+fn excerpted() {
+    let mut path = make_dir("feed-write", "excerpted");
+    path.push("feeds.toml");
+
+    let mut feed = Feed::new("nabijaczleweli".to_string(), 1056, 105);
+    feed.excerpt = Some(500);
+    Feed::write(vec![feed.clone()], &path);
+
+    let mut buf = String::new();
+    let _ = File::open(&path)
+        .unwrap()
+        .read_to_string(&mut buf);
+
+    assert_eq!(&buf,
+               "[[feed]]\n\
+                channel = 105\n\
+                excerpt = 500\n\
+                server = 1056\n\
+                subject = \"nabijaczleweli\"\n");
+    assert_eq!(Feed::read(&path), Ok(vec![feed]));
+}
+
+#[test]
+fn commits() {
+    let mut path = make_dir("feed-write", "commits");
+    path.push("feeds.toml");
+
+    let mut feed = Feed::new("nabijaczleweli".to_string(), 1056, 105);
+    feed.max_commits = 0;
+    feed.distinct_commits_only = true;
+    Feed::write(vec![feed.clone()], &path);
+
+    let mut buf = String::new();
+    let _ = File::open(&path)
+        .unwrap()
+        .read_to_string(&mut buf);
+
+    assert_eq!(&buf,
+               "[[feed]]\n\
+                channel = 105\n\
+                distinct_commits_only = true\n\
+                max_commits = 0\n\
+                server = 1056\n\
+                subject = \"nabijaczleweli\"\n");
+    assert_eq!(Feed::read(&path), Ok(vec![feed]));
+}
+
+#[test]
+fn previewed() {
+    let mut path = make_dir("feed-write", "previewed");
+    path.push("feeds.toml");
+
+    let mut feed = Feed::new("nabijaczleweli".to_string(), 1056, 105);
+    feed.previews.insert("Gollum".to_string(), Preview::Last);
+    feed.previews.insert("Release".to_string(), Preview::Nth(2));
+    Feed::write(vec![feed.clone()], &path);
+
+    let mut buf = String::new();
+    let _ = File::open(&path)
+        .unwrap()
+        .read_to_string(&mut buf);
+
+    assert_eq!(&buf,
+               "[[feed]]\n\
+                channel = 105\n\
+                server = 1056\n\
+                subject = \"nabijaczleweli\"\n\
+                \n\
+                [feed.previews]\n\
+                Gollum = \"last\"\n\
+                Release = \"3\"\n");
+    assert_eq!(Feed::read(&path), Ok(vec![feed]));
+}
+
+#[test]
+fn enterprise() {
+    let mut path = make_dir("feed-write", "enterprise");
+    path.push("feeds.toml");
+
+    let mut feed = Feed::new("nabijaczleweli".to_string(), 1056, 105);
+    feed.api_url = Some("https://ghe.example.com/api/v3".to_string());
+    feed.web_url = Some("https://ghe.example.com".to_string());
+    Feed::write(vec![feed.clone()], &path);
+
+    let mut buf = String::new();
+    let _ = File::open(&path)
+        .unwrap()
+        .read_to_string(&mut buf);
+
+    assert_eq!(&buf,
+               "[[feed]]\n\
+                api_url = \"https://ghe.example.com/api/v3\"\n\
+                channel = 105\n\
+                server = 1056\n\
+                subject = \"nabijaczleweli\"\n\
+                web_url = \"https://ghe.example.com\"\n");
+    assert_eq!(Feed::read(&path), Ok(vec![feed]));
+}
+
+#[test]
+fn localised() {
+    let mut path = make_dir("feed-write", "localised");
+    path.push("feeds.toml");
+
+    let mut feed = Feed::new("nabijaczleweli".to_string(), 1056, 105);
+    feed.locale = Some("pl".to_string());
+    feed.catalogue = Catalogue::builtin("pl").unwrap();
+    Feed::write(vec![feed.clone()], &path);
+
+    let mut buf = String::new();
+    let _ = File::open(&path)
+        .unwrap()
+        .read_to_string(&mut buf);
+
+    assert_eq!(&buf,
+               "[[feed]]\n\
+                channel = 105\n\
+                locale = \"pl\"\n\
+                server = 1056\n\
+                subject = \"nabijaczleweli\"\n");
+    assert_eq!(Feed::read(&path), Ok(vec![feed]));
+}
+
+#[test]
+fn channel_locales() {
+    let mut path = make_dir("feed-write", "channel_locales");
+    path.push("feeds.toml");
+
+    File::create(&path)
+        .unwrap()
+        .write_all(b"[[feed]]\n\
+                     channel = 105\n\
+                     server = 1056\n\
+                     subject = \"nabijaczleweli\"\n\
+                     [channel_locales]\n\
+                     105 = \"pl\"\n")
+        .unwrap();
+
+    let mut feeds = Feed::read(&path).unwrap();
+    feeds.push(Feed::new("sehe".to_string(), 1056, 106));
+    Feed::write(feeds.clone(), &path);
+
+    let mut buf = String::new();
+    let _ = File::open(&path)
+        .unwrap()
+        .read_to_string(&mut buf);
+
+    assert!(buf.contains("[channel_locales]\n105 = \"pl\"\n"));
+    assert!(!buf.contains("locale ="));
+    assert_eq!(Feed::read(&path), Ok(feeds));
+}
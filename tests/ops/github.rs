@@ -0,0 +1,164 @@
+use dishub::ops::github::{RateLimit, response_error, next_page};
+use chrono::{Duration, DateTime};
+use dishub::Error;
+
+
+static URL: &'static str = "https://api.github.com/repos/nabijaczleweli/dishub/events";
+
+
+#[test]
+fn success() {
+    assert_eq!(response_error(200, URL, "[]"), None);
+    assert_eq!(response_error(204, URL, ""), None);
+    assert_eq!(response_error(304, URL, ""), None);
+}
+
+#[test]
+fn unauthorised() {
+    assert_eq!(response_error(401,
+                              URL,
+                              r#"{"message": "Bad credentials", "documentation_url": "https://docs.github.com/rest"}"#),
+               Some(Error::GitHubUnauthorised {
+                   status: 401,
+                   url: URL.to_string(),
+                   message: Some("Bad credentials".to_string()),
+               }));
+}
+
+#[test]
+fn forbidden() {
+    assert_eq!(response_error(403, URL, r#"{"message": "Repository access blocked"}"#),
+               Some(Error::GitHubForbidden {
+                   status: 403,
+                   url: URL.to_string(),
+                   message: Some("Repository access blocked".to_string()),
+               }));
+}
+
+#[test]
+fn not_found() {
+    assert_eq!(response_error(404, URL, r#"{"message": "Not Found"}"#),
+               Some(Error::GitHubNotFound {
+                   status: 404,
+                   url: URL.to_string(),
+                   message: Some("Not Found".to_string()),
+               }));
+}
+
+#[test]
+fn server_error() {
+    assert_eq!(response_error(502, URL, "<html><body><h1>502 Bad Gateway</h1></body></html>"),
+               Some(Error::GitHubServerError {
+                   status: 502,
+                   url: URL.to_string(),
+                   message: None,
+               }));
+    assert_eq!(response_error(500, URL, r#"{"message": "Server Error"}"#),
+               Some(Error::GitHubServerError {
+                   status: 500,
+                   url: URL.to_string(),
+                   message: Some("Server Error".to_string()),
+               }));
+}
+
+#[test]
+fn rate_limited() {
+    assert_eq!(response_error(403, URL, r#"{"message": "API rate limit exceeded for 127.0.0.1."}"#),
+               Some(Error::GitHubRateLimited {
+                   url: URL.to_string(),
+                   message: Some("API rate limit exceeded for 127.0.0.1.".to_string()),
+                   until: None,
+               }));
+    assert_eq!(response_error(403, URL, r#"{"message": "You have exceeded a secondary rate limit. Please wait a few minutes before you try again."}"#),
+               Some(Error::GitHubRateLimited {
+                   url: URL.to_string(),
+                   message: Some("You have exceeded a secondary rate limit. Please wait a few minutes before you try again.".to_string()),
+                   until: None,
+               }));
+    assert_eq!(response_error(429, URL, ""),
+               Some(Error::GitHubRateLimited {
+                   url: URL.to_string(),
+                   message: None,
+                   until: None,
+               }));
+}
+
+#[test]
+fn rate_limit_from_headers() {
+    let now = DateTime::parse_from_rfc3339("2016-11-10T08:42:18Z").unwrap();
+    assert_eq!(RateLimit::from_headers(Some(60), Some(0), Some(1478767338), &now),
+               Some(RateLimit {
+                   limit: 60,
+                   remaining: 0,
+                   reset: DateTime::parse_from_rfc3339("2016-11-10T08:42:18Z").unwrap(),
+               }));
+    assert_eq!(RateLimit::from_headers(None, Some(0), Some(1478767338), &now), None);
+    assert_eq!(RateLimit::from_headers(Some(60), None, Some(1478767338), &now), None);
+    assert_eq!(RateLimit::from_headers(Some(60), Some(0), None, &now), None);
+}
+
+#[test]
+fn rate_limit_paused_until() {
+    let now = DateTime::parse_from_rfc3339("2016-11-10T08:42:18Z").unwrap();
+    let rl = RateLimit {
+        limit: 5000,
+        remaining: 0,
+        reset: now + Duration::seconds(90),
+    };
+    assert_eq!(rl.paused_until(&now), Some(now + Duration::seconds(90)));
+    assert_eq!(rl.paused_until(&(now + Duration::seconds(90))), None);
+    assert_eq!(RateLimit { remaining: 12, ..rl }.paused_until(&now), None);
+}
+
+#[test]
+fn rate_limit_display() {
+    assert_eq!(RateLimit {
+                       limit: 5000,
+                       remaining: 4987,
+                       reset: DateTime::parse_from_rfc3339("2016-11-10T09:08:58+01:00").unwrap(),
+                   }
+                   .to_string(),
+               "4987/5000 requests left, resetting at 09:08:58");
+}
+
+#[test]
+fn unexpected_status() {
+    assert_eq!(response_error(422, URL, r#"{"message": "Validation Failed"}"#),
+               Some(Error::GitHubUnexpectedStatus {
+                   status: 422,
+                   url: URL.to_string(),
+                   message: Some("Validation Failed".to_string()),
+               }));
+    assert_eq!(response_error(301, URL, ""),
+               Some(Error::GitHubUnexpectedStatus {
+                   status: 301,
+                   url: URL.to_string(),
+                   message: None,
+               }));
+}
+
+
+#[test]
+fn next_page_middle() {
+    assert_eq!(next_page("<https://api.github.com/repositories/71928026/events?page=1>; rel=\"prev\", \
+                          <https://api.github.com/repositories/71928026/events?page=3>; rel=\"next\", \
+                          <https://api.github.com/repositories/71928026/events?page=10>; rel=\"last\", \
+                          <https://api.github.com/repositories/71928026/events?page=1>; rel=\"first\""),
+               Some("https://api.github.com/repositories/71928026/events?page=3".to_string()));
+}
+
+#[test]
+fn next_page_last() {
+    assert_eq!(next_page("<https://api.github.com/repositories/71928026/events?page=9>; rel=\"prev\", \
+                          <https://api.github.com/repositories/71928026/events?page=1>; rel=\"first\""),
+               None);
+}
+
+#[test]
+fn next_page_malformed() {
+    for link in &["",
+                  "https://api.github.com/repositories/71928026/events?page=2; rel=\"next\"",
+                  "<https://api.github.com/repositories/71928026/events?page=2>"] {
+        assert_eq!(next_page(link), None, "{}", link);
+    }
+}
@@ -16,6 +16,7 @@ fn get_data() {
                AppTokens {
                    discord: "EaCdWByA0BTPnlt6OYpnuAc0.cfPzzG.7UuaJIiPatD507FYjgBcHw3Ecob".to_string(),
                    github: "YO4QXSAMh72MivCCOYpRWxNx0ZpEwocF8DM1D130".to_string(),
+                   github_instances: None,
                });
     assert_eq!(String::from_iter(out.iter().map(|&i| i as char)),
                "GitHub OAuth token: Discord bot token: ".to_string());
@@ -0,0 +1,92 @@
+use dishub::ops::locale::{BUILTIN, Catalogue, PluralRule};
+
+
+#[test]
+fn builtin() {
+    for &(name, _) in BUILTIN {
+        let catalogue = Catalogue::builtin(name).unwrap();
+        assert_eq!(catalogue.name, name);
+        assert!(!catalogue.messages.is_empty());
+    }
+    assert_eq!(Catalogue::builtin("en").as_ref(), Some(Catalogue::english()));
+    assert_eq!(Catalogue::default(), *Catalogue::english());
+}
+
+#[test]
+fn builtin_complete() {
+    let polish = Catalogue::builtin("pl").unwrap();
+    for key in Catalogue::english().messages.keys() {
+        assert!(polish.messages.contains_key(key), "{} missing from pl", key);
+    }
+}
+
+#[test]
+fn plural_rule_names() {
+    for rule in &[PluralRule::English, PluralRule::French, PluralRule::Polish, PluralRule::Russian, PluralRule::Czech, PluralRule::Japanese] {
+        assert_eq!(PluralRule::from_name(rule.name()), Some(*rule));
+        assert!(rule.form(0) < rule.forms());
+    }
+}
+
+#[test]
+fn plural_rule_forms() {
+    let counts = [0, 1, 2, 4, 5, 11, 12, 21, 22, 25, 101, 112, 122];
+    assert_eq!(counts.iter().map(|&n| PluralRule::English.form(n)).collect::<Vec<_>>(),
+               vec![1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1]);
+    assert_eq!(counts.iter().map(|&n| PluralRule::French.form(n)).collect::<Vec<_>>(),
+               vec![0, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1]);
+    assert_eq!(counts.iter().map(|&n| PluralRule::Polish.form(n)).collect::<Vec<_>>(),
+               vec![2, 0, 1, 1, 2, 2, 2, 2, 1, 2, 2, 2, 1]);
+    assert_eq!(counts.iter().map(|&n| PluralRule::Russian.form(n)).collect::<Vec<_>>(),
+               vec![2, 0, 1, 1, 2, 2, 2, 0, 1, 2, 0, 2, 1]);
+    assert_eq!(counts.iter().map(|&n| PluralRule::Czech.form(n)).collect::<Vec<_>>(),
+               vec![2, 0, 1, 1, 2, 2, 2, 2, 2, 2, 2, 2, 2]);
+    assert_eq!(counts.iter().map(|&n| PluralRule::Japanese.form(n)).collect::<Vec<_>>(),
+               vec![0; 13]);
+}
+
+#[test]
+fn format() {
+    let english = Catalogue::english();
+    assert_eq!(english.format("more_commits", None, 1, &[("count", &1)]), "and 1 more commit");
+    assert_eq!(english.format("more_commits", None, 12, &[("count", &12)]), "and 12 more commits");
+    assert_eq!(english.format("watch", None, 1, &[("actor", &"liigo"), ("repo", &"{repo}")]), "liigo starred {repo}");
+    assert_eq!(english.format("watch", None, 1, &[("actor", &"liigo")]), "liigo starred {repo}");
+    assert_eq!(english.format("no_such_message", None, 1, &[]), "no_such_message");
+
+    let polish = Catalogue::builtin("pl").unwrap();
+    assert_eq!(polish.format("more_commits", None, 5, &[("count", &5)]), "i 5 commitów więcej");
+    assert_eq!(polish.format("more_commits", None, 22, &[("count", &22)]), "i 22 commity więcej");
+    assert_eq!(polish.format("pull_request_review_thread", Some("unresolved"), 1, &[("actor", &"A"), ("number", &1), ("repo", &"A/b")]),
+               "A oznaczył(a) wątek w #1 w A/b jako nierozwiązany");
+    assert_eq!(polish.format("events_missed", None, 0, &[("subject", &"A/b")]), "Niektóre zdarzenia w A/b zostały przeoczone");
+}
+
+#[test]
+fn fallback() {
+    let catalogue = Catalogue::parse("en-pirate",
+                                     "plural = \"ja\"\n\
+                                      [messages]\n\
+                                      more_commits = [\"and {count} more o' them\"]\n")
+        .unwrap();
+    assert_eq!(catalogue.format("more_commits", None, 3, &[("count", &3)]), "and 3 more o' them");
+    assert_eq!(catalogue.format("watch", None, 1, &[("actor", &"liigo"), ("repo", &"a/b")]), "liigo starred a/b");
+    assert_eq!(catalogue.word("action", "opened"), None);
+}
+
+#[test]
+fn parse_errors() {
+    assert_eq!(Catalogue::parse("x", "[messages]\nwatch = \"\"\n"), Err(vec!["Missing plural rule".to_string()]));
+    assert_eq!(Catalogue::parse("x", "plural = \"tlh\"\n[messages]\n"),
+               Err(vec!["Unknown plural rule \"tlh\"".to_string()]));
+    assert_eq!(Catalogue::parse("x", "plural = \"en\"\n"), Err(vec!["Missing messages table".to_string()]));
+    assert_eq!(Catalogue::parse("x",
+                                "plural = \"en\"\n\
+                                 [messages]\n\
+                                 push = \"{count} commits\"\n\
+                                 watch = [\"a\", \"b\"]\n\
+                                 \"watch.started\" = \"c\"\n\
+                                 \"action.started\" = \"d\"\n"),
+               Err(vec!["Message \"push\" needs 2 forms for plural rule \"en\"".to_string(), "Message \"watch\" isn't a string".to_string()]));
+    assert_eq!(Catalogue::parse("x", "plural = \"en\"\n[messages\n").map_err(|e| e.len()), Err(1));
+}
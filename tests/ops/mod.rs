@@ -5,8 +5,14 @@ use std::env::temp_dir;
 mod init;
 mod feed;
 mod event;
+mod github;
+mod locale;
+mod retry;
+mod proxy;
+mod render;
 mod add_feeds;
 mod app_tokens;
+mod app_config;
 mod unfollow_feeds;
 
 
@@ -0,0 +1,301 @@
+use dishub::ops::render::{COLOUR_OPENED, COLOUR_MERGED, COLOUR_CLOSED, EmbedRenderer, RenderOptions, EmbedAuthor, EmbedField, Renderer, Message, Embed};
+use dishub::ops::{PullRequestAction, IssuesAction, EventPayload, Event};
+use dishub::ops::locale::Catalogue;
+use self::super::TWO_EVENTS;
+use chrono::DateTime;
+use json;
+
+
+static PULL_REQUEST: &'static str = include_str!("../../../test-data/pull_request_payload.json");
+static PULL_REQUEST_DRAFT: &'static str = include_str!("../../../test-data/pull_request_draft_payload.json");
+static RELEASE: &'static str = include_str!("../../../test-data/release_payload.json");
+static PUSH: &'static str = include_str!("../../../test-data/push_payload.json");
+
+
+#[test]
+fn two_events() {
+    let events = Event::parse(TWO_EVENTS).unwrap();
+    assert_eq!(EmbedRenderer.render(&events[0], &RenderOptions::default()),
+               Message::Embed(Embed {
+                   author: Some(EmbedAuthor {
+                       name: "liigo".to_string(),
+                       url: Some("https://github.com/liigo".to_string()),
+                       icon_url: Some("https://avatars.githubusercontent.com/u/346530?".to_string()),
+                   }),
+                   title: "nabijaczleweli/cargo-update".to_string(),
+                   url: Some("https://github.com/nabijaczleweli/cargo-update".to_string()),
+                   description: "liigo opened [#11](<https://github.com/nabijaczleweli/cargo-update/issues/11>) \
+                                 on nabijaczleweli/cargo-update: \"'unknown error occurred': It\"\n\
+                                 https://github.com/nabijaczleweli/cargo-update/issues/11\n\
+                                 \n\
+                                 \\`\\`\\`C:\\\\Users\\\\liigo>cargo install-update\\`\\`\\`"
+                       .to_string(),
+                   colour: Some(COLOUR_OPENED),
+                   fields: vec![],
+                   timestamp: DateTime::parse_from_rfc3339("2016-11-10T00:42:18Z").unwrap(),
+               }));
+    assert_eq!(EmbedRenderer.render(&events[1], &RenderOptions::default()),
+               Message::Embed(Embed {
+                   author: Some(EmbedAuthor {
+                       name: "nabijaczleweli".to_string(),
+                       url: Some("https://github.com/nabijaczleweli".to_string()),
+                       icon_url: Some("https://avatars.githubusercontent.com/u/6709544?".to_string()),
+                   }),
+                   title: "nabijaczleweli/cargo-update".to_string(),
+                   url: Some("https://github.com/nabijaczleweli/cargo-update".to_string()),
+                   description: "nabijaczleweli created comment to [#12](<https://github.com/nabijaczleweli/cargo-update/issues/12>) \
+                                 on nabijaczleweli/cargo-update\n\
+                                 https://github.com/nabijaczleweli/cargo-update/issues/12#issuecomment-259659314"
+                       .to_string(),
+                   colour: None,
+                   fields: vec![],
+                   timestamp: DateTime::parse_from_rfc3339("2016-11-10T10:48:04Z").unwrap(),
+               }));
+}
+
+#[test]
+fn enterprise() {
+    let options = RenderOptions { web_url: "https://ghe.example.com".to_string(), ..RenderOptions::default() };
+    match EmbedRenderer.render(&Event::parse(TWO_EVENTS).unwrap()[0], &options) {
+        Message::Embed(embed) => {
+            assert_eq!(embed.author.unwrap().url, Some("https://ghe.example.com/liigo".to_string()));
+            assert_eq!(embed.url, Some("https://ghe.example.com/nabijaczleweli/cargo-update".to_string()));
+            assert!(embed.description.contains("\nhttps://ghe.example.com/nabijaczleweli/cargo-update/issues/11\n"));
+        }
+        msg => panic!("{:?}", msg),
+    }
+}
+
+#[test]
+fn trusted() {
+    let options = RenderOptions { trusted: vec!["nabijaczleweli/cargo-update".to_string()], ..RenderOptions::default() };
+    match EmbedRenderer.render(&Event::parse(TWO_EVENTS).unwrap()[0], &options) {
+        Message::Embed(embed) => assert!(embed.description.ends_with("\n\n```C:\\Users\\liigo>cargo install-update```")),
+        msg => panic!("{:?}", msg),
+    }
+}
+
+#[test]
+fn pull_request() {
+    let mut ev = Event::parse(TWO_EVENTS).unwrap().remove(0);
+    ev.payload = EventPayload::from("PullRequestEvent", &json::parse(PULL_REQUEST_DRAFT).unwrap()).unwrap();
+
+    match EmbedRenderer.render(&ev, &RenderOptions::default()) {
+        Message::Embed(embed) => {
+            assert_eq!(embed.description,
+                       "liigo opened draft [#214](<https://github.com/nabijaczleweli/cargo-update/issues/214>) \
+                       from git-packages into master on nabijaczleweli/cargo-update: \"Install git packages with -g\" \
+                        (+120/−4)\n\
+                        https://github.com/nabijaczleweli/cargo-update/pull/214\n\
+                        \n\
+                        Closes [#213](<https://github.com/nabijaczleweli/cargo-update/issues/213>).");
+            assert_eq!(embed.colour, Some(COLOUR_OPENED));
+            assert_eq!(embed.fields,
+                       vec![EmbedField {
+                                name: "Branches".to_string(),
+                                value: "git-packages → master".to_string(),
+                                inline: true,
+                            },
+                            EmbedField {
+                                name: "Labels".to_string(),
+                                value: "enhancement".to_string(),
+                                inline: true,
+                            },
+                            EmbedField {
+                                name: "Assignees".to_string(),
+                                value: "nabijaczleweli".to_string(),
+                                inline: true,
+                            },
+                            EmbedField {
+                                name: "Milestone".to_string(),
+                                value: "v11.0.0".to_string(),
+                                inline: true,
+                            }]);
+        }
+        msg => panic!("{:?}", msg),
+    }
+}
+
+#[test]
+fn pull_request_merged() {
+    let mut ev = Event::parse(TWO_EVENTS).unwrap().remove(0);
+    ev.payload = EventPayload::from("PullRequestEvent", &json::parse(PULL_REQUEST).unwrap()).unwrap();
+
+    match EmbedRenderer.render(&ev, &RenderOptions::default()) {
+        Message::Embed(embed) => {
+            assert_eq!(embed.colour, Some(COLOUR_MERGED));
+            assert_eq!(embed.fields,
+                       vec![EmbedField {
+                                name: "Branches".to_string(),
+                                value: "master → master".to_string(),
+                                inline: true,
+                            },
+                            EmbedField {
+                                name: "Merged by".to_string(),
+                                value: "stephenmathieson".to_string(),
+                                inline: true,
+                            }]);
+        }
+        msg => panic!("{:?}", msg),
+    }
+}
+
+#[test]
+fn release() {
+    let mut ev = Event::parse(TWO_EVENTS).unwrap().remove(0);
+    ev.payload = EventPayload::from("ReleaseEvent", &json::parse(RELEASE).unwrap()).unwrap();
+
+    match EmbedRenderer.render(&ev, &RenderOptions::default()) {
+        Message::Embed(embed) => {
+            assert_eq!(embed.description,
+                       "liigo published v0.5.0 from master\n\
+                        https://github.com/nabijaczleweli/cargo-update/releases/tag/v0.5.0");
+            assert_eq!(embed.colour, None);
+            assert_eq!(embed.fields, vec![]);
+        }
+        msg => panic!("{:?}", msg),
+    }
+}
+
+#[test]
+fn push() {
+    let mut ev = Event::parse(TWO_EVENTS).unwrap().remove(0);
+    ev.payload = EventPayload::from("PushEvent", &json::parse(PUSH).unwrap()).unwrap();
+
+    match EmbedRenderer.render(&ev, &RenderOptions::default()) {
+        Message::Embed(embed) => {
+            assert_eq!(embed.colour, None);
+            assert_eq!(embed.description,
+                       "liigo pushed 1 commit by Nabijaczleweli Autouploader Bot to doc in nabijaczleweli/cargo-update\n\
+                        [`4665079`](https://github.com/nabijaczleweli/cargo-update/commit/46650797f4aec4d373c647062eb3df288ee7b8f2) \
+                        Update docs for commits [f69e263c6caf](<https://github.com/nabijaczleweli/cargo-update/commit/f69e263c6caf>)...\
+                        [40e07efdf879](<https://github.com/nabijaczleweli/cargo-update/commit/40e07efdf879>) \
+                        - Nabijaczleweli Autouploader Bot\n\
+                        [`8eab689`](https://github.com/nabijaczleweli/cargo-update/commit/8eab6892203102e66a4b1eed26abc5a24cad8afe) \
+                        Update manual for commits [1c8725dc2222](<https://github.com/nabijaczleweli/cargo-update/commit/1c8725dc2222>)...\
+                        [58998fadba13](<https://github.com/nabijaczleweli/cargo-update/commit/58998fadba13>) \
+                        - Nabijaczleweli Autouploader Bot\n\
+                        https://github.com/nabijaczleweli/cargo-update/compare/4d28f4b488f04c35135af7576ff5fd1f0ce53c7a...\
+                        46650797f4aec4d373c647062eb3df288ee7b8f2");
+            assert_eq!(embed.fields,
+                       vec![EmbedField {
+                                name: "Branch".to_string(),
+                                value: "doc".to_string(),
+                                inline: true,
+                            }]);
+        }
+        msg => panic!("{:?}", msg),
+    }
+}
+
+#[test]
+fn localised() {
+    let mut ev = Event::parse(TWO_EVENTS).unwrap().remove(0);
+    ev.payload = EventPayload::from("PushEvent", &json::parse(PUSH).unwrap()).unwrap();
+
+    let options = RenderOptions {
+        max_commits: 1,
+        catalogue: Catalogue::builtin("pl").unwrap(),
+        ..RenderOptions::default()
+    };
+    match EmbedRenderer.render(&ev, &options) {
+        Message::Embed(embed) => {
+            assert_eq!(embed.description,
+                       "liigo wypchnął(-ęła) 1 commit autorstwa Nabijaczleweli Autouploader Bot do doc w nabijaczleweli/cargo-update\n\
+                        [`4665079`](https://github.com/nabijaczleweli/cargo-update/commit/46650797f4aec4d373c647062eb3df288ee7b8f2) \
+                        Update docs for commits [f69e263c6caf](<https://github.com/nabijaczleweli/cargo-update/commit/f69e263c6caf>)...\
+                        [40e07efdf879](<https://github.com/nabijaczleweli/cargo-update/commit/40e07efdf879>) \
+                        - Nabijaczleweli Autouploader Bot\n\
+                        i 1 commit więcej\n\
+                        https://github.com/nabijaczleweli/cargo-update/compare/4d28f4b488f04c35135af7576ff5fd1f0ce53c7a...\
+                        46650797f4aec4d373c647062eb3df288ee7b8f2");
+            assert_eq!(embed.fields,
+                       vec![EmbedField {
+                                name: "Gałąź".to_string(),
+                                value: "doc".to_string(),
+                                inline: true,
+                            }]);
+        }
+        msg => panic!("{:?}", msg),
+    }
+}
+
+#[test]
+fn colour() {
+    let mut ev = Event::parse(TWO_EVENTS).unwrap().remove(0);
+    assert_eq!(EmbedRenderer::colour(&ev), Some(COLOUR_OPENED));
+
+    if let EventPayload::Issues { ref mut action, .. } = ev.payload {
+        *action = IssuesAction::Closed;
+    }
+    assert_eq!(EmbedRenderer::colour(&ev), Some(COLOUR_CLOSED));
+
+    if let EventPayload::Issues { ref mut action, .. } = ev.payload {
+        *action = IssuesAction::Labeled;
+    }
+    assert_eq!(EmbedRenderer::colour(&ev), None);
+
+    ev.payload = EventPayload::from("PullRequestEvent", &json::parse(PULL_REQUEST_DRAFT).unwrap()).unwrap();
+    if let EventPayload::PullRequest { ref mut action, ref mut merged, .. } = ev.payload {
+        *action = PullRequestAction::Closed;
+        *merged = true;
+    }
+    assert_eq!(EmbedRenderer::colour(&ev), Some(COLOUR_MERGED));
+
+    if let EventPayload::PullRequest { ref mut merged, .. } = ev.payload {
+        *merged = false;
+    }
+    assert_eq!(EmbedRenderer::colour(&ev), Some(COLOUR_CLOSED));
+}
+
+#[test]
+fn to_json() {
+    let embed = Embed {
+        author: None,
+        title: "nabijaczleweli/cargo-update".to_string(),
+        url: None,
+        description: "".to_string(),
+        colour: None,
+        fields: vec![],
+        timestamp: DateTime::parse_from_rfc3339("2016-11-10T08:42:18Z").unwrap(),
+    };
+    assert_eq!(embed.to_json(),
+               object!{
+                   "title" => "nabijaczleweli/cargo-update",
+                   "timestamp" => "2016-11-10T08:42:18+00:00",
+               });
+
+    let embed = Embed {
+        author: Some(EmbedAuthor {
+            name: "liigo".to_string(),
+            url: None,
+            icon_url: Some("https://avatars.githubusercontent.com/u/346530?".to_string()),
+        }),
+        url: Some("https://github.com/nabijaczleweli/cargo-update".to_string()),
+        description: "liigo starred nabijaczleweli/cargo-update".to_string(),
+        colour: Some(COLOUR_MERGED),
+        fields: vec![EmbedField {
+                         name: "Branch".to_string(),
+                         value: "master".to_string(),
+                         inline: true,
+                     }],
+        ..embed
+    };
+    assert_eq!(embed.to_json(),
+               object!{
+                   "title" => "nabijaczleweli/cargo-update",
+                   "timestamp" => "2016-11-10T08:42:18+00:00",
+                   "author" => object!{
+                       "name" => "liigo",
+                       "icon_url" => "https://avatars.githubusercontent.com/u/346530?",
+                   },
+                   "url" => "https://github.com/nabijaczleweli/cargo-update",
+                   "description" => "liigo starred nabijaczleweli/cargo-update",
+                   "color" => 0x6F42C1,
+                   "fields" => vec![object!{
+                       "name" => "Branch",
+                       "value" => "master",
+                       "inline" => true,
+                   }],
+               });
+}
@@ -0,0 +1,63 @@
+use dishub::ops::render::{PlainRenderer, RenderOptions, Renderer, Message, escape_markdown};
+use dishub::ops::Event;
+use self::super::TWO_EVENTS;
+
+
+#[test]
+fn markdown() {
+    assert_eq!(escape_markdown("*bold* _italic_ ~~struck~~ `code` ||spoiler|| [link](https://example.com)"),
+               "\\*bold\\* \\_italic\\_ \\~\\~struck\\~\\~ \\`code\\` \\|\\|spoiler\\|\\| \\[link\\](https://example.com)");
+    assert_eq!(escape_markdown("C:\\Users"), "C:\\\\Users");
+}
+
+#[test]
+fn line_starts() {
+    assert_eq!(escape_markdown("# Heading\n> quote\n  - item\n+ item\nnot # a > heading - or + list"),
+               "\\# Heading\n\\> quote\n  \\- item\n\\+ item\nnot # a > heading - or + list");
+}
+
+#[test]
+fn mentions() {
+    assert_eq!(escape_markdown("@everyone @here"), "@\u{200B}everyone @\u{200B}here");
+    assert_eq!(escape_markdown("<@123> <@&456> <#789>"), "\\<@\u{200B}123> \\<@\u{200B}&456> \\<#789>");
+}
+
+#[test]
+fn plain() {
+    assert_eq!(escape_markdown("liigo opened #11 on nabijaczleweli/cargo-update: \"'unknown error occurred': It\""),
+               "liigo opened #11 on nabijaczleweli/cargo-update: \"'unknown error occurred': It\"");
+    assert_eq!(escape_markdown("zażółć gęślą jaźń"), "zażółć gęślą jaźń");
+}
+
+#[test]
+fn escaped() {
+    let mut ev = Event::parse(TWO_EVENTS).unwrap().remove(0);
+    ev.repo.name = "nabijaczleweli/cargo_update".to_string();
+
+    assert_eq!(PlainRenderer.render(&ev, &RenderOptions::default()),
+               Message::Text("10.11.2016 12:42:18 AM: liigo opened #11 on nabijaczleweli/cargo\\_update: \"'unknown error occurred': It\"\n\
+                              <https://github.com/nabijaczleweli/cargo_update/issues/11>"
+                   .to_string()));
+}
+
+#[test]
+fn trusted() {
+    let mut ev = Event::parse(TWO_EVENTS).unwrap().remove(0);
+    ev.repo.name = "nabijaczleweli/cargo_update".to_string();
+
+    for trusted in &["nabijaczleweli/cargo_update", "Nabijaczleweli/Cargo_Update", "nabijaczleweli/*"] {
+        let options = RenderOptions { trusted: vec![trusted.to_string()], ..RenderOptions::default() };
+        assert_eq!(PlainRenderer.render(&ev, &options),
+                   Message::Text("10.11.2016 12:42:18 AM: liigo opened #11 on nabijaczleweli/cargo_update: \"'unknown error occurred': It\"\n\
+                                  <https://github.com/nabijaczleweli/cargo_update/issues/11>"
+                       .to_string()));
+    }
+}
+
+#[test]
+fn untrusted() {
+    for trusted in &["nabijaczleweli/cargo-update", "nabijaczleweli/cargo", "nabijaczleweli/cargo_update/*", "nabijacz/*"] {
+        let options = RenderOptions { trusted: vec![trusted.to_string()], ..RenderOptions::default() };
+        assert!(!options.trusts("nabijaczleweli/cargo_update"));
+    }
+}
@@ -0,0 +1,69 @@
+use dishub::ops::render::{HtmlRenderer, RenderOptions, Renderer, Message};
+use dishub::ops::{EventPayload, Event};
+use self::super::TWO_EVENTS;
+use json;
+
+
+static PUSH: &'static str = include_str!("../../../test-data/push_payload.json");
+
+
+#[test]
+fn two_events() {
+    let events = Event::parse(TWO_EVENTS).unwrap();
+    assert_eq!(events.iter().map(|ev| HtmlRenderer.render(ev, &RenderOptions::default())).collect::<Vec<_>>(),
+               vec![Message::Text("<p><time datetime=\"2016-11-10T00:42:18+00:00\">10.11.2016 12:42:18 AM</time>: \
+                                  liigo opened #11 on nabijaczleweli/cargo-update: &quot;&#39;unknown error occurred&#39;: It&quot;</p>\n\
+                                  <ul>\n  \
+                                  <li><a href=\"https://github.com/nabijaczleweli/cargo-update/issues/11\">\
+                                  https://github.com/nabijaczleweli/cargo-update/issues/11</a></li>\n\
+                                  </ul>"
+                    .to_string()),
+                    Message::Text("<p><time datetime=\"2016-11-10T10:48:04+00:00\">10.11.2016 10:48:04 AM</time>: \
+                                  nabijaczleweli created comment to #12 on nabijaczleweli/cargo-update</p>\n\
+                                  <ul>\n  \
+                                  <li><a href=\"https://github.com/nabijaczleweli/cargo-update/issues/12#issuecomment-259659314\">\
+                                  https://github.com/nabijaczleweli/cargo-update/issues/12#issuecomment-259659314</a></li>\n\
+                                  </ul>"
+                    .to_string())]);
+}
+
+#[test]
+fn push() {
+    let mut ev = Event::parse(TWO_EVENTS).unwrap().remove(0);
+    ev.payload = EventPayload::from("PushEvent", &json::parse(PUSH).unwrap()).unwrap();
+
+    let options = RenderOptions { distinct_commits_only: true, ..RenderOptions::default() };
+    assert_eq!(HtmlRenderer.render(&ev, &options),
+               Message::Text("<p><time datetime=\"2016-11-10T00:42:18+00:00\">10.11.2016 12:42:18 AM</time>: \
+                              liigo pushed 1 commit by Nabijaczleweli Autouploader Bot to doc in nabijaczleweli/cargo-update</p>\n\
+                              <ul class=\"commits\">\n  \
+                                <li><a href=\"https://github.com/nabijaczleweli/cargo-update/commit/46650797f4aec4d373c647062eb3df288ee7b8f2\">\
+                                  <code>4665079</code></a> Update docs for commits f69e263c6caf...40e07efdf879 - Nabijaczleweli Autouploader Bot</li>\n\
+                              </ul>\n\
+                              <ul>\n  \
+                                <li><a href=\"https://github.com/nabijaczleweli/cargo-update/compare/4d28f4b488f04c35135af7576ff5fd1f0ce53c7a...\
+                                  46650797f4aec4d373c647062eb3df288ee7b8f2\">\
+                                  https://github.com/nabijaczleweli/cargo-update/compare/4d28f4b488f04c35135af7576ff5fd1f0ce53c7a...\
+                                  46650797f4aec4d373c647062eb3df288ee7b8f2</a></li>\n\
+                              </ul>"
+                   .to_string()));
+}
+
+#[test]
+fn mentions() {
+    let mut ev = Event::parse(TWO_EVENTS).unwrap().remove(0);
+    if let EventPayload::Issues { ref mut title, .. } = ev.payload {
+        *title = "@everyone <@80351110224678912>".to_string();
+    }
+
+    let summary = |options: &RenderOptions| match HtmlRenderer.render(&ev, options) {
+        Message::Text(html) => html.lines().next().unwrap().to_string(),
+        Message::Embed(_) => panic!(),
+    };
+    assert_eq!(summary(&RenderOptions::default()),
+               "<p><time datetime=\"2016-11-10T00:42:18+00:00\">10.11.2016 12:42:18 AM</time>: \
+                liigo opened #11 on nabijaczleweli/cargo-update: &quot;@\u{200b}everyone &lt;@\u{200b}80351110224678912&gt;&quot;</p>");
+    assert_eq!(summary(&RenderOptions { trusted: vec!["nabijaczleweli/*".to_string()], ..RenderOptions::default() }),
+               "<p><time datetime=\"2016-11-10T00:42:18+00:00\">10.11.2016 12:42:18 AM</time>: \
+                liigo opened #11 on nabijaczleweli/cargo-update: &quot;@everyone &lt;@80351110224678912&gt;&quot;</p>");
+}
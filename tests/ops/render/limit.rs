@@ -0,0 +1,145 @@
+use dishub::ops::render::{EMBED_DESCRIPTION_LIMIT, MESSAGE_LIMIT, MarkdownRenderer, PlainRenderer, RenderOptions, EmbedRenderer, Renderer, Message,
+                          split_message, excerpt};
+use dishub::ops::{GollumPayload, EventPayload, Event, start_daemon};
+use self::super::TWO_EVENTS;
+
+
+#[test]
+fn excerpt_short() {
+    assert_eq!(excerpt("  Fits exactly.\n", 12), "Fits…");
+    assert_eq!(excerpt("  Fits exactly.\n", 13), "Fits exactly.");
+}
+
+#[test]
+fn excerpt_no_whitespace() {
+    assert_eq!(excerpt("abcdefghijklmnopqrstuvwxyz", 15), "abcdefghij…");
+}
+
+#[test]
+fn excerpt_combining() {
+    // "e" followed by a combining acute accent, and a family emoji joined with ZWJs
+    assert_eq!(excerpt("abcdefghie\u{301}klmnopqrstuvwxyz", 15), "abcdefghi…");
+    assert_eq!(excerpt("abcdefgh\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}xyzxyzxyz", 14), "abcdefgh…");
+}
+
+#[test]
+fn excerpt_escape() {
+    assert_eq!(excerpt("abcdefghi\\*klmnopqrstuvwxyz", 15), "abcdefghi…");
+}
+
+#[test]
+fn excerpt_code_block() {
+    let ex = excerpt("```\nfn main() {\n    println!(\"Hello, world!\");\n}\n```", 30);
+    assert_eq!(ex, "```\nfn main() {…\n```");
+    assert!(ex.chars().count() <= 30);
+}
+
+#[test]
+fn split_long_line() {
+    let messages = split_message(&"word ".repeat(1000), MESSAGE_LIMIT);
+    assert_eq!(messages.len(), 3);
+    assert!(messages.iter().all(|m| m.chars().count() <= MESSAGE_LIMIT));
+    assert_eq!(messages.join(" ").split_whitespace().count(), 1000);
+}
+
+#[test]
+fn split_no_spaces() {
+    let messages = split_message(&"ż".repeat(4500), MESSAGE_LIMIT);
+    assert_eq!(messages.iter().map(|m| m.chars().count()).collect::<Vec<_>>(), vec![2000, 2000, 500]);
+}
+
+#[test]
+fn split_code_block() {
+    let text = format!("Output:\n```text\n{}```", "line\n".repeat(600));
+    let messages = split_message(&text, MESSAGE_LIMIT);
+    assert_eq!(messages.len(), 2);
+    for msg in &messages {
+        assert!(msg.chars().count() <= MESSAGE_LIMIT);
+        assert_eq!(msg.matches("```").count(), 2);
+    }
+    assert!(messages[1].starts_with("```text\nline\n"));
+    assert_eq!(messages.iter().map(|m| m.matches("line").count()).sum::<usize>(), 600);
+}
+
+#[test]
+fn render_split() {
+    let mut ev = Event::parse(TWO_EVENTS).unwrap().remove(1);
+    ev.payload = EventPayload::Gollum {
+        pages: (0..100)
+            .map(|i| {
+                GollumPayload {
+                    page_name: format!("Page-{}", i),
+                    title: format!("Page {}", i),
+                    action: "edited".to_string(),
+                    sha: "bf842e3aa1fad56ef96e0256eccb3d9d8f288aab".to_string(),
+                    html_url: format!("/nabijaczleweli/cargo-update/wiki/Page-{}", i),
+                }
+            })
+            .collect(),
+    };
+
+    let messages = start_daemon::render_messages(&PlainRenderer, &RenderOptions::default(), &[ev]);
+    assert!(messages.len() > 1);
+    for msg in messages {
+        match msg {
+            Message::Text(text) => assert!(text.chars().count() <= MESSAGE_LIMIT),
+            msg => panic!("{:?}", msg),
+        }
+    }
+}
+
+#[test]
+fn plain_excerpt() {
+    let options = RenderOptions { excerpt: Some(300), ..RenderOptions::default() };
+    assert_eq!(PlainRenderer.render(&Event::parse(TWO_EVENTS).unwrap()[1], &options),
+               Message::Text("10.11.2016 10:48:04 AM: nabijaczleweli created comment to #12 on nabijaczleweli/cargo-update\n\
+                              Your \\`.cargo.toml\\`?\n\
+                              <https://github.com/nabijaczleweli/cargo-update/issues/12#issuecomment-259659314>"
+                   .to_string()));
+}
+
+#[test]
+fn markdown_excerpt() {
+    let options = RenderOptions { excerpt: Some(300), ..RenderOptions::default() };
+    assert_eq!(MarkdownRenderer.render(&Event::parse(TWO_EVENTS).unwrap()[1], &options),
+               Message::Text("*10.11.2016 10:48:04 AM*\n\
+                              **nabijaczleweli created comment to [#12](<https://github.com/nabijaczleweli/cargo-update/issues/12>) \
+                              on nabijaczleweli/cargo-update**\n\
+                              > Your \\`.cargo.toml\\`?\n\
+                              <https://github.com/nabijaczleweli/cargo-update/issues/12#issuecomment-259659314>"
+                   .to_string()));
+}
+
+#[test]
+fn embed_excerpt() {
+    let options = RenderOptions { excerpt: Some(10), ..RenderOptions::default() };
+    match EmbedRenderer.render(&Event::parse(TWO_EVENTS).unwrap()[0], &options) {
+        Message::Embed(embed) => assert!(embed.description.ends_with("\n\n\\`\\`\\`C:…\n\\`\\`\\`")),
+        msg => panic!("{:?}", msg),
+    }
+    match EmbedRenderer.render(&Event::parse(TWO_EVENTS).unwrap()[1], &options) {
+        Message::Embed(embed) => assert!(embed.description.ends_with("\n\nYour…")),
+        msg => panic!("{:?}", msg),
+    }
+}
+
+#[test]
+fn embed_limit() {
+    let mut ev = Event::parse(TWO_EVENTS).unwrap().remove(0);
+    if let EventPayload::Issues { ref mut body, .. } = ev.payload {
+        *body = "long ".repeat(2000);
+    }
+
+    match EmbedRenderer.render(&ev, &RenderOptions::default()) {
+        Message::Embed(embed) => {
+            assert!(embed.description.chars().count() <= EMBED_DESCRIPTION_LIMIT);
+            assert!(embed.description.ends_with(" long…"));
+        }
+        msg => panic!("{:?}", msg),
+    }
+}
+
+#[test]
+fn excerpt_zwj_sequence() {
+    assert_eq!(excerpt("abcdefg\u{1F468}\u{200D}\u{1F469}xyzxyzxyz", 14), "abcdefg…");
+}
@@ -0,0 +1,80 @@
+use dishub::ops::render::{RenderOptions, linkify_at, linkify};
+use dishub::ops::{Event, Repo};
+use self::super::TWO_EVENTS;
+
+
+#[test]
+fn issues() {
+    assert_eq!(linkify("#12 and rust-lang/cargo#45,#13", &repo(), false),
+               "[#12](<https://github.com/nabijaczleweli/cargo-update/issues/12>) and \
+                [rust-lang/cargo#45](<https://github.com/rust-lang/cargo/issues/45>),\
+                [#13](<https://github.com/nabijaczleweli/cargo-update/issues/13>)");
+}
+
+#[test]
+fn users() {
+    assert_eq!(linkify("cc @nabijaczleweli @rust-lang-nursery", &repo(), false),
+               "cc [@nabijaczleweli](<https://github.com/nabijaczleweli>) [@rust-lang-nursery](<https://github.com/rust-lang-nursery>)");
+}
+
+#[test]
+fn commits() {
+    assert_eq!(linkify("Reverts 8eab689 and rust-lang/cargo@46650797f4aec4d373c647062eb3df288ee7b8f2", &repo(), false),
+               "Reverts [8eab689](<https://github.com/nabijaczleweli/cargo-update/commit/8eab689>) and \
+                [rust-lang/cargo@46650797f4aec4d373c647062eb3df288ee7b8f2]\
+                (<https://github.com/rust-lang/cargo/commit/46650797f4aec4d373c647062eb3df288ee7b8f2>)");
+    assert_eq!(linkify("4d28f4b...8eab689", &repo(), false),
+               "[4d28f4b](<https://github.com/nabijaczleweli/cargo-update/commit/4d28f4b>)...\
+                [8eab689](<https://github.com/nabijaczleweli/cargo-update/commit/8eab689>)");
+}
+
+#[test]
+fn not_references() {
+    for text in &["deadbeef and 1234567 aren't commits",
+                  "issue#12, a@example.com, &#123;",
+                  "https://github.com/nabijaczleweli/cargo-update/issues/12#issuecomment-259659314",
+                  "example.com/#12 and 8eab689x",
+                  "`#12` and ``@nabijaczleweli `8eab689` ``",
+                  "```\ncargo install-update #12\n```"] {
+        assert_eq!(linkify(text, &repo(), false), *text);
+    }
+}
+
+#[test]
+fn escaped() {
+    assert_eq!(linkify("- @every_one #12\n- fixed_it", &repo(), true),
+               "\\- @\u{200B}every\\_one [#12](<https://github.com/nabijaczleweli/cargo-update/issues/12>)\n\
+                \\- fixed\\_it");
+    assert_eq!(linkify("#12 - see `#13`", &repo(), true),
+               "[#12](<https://github.com/nabijaczleweli/cargo-update/issues/12>) - see \\`#13\\`");
+}
+
+#[test]
+fn options() {
+    let ev = Event::parse(TWO_EVENTS).unwrap().remove(0);
+    assert_eq!(RenderOptions::default().linkify(&ev, "**#12**"),
+               "\\*\\*[#12](<https://github.com/nabijaczleweli/cargo-update/issues/12>)\\*\\*");
+
+    let options = RenderOptions { trusted: vec!["nabijaczleweli/*".to_string()], ..RenderOptions::default() };
+    assert_eq!(options.linkify(&ev, "**#12**"), "**[#12](<https://github.com/nabijaczleweli/cargo-update/issues/12>)**");
+}
+
+#[test]
+fn enterprise() {
+    assert_eq!(linkify_at("@nabijaczleweli fixed #12 in 8eab689", &repo(), "https://ghe.example.com", false),
+               "[@nabijaczleweli](<https://ghe.example.com/nabijaczleweli>) fixed \
+                [#12](<https://ghe.example.com/nabijaczleweli/cargo-update/issues/12>) in \
+                [8eab689](<https://ghe.example.com/nabijaczleweli/cargo-update/commit/8eab689>)");
+
+    let ev = Event::parse(TWO_EVENTS).unwrap().remove(0);
+    let options = RenderOptions { web_url: "https://ghe.example.com".to_string(), ..RenderOptions::default() };
+    assert_eq!(options.linkify(&ev, "#12"), "[#12](<https://ghe.example.com/nabijaczleweli/cargo-update/issues/12>)");
+}
+
+
+fn repo() -> Repo {
+    Repo {
+        id: 71928026,
+        name: "nabijaczleweli/cargo-update".to_string(),
+    }
+}
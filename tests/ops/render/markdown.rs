@@ -0,0 +1,74 @@
+use dishub::ops::render::{MarkdownRenderer, RenderOptions, TimeFormat, TimeStyle, Renderer, Timezone, Message, Preview};
+use dishub::ops::{EventPayload, Event};
+use self::super::TWO_EVENTS;
+use std::collections::BTreeMap;
+use json;
+
+
+static PUSH: &'static str = include_str!("../../../test-data/push_payload.json");
+
+
+#[test]
+fn two_events() {
+    let events = Event::parse(TWO_EVENTS).unwrap();
+    assert_eq!(events.iter().map(|ev| MarkdownRenderer.render(ev, &RenderOptions::default())).collect::<Vec<_>>(),
+               vec![Message::Text("*10.11.2016 12:42:18 AM*\n\
+                                  **liigo opened [#11](<https://github.com/nabijaczleweli/cargo-update/issues/11>) \
+                                  on nabijaczleweli/cargo-update: \"'unknown error occurred': It\"**\n\
+                                  <https://github.com/nabijaczleweli/cargo-update/issues/11>"
+                    .to_string()),
+                    Message::Text("*10.11.2016 10:48:04 AM*\n\
+                                  **nabijaczleweli created comment to [#12](<https://github.com/nabijaczleweli/cargo-update/issues/12>) \
+                                  on nabijaczleweli/cargo-update**\n\
+                                  <https://github.com/nabijaczleweli/cargo-update/issues/12#issuecomment-259659314>"
+                    .to_string())]);
+}
+
+#[test]
+fn discord_time() {
+    let options = RenderOptions {
+        time: TimeFormat {
+            timezone: Timezone::Original,
+            style: TimeStyle::Discord('R'),
+        },
+        ..RenderOptions::default()
+    };
+    assert_eq!(MarkdownRenderer.render(&Event::parse(TWO_EVENTS).unwrap()[1], &options),
+               Message::Text("*<t:1478774884:R>*\n\
+                              **nabijaczleweli created comment to [#12](<https://github.com/nabijaczleweli/cargo-update/issues/12>) \
+                              on nabijaczleweli/cargo-update**\n\
+                              <https://github.com/nabijaczleweli/cargo-update/issues/12#issuecomment-259659314>"
+                   .to_string()));
+}
+
+#[test]
+fn push() {
+    let mut ev = Event::parse(TWO_EVENTS).unwrap().remove(0);
+    ev.payload = EventPayload::from("PushEvent", &json::parse(PUSH).unwrap()).unwrap();
+
+    let options = RenderOptions { max_commits: 1, ..RenderOptions::default() };
+    assert_eq!(MarkdownRenderer.render(&ev, &options),
+               Message::Text("*10.11.2016 12:42:18 AM*\n\
+                              **liigo pushed 1 commit by Nabijaczleweli Autouploader Bot to doc in nabijaczleweli/cargo-update**\n\
+                              - [`4665079`](<https://github.com/nabijaczleweli/cargo-update/commit/46650797f4aec4d373c647062eb3df288ee7b8f2>) \
+                              Update docs for commits [f69e263c6caf](<https://github.com/nabijaczleweli/cargo-update/commit/f69e263c6caf>)...\
+                              [40e07efdf879](<https://github.com/nabijaczleweli/cargo-update/commit/40e07efdf879>) \
+                              - Nabijaczleweli Autouploader Bot\n\
+                              *and 1 more commit*\n\
+                              <https://github.com/nabijaczleweli/cargo-update/compare/4d28f4b488f04c35135af7576ff5fd1f0ce53c7a...\
+                              46650797f4aec4d373c647062eb3df288ee7b8f2>"
+                   .to_string()));
+}
+
+#[test]
+fn previews() {
+    let mut previews = BTreeMap::new();
+    previews.insert("IssueComment".to_string(), Preview::Nth(0));
+    let options = RenderOptions { previews: previews, ..RenderOptions::default() };
+    assert_eq!(MarkdownRenderer.render(&Event::parse(TWO_EVENTS).unwrap()[1], &options),
+               Message::Text("*10.11.2016 10:48:04 AM*\n\
+                              **nabijaczleweli created comment to [#12](<https://github.com/nabijaczleweli/cargo-update/issues/12>) \
+                              on nabijaczleweli/cargo-update**\n\
+                              https://github.com/nabijaczleweli/cargo-update/issues/12#issuecomment-259659314"
+                   .to_string()));
+}
@@ -0,0 +1,60 @@
+use dishub::ops::render::{PlainRenderer, RenderOptions, Renderer, Message, Format};
+use dishub::ops::Event;
+
+mod html;
+mod time;
+mod plain;
+mod embed;
+mod link;
+mod limit;
+mod escape;
+mod markdown;
+mod template;
+
+
+static TWO_EVENTS: &'static str = include_str!("../../../test-data/two_events.json");
+
+
+#[test]
+fn format_names() {
+    for fmt in &[Format::Plain, Format::Markdown, Format::Html, Format::Embed] {
+        assert_eq!(Format::from_name(fmt.name()), Some(*fmt));
+    }
+}
+
+#[test]
+fn format_unknown() {
+    assert_eq!(Format::from_name(""), None);
+    assert_eq!(Format::from_name("Plain"), None);
+    assert_eq!(Format::from_name("discord"), None);
+}
+
+#[test]
+fn format_renderer() {
+    let events = Event::parse(TWO_EVENTS).unwrap();
+    for fmt in &[Format::Plain, Format::Markdown, Format::Html, Format::Embed] {
+        let renderer = fmt.renderer();
+        assert_eq!(events.iter().map(|ev| renderer.render(ev, &RenderOptions::default())).collect::<Vec<_>>(),
+                   match *fmt {
+                       Format::Plain => events.iter().map(|ev| PlainRenderer.render(ev, &RenderOptions::default())).collect::<Vec<_>>(),
+                       Format::Markdown => events.iter().map(|ev| ::dishub::ops::render::MarkdownRenderer.render(ev, &RenderOptions::default())).collect(),
+                       Format::Html => events.iter().map(|ev| ::dishub::ops::render::HtmlRenderer.render(ev, &RenderOptions::default())).collect(),
+                       Format::Embed => events.iter().map(|ev| ::dishub::ops::render::EmbedRenderer.render(ev, &RenderOptions::default())).collect(),
+                   });
+    }
+}
+
+#[test]
+fn custom() {
+    struct LoginRenderer;
+
+    impl Renderer for LoginRenderer {
+        fn render(&self, ev: &Event, _: &RenderOptions) -> Message {
+            Message::Text(ev.actor.login.clone())
+        }
+    }
+
+    let renderer: Box<Renderer> = Box::new(LoginRenderer);
+    assert_eq!(Event::parse(TWO_EVENTS).unwrap().iter().map(|ev| renderer.render(ev, &RenderOptions::default())).collect::<Vec<_>>(),
+               vec![Message::Text("liigo".to_string()), Message::Text("nabijaczleweli".to_string())]);
+}
@@ -0,0 +1,105 @@
+use dishub::ops::render::{PlainRenderer, RenderOptions, TimeFormat, TimeStyle, Renderer, Timezone, Message, Preview};
+use dishub::ops::start_daemon::post_text;
+use dishub::ops::{EventPayload, Event};
+use self::super::TWO_EVENTS;
+use std::collections::BTreeMap;
+use json;
+
+
+static PUSH: &'static str = include_str!("../../../test-data/push_payload.json");
+static GOLLUM: &'static str = include_str!("../../../test-data/gollum_payload.json");
+
+
+#[test]
+fn two_events() {
+    let events = Event::parse(TWO_EVENTS).unwrap();
+    assert_eq!(events.iter().map(|ev| PlainRenderer.render(ev, &RenderOptions::default())).collect::<Vec<_>>(),
+               vec![Message::Text("10.11.2016 12:42:18 AM: liigo opened #11 on nabijaczleweli/cargo-update: \"'unknown error occurred': It\"\n\
+                                  <https://github.com/nabijaczleweli/cargo-update/issues/11>"
+                    .to_string()),
+                    Message::Text("10.11.2016 10:48:04 AM: nabijaczleweli created comment to #12 on nabijaczleweli/cargo-update\n\
+                                  <https://github.com/nabijaczleweli/cargo-update/issues/12#issuecomment-259659314>"
+                    .to_string())]);
+}
+
+#[test]
+fn text() {
+    for ev in Event::parse(TWO_EVENTS).unwrap() {
+        assert_eq!(PlainRenderer.render(&ev, &RenderOptions::default()), Message::Text(PlainRenderer.text(&ev, &RenderOptions::default())));
+        assert_eq!(PlainRenderer.text(&ev, &RenderOptions::default()), post_text(&ev));
+    }
+}
+
+#[test]
+fn time() {
+    let options = RenderOptions {
+        time: TimeFormat {
+            timezone: Timezone::from_name("+01:00").unwrap(),
+            style: TimeStyle::from_name("%H:%M").unwrap(),
+        },
+        ..RenderOptions::default()
+    };
+    assert_eq!(PlainRenderer.text(&Event::parse(TWO_EVENTS).unwrap()[1], &options),
+               "11:48: nabijaczleweli created comment to #12 on nabijaczleweli/cargo-update\n\
+                <https://github.com/nabijaczleweli/cargo-update/issues/12#issuecomment-259659314>");
+}
+
+#[test]
+fn push() {
+    let mut ev = Event::parse(TWO_EVENTS).unwrap().remove(0);
+    ev.payload = EventPayload::from("PushEvent", &json::parse(PUSH).unwrap()).unwrap();
+
+    assert_eq!(PlainRenderer.text(&ev, &RenderOptions::default()),
+               "10.11.2016 12:42:18 AM: liigo pushed 1 commit by Nabijaczleweli Autouploader Bot to doc in nabijaczleweli/cargo-update\n\
+                4665079 Update docs for commits f69e263c6caf...40e07efdf879 - Nabijaczleweli Autouploader Bot \
+                <https://github.com/nabijaczleweli/cargo-update/commit/46650797f4aec4d373c647062eb3df288ee7b8f2>\n\
+                8eab689 Update manual for commits 1c8725dc2222...58998fadba13 - Nabijaczleweli Autouploader Bot \
+                <https://github.com/nabijaczleweli/cargo-update/commit/8eab6892203102e66a4b1eed26abc5a24cad8afe>\n\
+                <https://github.com/nabijaczleweli/cargo-update/compare/4d28f4b488f04c35135af7576ff5fd1f0ce53c7a...\
+                46650797f4aec4d373c647062eb3df288ee7b8f2>");
+}
+
+#[test]
+fn push_capped() {
+    let mut ev = Event::parse(TWO_EVENTS).unwrap().remove(0);
+    ev.payload = EventPayload::from("PushEvent", &json::parse(PUSH).unwrap()).unwrap();
+
+    let options = RenderOptions { max_commits: 1, ..RenderOptions::default() };
+    assert_eq!(PlainRenderer.text(&ev, &options),
+               "10.11.2016 12:42:18 AM: liigo pushed 1 commit by Nabijaczleweli Autouploader Bot to doc in nabijaczleweli/cargo-update\n\
+                4665079 Update docs for commits f69e263c6caf...40e07efdf879 - Nabijaczleweli Autouploader Bot \
+                <https://github.com/nabijaczleweli/cargo-update/commit/46650797f4aec4d373c647062eb3df288ee7b8f2>\n\
+                and 1 more commit\n\
+                <https://github.com/nabijaczleweli/cargo-update/compare/4d28f4b488f04c35135af7576ff5fd1f0ce53c7a...\
+                46650797f4aec4d373c647062eb3df288ee7b8f2>");
+
+    let options = RenderOptions { distinct_commits_only: true, ..RenderOptions::default() };
+    assert_eq!(PlainRenderer.text(&ev, &options).lines().count(), 3);
+
+    let options = RenderOptions { max_commits: 0, ..RenderOptions::default() };
+    assert_eq!(PlainRenderer.text(&ev, &options).lines().count(), 2);
+}
+
+#[test]
+fn previews() {
+    let events = Event::parse(TWO_EVENTS).unwrap();
+    let mut gollum = events[0].clone();
+    gollum.payload = EventPayload::from("GollumEvent", &json::parse(&GOLLUM.replacen("wiki/Packages", "wiki/Home", 1)).unwrap()).unwrap();
+
+    let mut previews = BTreeMap::new();
+    previews.insert("default".to_string(), Preview::All);
+    previews.insert("Gollum".to_string(), Preview::Last);
+    let options = RenderOptions { previews: previews, ..RenderOptions::default() };
+    assert_eq!(PlainRenderer.text(&events[0], &options),
+               "10.11.2016 12:42:18 AM: liigo opened #11 on nabijaczleweli/cargo-update: \"'unknown error occurred': It\"\n\
+                https://github.com/nabijaczleweli/cargo-update/issues/11");
+    assert_eq!(PlainRenderer.text(&gollum, &options).lines().skip(3).collect::<Vec<_>>(),
+               vec!["<https://github.com/clibs/clib/wiki/Home>", "https://github.com/clibs/clib/wiki/Packages"]);
+
+    let mut previews = BTreeMap::new();
+    previews.insert("Gollum".to_string(), Preview::Nth(0));
+    let options = RenderOptions { previews: previews, ..RenderOptions::default() };
+    assert_eq!(PlainRenderer.text(&events[0], &options), post_text(&events[0]));
+    assert_eq!(PlainRenderer.text(&gollum, &options).lines().skip(3).collect::<Vec<_>>(),
+               vec!["https://github.com/clibs/clib/wiki/Home", "<https://github.com/clibs/clib/wiki/Packages>"]);
+}
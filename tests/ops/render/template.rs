@@ -0,0 +1,209 @@
+use dishub::ops::render::{TemplateRenderer, RenderOptions, PlainRenderer, TimeFormat, TimeStyle, Renderer, Template, Message};
+use dishub::ops::{EventPayload, Event};
+use std::collections::BTreeMap;
+use self::super::TWO_EVENTS;
+use json;
+
+
+static PUSH_CO_AUTHORED: &'static str = include_str!("../../../test-data/push_co_authored_payload.json");
+static GOLLUM: &'static str = include_str!("../../../test-data/gollum_payload.json");
+static PUSH: &'static str = include_str!("../../../test-data/push_payload.json");
+
+
+#[test]
+fn values() {
+    let ev = &Event::parse(TWO_EVENTS).unwrap()[0];
+    assert_eq!(Template::parse("[{{time}}] {{actor}} ({{actor.login}}, {{actor.id}}) {{payload.action}} #{{payload.number}} in {{repo}}: {{payload.title}} \
+                                <{{url}}>")
+                   .unwrap()
+                   .render(ev, &RenderOptions::default()),
+               "[10.11.2016 12:42:18 AM] liigo (liigo, 346530) opened #11 in nabijaczleweli/cargo-update: 'unknown error occurred': It \
+                <https://github.com/nabijaczleweli/cargo-update/issues/11>");
+}
+
+#[test]
+fn summary() {
+    for ev in Event::parse(TWO_EVENTS).unwrap() {
+        assert_eq!(Template::parse("{{summary}}").unwrap().render(&ev, &RenderOptions::default()), ev.summary());
+    }
+}
+
+#[test]
+fn missing() {
+    let ev = &Event::parse(TWO_EVENTS).unwrap()[0];
+    assert_eq!(Template::parse("a{{payload.commits}}b{{nonexistant.value}}c{{org}}d").unwrap().render(ev, &RenderOptions::default()), "abcd");
+}
+
+#[test]
+fn conditionals() {
+    let ev = &Event::parse(TWO_EVENTS).unwrap()[0];
+    assert_eq!(Template::parse("{{#if payload.labels}}labelled{{else}}unlabelled{{/if}}, \
+                                {{#if payload.action == \"opened\"}}new{{/if}}\
+                                {{#if payload.action != \"opened\"}}old{{/if}}, \
+                                {{#if org}}org{{else}}{{#if payload.body}}body{{/if}}{{/if}}")
+                   .unwrap()
+                   .render(ev, &RenderOptions::default()),
+               "unlabelled, new, body");
+}
+
+#[test]
+fn commits() {
+    let mut ev = Event::parse(TWO_EVENTS).unwrap().remove(0);
+    ev.payload = EventPayload::from("PushEvent", &json::parse(PUSH_CO_AUTHORED).unwrap()).unwrap();
+
+    let len = match ev.payload {
+        EventPayload::Push { ref commits, .. } => commits.len(),
+        _ => unreachable!(),
+    };
+    let rendered = Template::parse("{{actor}} pushed to {{branch}} in {{repo}}:\n\
+                                    {{#each payload.commits}}{{@index}}: {{author_name}} in {{repo}}{{#if @last}}.{{else}};\n{{/if}}{{/each}}")
+        .unwrap()
+        .render(&ev, &RenderOptions::default());
+
+    let mut lines = rendered.lines();
+    assert!(lines.next().unwrap().starts_with("liigo pushed to "));
+    for (i, line) in lines.enumerate() {
+        assert!(line.starts_with(&format!("{}: ", i)));
+        assert!(line.ends_with(&format!(" in nabijaczleweli/cargo-update{}", if i + 1 == len { "." } else { ";" })));
+    }
+    assert_eq!(rendered.lines().count(), len + 1);
+}
+
+#[test]
+fn commit_messages() {
+    let mut ev = Event::parse(TWO_EVENTS).unwrap().remove(0);
+    ev.payload = EventPayload::from("PushEvent", &json::parse(PUSH_CO_AUTHORED).unwrap()).unwrap();
+    let options = RenderOptions { trusted: vec!["nabijaczleweli/cargo-update".to_string()], ..RenderOptions::default() };
+
+    let expected = "Install git packages with -g\n\nCloses #213.|Document -g|Update manual for commits 1c8725dc2222...58998fadba13|";
+    assert_eq!(Template::parse("{{#each payload.commits}}{{message}}|{{/each}}").unwrap().render(&ev, &options), expected);
+    assert_eq!(Template::parse("{{#each commits}}{{message}}|{{/each}}").unwrap().render(&ev, &options), expected);
+}
+
+#[test]
+fn gollum_pages() {
+    let mut ev = Event::parse(TWO_EVENTS).unwrap().remove(0);
+    ev.payload = EventPayload::from("GollumEvent", &json::parse(GOLLUM).unwrap()).unwrap();
+
+    let pages = match ev.payload {
+        EventPayload::Gollum { ref pages } => pages.clone(),
+        _ => unreachable!(),
+    };
+    assert_eq!(Template::parse("{{#each payload.pages}}{{#if @first}}{{else}} | {{/if}}{{action}} {{title}}{{/each}}")
+                   .unwrap()
+                   .render(&ev, &RenderOptions::default()),
+               pages.iter().map(|p| format!("{} {}", p.action, p.title)).collect::<Vec<_>>().join(" | "));
+}
+
+#[test]
+fn this() {
+    let template = Template::parse("{{#each labels}}[{{this}}]{{/each}} {{labels}}").unwrap();
+    assert_eq!(template.render_json(&object!{ "labels" => vec!["bug", "help wanted"] }), "[bug][help wanted] bug, help wanted");
+}
+
+#[test]
+fn source() {
+    assert_eq!(Template::parse("{{ actor }}, {{#if  org }}{{/if}}").unwrap().source(), "{{ actor }}, {{#if  org }}{{/if}}");
+}
+
+#[test]
+fn malformed() {
+    assert_eq!(Template::parse("{{actor"), Err("1:1: unclosed tag".to_string()));
+    assert_eq!(Template::parse("{{}}"), Err("1:1: empty tag".to_string()));
+    assert_eq!(Template::parse("a\n{{#if actor}}\n{{/each}}"), Err("3:1: unexpected {{/each}}".to_string()));
+    assert_eq!(Template::parse("{{/if}}"), Err("1:1: unexpected {{/if}}".to_string()));
+    assert_eq!(Template::parse("{{else}}"), Err("1:1: unexpected {{else}}".to_string()));
+    assert_eq!(Template::parse("ab{{#each payload.commits}}"), Err("1:3: unclosed {{#each}}".to_string()));
+    assert_eq!(Template::parse("{{#unless org}}{{/unless}}"), Err("1:1: unknown block {{#unless org}}".to_string()));
+    assert_eq!(Template::parse("{{#if action == opened}}{{/if}}"),
+               Err("1:1: expected a quoted string after ==, got opened".to_string()));
+    assert_eq!(Template::parse("{{payload..title}}"), Err("1:1: invalid name \"\" in payload..title".to_string()));
+    assert_eq!(Template::parse("ąę {{pay load}}"), Err("1:4: invalid name \"pay load\" in pay load".to_string()));
+}
+
+#[test]
+fn renderer() {
+    let events = Event::parse(TWO_EVENTS).unwrap();
+
+    let mut templates = BTreeMap::new();
+    templates.insert("Issues".to_string(), Template::parse("{{actor}} {{payload.action}} {{payload.title}}").unwrap());
+    let renderer = TemplateRenderer {
+        templates: templates,
+        fallback: Box::new(PlainRenderer),
+    };
+
+    assert_eq!(renderer.render(&events[0], &RenderOptions::default()), Message::Text("liigo opened 'unknown error occurred': It".to_string()));
+    assert_eq!(renderer.render(&events[1], &RenderOptions::default()), PlainRenderer.render(&events[1], &RenderOptions::default()));
+}
+
+#[test]
+fn time() {
+    let ev = &Event::parse(TWO_EVENTS).unwrap()[0];
+    let options = RenderOptions {
+        time: TimeFormat {
+            style: TimeStyle::Discord('f'),
+            ..TimeFormat::default()
+        },
+        ..RenderOptions::default()
+    };
+    assert_eq!(Template::parse("{{time}} ({{timestamp}})").unwrap().render(ev, &options), "<t:1478738538:f> (1478738538)");
+}
+
+#[test]
+fn escaped() {
+    let mut ev = Event::parse(TWO_EVENTS).unwrap().remove(0);
+    ev.repo.name = "nabijaczleweli/cargo_update".to_string();
+    let template = Template::parse("**{{repo}}**: {{payload.body}} <{{url}}>").unwrap();
+
+    assert_eq!(template.render(&ev, &RenderOptions::default()),
+               "**nabijaczleweli/cargo\\_update**: \\`\\`\\`C:\\\\Users\\\\liigo>cargo install-update\\`\\`\\` \
+                <https://github.com/nabijaczleweli/cargo_update/issues/11>");
+    assert_eq!(template.render(&ev,
+                               &RenderOptions {
+                                   trusted: vec!["nabijaczleweli/*".to_string()],
+                                   ..RenderOptions::default()
+                               }),
+               "**nabijaczleweli/cargo_update**: ```C:\\Users\\liigo>cargo install-update``` <https://github.com/nabijaczleweli/cargo_update/issues/11>");
+}
+
+#[test]
+fn escaped_url_like() {
+    let mut ev = Event::parse(TWO_EVENTS).unwrap().remove(0);
+    ev.repo.name = "nabijaczleweli/cargo_update".to_string();
+    if let EventPayload::Issues { ref mut title, .. } = ev.payload {
+        *title = "https://x @everyone **boom**".to_string();
+    }
+
+    assert_eq!(Template::parse("{{payload.title}} <{{url}}>").unwrap().render(&ev, &RenderOptions::default()),
+               "https://x @\u{200b}everyone \\*\\*boom\\*\\* <https://github.com/nabijaczleweli/cargo_update/issues/11>");
+}
+
+#[test]
+fn escaped_urls_each() {
+    let mut ev = Event::parse(TWO_EVENTS).unwrap().remove(0);
+    ev.repo.name = "nabijaczleweli/cargo_update".to_string();
+    if let EventPayload::Issues { ref mut labels, .. } = ev.payload {
+        *labels = vec!["https://x/good_first_issue".to_string()];
+    }
+
+    assert_eq!(Template::parse("{{#each urls}}<{{this}}>{{/each}} <{{url}}> {{#each payload.labels}}{{this}}{{/each}}")
+                   .unwrap()
+                   .render(&ev, &RenderOptions::default()),
+               "<https://github.com/nabijaczleweli/cargo_update/issues/11> <https://github.com/nabijaczleweli/cargo_update/issues/11> \
+                https://x/good\\_first\\_issue");
+}
+
+#[test]
+fn listed_commits() {
+    let mut ev = Event::parse(TWO_EVENTS).unwrap().remove(0);
+    ev.payload = EventPayload::from("PushEvent", &json::parse(PUSH).unwrap()).unwrap();
+
+    let template = Template::parse("{{#each commits}}[{{short_sha}}]({{url}}) {{title}} by {{author}}\n{{/each}}\
+                                    {{#if more_commits}}and {{more_commits}} more{{/if}}")
+        .unwrap();
+    assert_eq!(template.render(&ev, &RenderOptions { max_commits: 1, ..RenderOptions::default() }),
+               "[4665079](https://github.com/nabijaczleweli/cargo-update/commit/46650797f4aec4d373c647062eb3df288ee7b8f2) \
+                Update docs for commits f69e263c6caf...40e07efdf879 by Nabijaczleweli Autouploader Bot\n\
+                and 1 more");
+    assert_eq!(template.render(&ev, &RenderOptions { max_commits: 0, ..RenderOptions::default() }), "");
+}
@@ -0,0 +1,79 @@
+use dishub::ops::render::{TimeFormat, TimeStyle, Timezone};
+use chrono::{FixedOffset, DateTime, Local};
+
+
+#[test]
+fn default() {
+    assert_eq!(TimeFormat::default().format(&DateTime::parse_from_rfc3339("2016-11-10T08:42:18Z").unwrap()), "10.11.2016 08:42:18 AM");
+    assert_eq!(TimeFormat::default().format(&DateTime::parse_from_rfc3339("2016-11-10T20:42:18+05:00").unwrap()),
+               "10.11.2016 08:42:18 PM");
+}
+
+#[test]
+fn fixed() {
+    let time = TimeFormat {
+        timezone: Timezone::Fixed(FixedOffset::west(8 * 60 * 60)),
+        style: TimeStyle::Strftime("%Y-%m-%d %H:%M:%S %z".to_string()),
+    };
+    assert_eq!(time.format(&DateTime::parse_from_rfc3339("2016-11-10T08:42:18Z").unwrap()), "2016-11-10 00:42:18 -0800");
+    assert_eq!(time.format(&DateTime::parse_from_rfc3339("2016-11-10T05:42:18+05:00").unwrap()), "2016-11-09 16:42:18 -0800");
+}
+
+#[test]
+fn local() {
+    let created_at = DateTime::parse_from_rfc3339("2016-11-10T08:42:18Z").unwrap();
+    let time = TimeFormat {
+        timezone: Timezone::Local,
+        style: TimeStyle::Strftime("%d.%m.%Y %H:%M:%S".to_string()),
+    };
+    assert_eq!(time.format(&created_at), created_at.with_timezone(&Local).format("%d.%m.%Y %H:%M:%S").to_string());
+}
+
+#[test]
+fn discord() {
+    let created_at = DateTime::parse_from_rfc3339("2016-11-10T08:42:18Z").unwrap();
+    for style in "tTdDfFR".chars() {
+        for tz in &[Timezone::Original, Timezone::Local, Timezone::Fixed(FixedOffset::east(60 * 60))] {
+            let time = TimeFormat {
+                timezone: *tz,
+                style: TimeStyle::Discord(style),
+            };
+            assert_eq!(time.format(&created_at), format!("<t:1478767338:{}>", style));
+        }
+    }
+}
+
+#[test]
+fn timezone_names() {
+    for tz in &[Timezone::Original,
+                Timezone::Local,
+                Timezone::Fixed(FixedOffset::east(0)),
+                Timezone::Fixed(FixedOffset::east(2 * 60 * 60)),
+                Timezone::Fixed(FixedOffset::west(9 * 60 * 60 + 30 * 60))] {
+        assert_eq!(Timezone::from_name(&tz.name()), Some(*tz));
+    }
+}
+
+#[test]
+fn timezone_bad_names() {
+    for name in &["", "utc", "Europe/Warsaw", "+1", "+01:0", "+0100", "01:00", "+24:00", "-01:60", "+ab:cd"] {
+        assert_eq!(Timezone::from_name(name), None);
+    }
+}
+
+#[test]
+fn style_names() {
+    for style in &[TimeStyle::Discord('R'),
+                   TimeStyle::Discord('f'),
+                   TimeStyle::Strftime("%d.%m.%Y %r".to_string()),
+                   TimeStyle::Strftime("".to_string())] {
+        assert_eq!(TimeStyle::from_name(&style.name()), Some(style.clone()));
+    }
+}
+
+#[test]
+fn style_bad_names() {
+    for name in &["discord:", "discord:r", "discord:RR", "%", "%Q", "%d.%m.%"] {
+        assert_eq!(TimeStyle::from_name(name), None);
+    }
+}
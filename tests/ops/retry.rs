@@ -0,0 +1,76 @@
+use dishub::ops::retry::RetryPolicy;
+use std::time::Duration;
+use dishub::Error;
+
+
+fn policy() -> RetryPolicy {
+    RetryPolicy {
+        retries: 3,
+        initial_delay: Duration::from_millis(1),
+        max_delay: Duration::from_millis(3),
+    }
+}
+
+
+#[test]
+fn delay() {
+    let policy = policy();
+    assert_eq!((0..5).map(|r| policy.delay(r)).collect::<Vec<_>>(),
+               vec![Duration::from_millis(1), Duration::from_millis(2), Duration::from_millis(3), Duration::from_millis(3), Duration::from_millis(3)]);
+    assert_eq!(policy.delay(100), Duration::from_millis(3));
+}
+
+#[test]
+fn jittered_delay() {
+    let policy = RetryPolicy::default();
+    for &jitter in &[0.0, 0.25, 0.5, 0.999] {
+        let delay = policy.jittered_delay(3, jitter);
+        assert!(delay >= Duration::from_secs(4) && delay < Duration::from_secs(8), "{:?}", delay);
+    }
+}
+
+#[test]
+fn success() {
+    let mut out = Vec::new();
+    assert_eq!(policy().run(&mut out, || Ok(12)), Ok(12));
+    assert!(out.is_empty());
+}
+
+#[test]
+fn permanent() {
+    let mut out = Vec::new();
+    let mut attempts = 0;
+    assert_eq!(policy().run::<(), _, _>(&mut out, || {
+                   attempts += 1;
+                   Err(Error::LoginFailed("Discord"))
+               }),
+               Err(Error::LoginFailed("Discord")));
+    assert_eq!(attempts, 1);
+    assert!(out.is_empty());
+}
+
+#[test]
+fn transient() {
+    let mut out = Vec::new();
+    let mut attempts = 0;
+    assert_eq!(policy().run::<(), _, _>(&mut out, || {
+                   attempts += 1;
+                   Err(Error::DiscordServerError {
+                       desc: "event message",
+                       status: 502,
+                   })
+               }),
+               Err(Error::DiscordServerError {
+                   desc: "event message",
+                   status: 502,
+               }));
+    assert_eq!(attempts, 4);
+
+    let out = String::from_utf8(out).unwrap();
+    let lines: Vec<_> = out.lines().collect();
+    assert_eq!(lines.len(), 3);
+    for (i, line) in lines.into_iter().enumerate() {
+        assert!(line.starts_with(&format!("Retry {}/3 in 0.00", i + 1)), "{}", line);
+        assert!(line.ends_with("s: Discord failed to handle the event message (502)."), "{}", line);
+    }
+}